@@ -0,0 +1,96 @@
+use std::{fs, path::Path};
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+
+/// 网关配置
+///
+/// ```toml
+/// listen = "0.0.0.0:8080"
+/// cache_ttl = 300
+/// rate_limit = 2.0
+///
+/// [[keys]]
+/// name = "recommend-service"
+/// key = "secret"
+/// token = "..."
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// 监听地址
+    pub listen: String,
+    /// 上游 API 地址
+    pub upstream: String,
+    /// 请求上游时使用的 User-Agent，为空时使用客户端默认值
+    pub user_agent: Option<String>,
+    /// 缓存有效期（秒），为 0 时不缓存
+    pub cache_ttl: u64,
+    /// 缓存的最大条目数
+    pub cache_capacity: usize,
+    /// 每秒允许发往上游的请求数
+    pub rate_limit: f64,
+    /// 令牌桶容量，允许的瞬时突发请求数
+    pub burst: u32,
+    /// 上游返回 429/5xx 或连接失败时的最大重试次数
+    pub retries: u32,
+    /// 首次重试前的等待时间（毫秒），之后逐次翻倍
+    pub retry_backoff_ms: u64,
+    /// 是否允许不带 API Key 的请求（此时透传请求自带的 Authorization 头）
+    pub allow_anonymous: bool,
+    /// API Key 列表
+    pub keys: Vec<ApiKey>,
+}
+
+/// API Key
+///
+/// 请求通过`X-API-Key`头携带，网关以对应的令牌访问上游
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKey {
+    /// 名称，用于监控指标
+    pub name: String,
+    /// 密钥
+    pub key: String,
+    /// 注入的 Bangumi 访问令牌
+    pub token: Option<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            listen: "127.0.0.1:8080".to_string(),
+            upstream: "https://api.bgm.tv".to_string(),
+            user_agent: None,
+            cache_ttl: 300,
+            cache_capacity: 10_000,
+            rate_limit: 2.0,
+            burst: 5,
+            retries: 3,
+            retry_backoff_ms: 500,
+            allow_anonymous: true,
+            keys: Vec::new(),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// 读取配置文件
+    pub fn load(path: &Path) -> Result<ServerConfig> {
+        match fs::read_to_string(path) {
+            Ok(text) => ServerConfig::parse(&text),
+            Err(error) => bail!(format!("读取配置文件{}失败: {error}", path.display())),
+        }
+    }
+
+    /// 解析配置文件内容
+    pub fn parse(text: &str) -> Result<ServerConfig> {
+        let config: ServerConfig = match toml::from_str(text) {
+            Ok(config) => config,
+            Err(error) => bail!(format!("配置文件格式错误: {error}")),
+        };
+        if config.rate_limit <= 0.0 || config.burst == 0 {
+            bail!("rate_limit 与 burst 必须大于 0");
+        }
+        Ok(config)
+    }
+}
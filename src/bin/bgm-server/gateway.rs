@@ -0,0 +1,474 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    hash::{BuildHasher, RandomState},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, HeaderValue, Method, StatusCode, Uri, header},
+    response::{IntoResponse, Response},
+    routing::{any, get},
+};
+use bangumi_api::common::{
+    model::BangumiClient,
+    transport::{
+        HttpRequest, RateLimitTransport, ReqwestTransport, RetryTransport, SingleFlightTransport,
+        Sleep, is_idempotent,
+    },
+};
+
+use crate::config::ServerConfig;
+
+/// 携带 API Key 的请求头
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// 标记缓存命中情况的响应头
+pub const CACHE_HEADER: &str = "x-cache";
+
+/// 缓存的上游响应
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    status: StatusCode,
+    content_type: Option<HeaderValue>,
+    body: Bytes,
+    expires: Instant,
+}
+
+/// 上游响应缓存
+///
+/// 键以调用方身份开头，不同令牌的响应互不可见。
+/// 网关需要把上游的原始响应（含非2xx的错误体）原样返回给调用方，
+/// 而`store::CachedClient`按类型把解析后的实体写入SQLite，且只缓存成功结果，
+/// 因此这里按请求保存原始响应体
+#[derive(Debug)]
+struct Cache {
+    entries: HashMap<String, CachedResponse>,
+    capacity: usize,
+}
+
+impl Cache {
+    fn get(&mut self, key: &str, now: Instant) -> Option<CachedResponse> {
+        match self.entries.get(key) {
+            Some(entry) if entry.expires > now => Some(entry.clone()),
+            Some(_) => {
+                self.entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// 写入缓存，容量已满时先清除过期条目，仍不足则淘汰最早过期的条目
+    fn insert(&mut self, key: String, response: CachedResponse, now: Instant) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            self.entries.retain(|_, entry| entry.expires > now);
+            if self.entries.len() >= self.capacity
+                && let Some(oldest) = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires)
+                    .map(|(key, _)| key.clone())
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(key, response);
+    }
+
+    /// 清除某个身份的全部缓存
+    fn invalidate(&mut self, identity: &str) {
+        let prefix = format!("{identity}\n");
+        self.entries.retain(|key, _| !key.starts_with(&prefix));
+    }
+}
+
+/// 监控指标
+#[derive(Debug, Default)]
+struct Metrics {
+    requests: AtomicU64,
+    unauthorized: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    upstream_errors: AtomicU64,
+    /// 各调用方的请求数
+    callers: Mutex<BTreeMap<String, u64>>,
+    /// 返回给调用方的各状态码数量
+    statuses: Mutex<BTreeMap<u16, u64>>,
+}
+
+impl Metrics {
+    fn count_caller(&self, name: &str) {
+        *self
+            .callers
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_default() += 1;
+    }
+
+    fn count_status(&self, status: StatusCode) {
+        *self
+            .statuses
+            .lock()
+            .unwrap()
+            .entry(status.as_u16())
+            .or_default() += 1;
+    }
+
+    /// 以 Prometheus 文本格式输出，上游请求相关的计数由传输层提供
+    fn render(&self, cache_entries: usize, upstream: &UpstreamTransport) -> String {
+        let mut text = String::new();
+        let limiter = upstream.inner();
+        let load = |value: &AtomicU64| value.load(Ordering::Relaxed);
+        let counters = [
+            (
+                "bgm_gateway_requests_total",
+                "收到的请求数",
+                load(&self.requests),
+            ),
+            (
+                "bgm_gateway_unauthorized_total",
+                "API Key 无效被拒绝的请求数",
+                load(&self.unauthorized),
+            ),
+            (
+                "bgm_gateway_cache_hits_total",
+                "缓存命中数",
+                load(&self.cache_hits),
+            ),
+            (
+                "bgm_gateway_cache_misses_total",
+                "缓存未命中数",
+                load(&self.cache_misses),
+            ),
+            (
+                "bgm_gateway_upstream_requests_total",
+                "发往上游的请求数（含重试）",
+                limiter.sent(),
+            ),
+            (
+                "bgm_gateway_upstream_retries_total",
+                "重试次数",
+                upstream.retried(),
+            ),
+            (
+                "bgm_gateway_upstream_errors_total",
+                "重试后仍无法连接上游的请求数",
+                load(&self.upstream_errors),
+            ),
+            (
+                "bgm_gateway_rate_limit_wait_milliseconds_total",
+                "限流累计等待时间",
+                limiter.waited().as_millis() as u64,
+            ),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(text, "# HELP {name} {help}");
+            let _ = writeln!(text, "# TYPE {name} counter");
+            let _ = writeln!(text, "{name} {value}");
+        }
+
+        let _ = writeln!(text, "# HELP bgm_gateway_cache_entries 当前缓存条目数");
+        let _ = writeln!(text, "# TYPE bgm_gateway_cache_entries gauge");
+        let _ = writeln!(text, "bgm_gateway_cache_entries {cache_entries}");
+
+        let _ = writeln!(
+            text,
+            "# HELP bgm_gateway_caller_requests_total 各调用方的请求数"
+        );
+        let _ = writeln!(text, "# TYPE bgm_gateway_caller_requests_total counter");
+        for (name, count) in self.callers.lock().unwrap().iter() {
+            let _ = writeln!(
+                text,
+                "bgm_gateway_caller_requests_total{{caller=\"{}\"}} {count}",
+                name.replace('\\', "\\\\").replace('"', "\\\"")
+            );
+        }
+
+        let _ = writeln!(text, "# HELP bgm_gateway_responses_total 各状态码的响应数");
+        let _ = writeln!(text, "# TYPE bgm_gateway_responses_total counter");
+        for (status, count) in self.statuses.lock().unwrap().iter() {
+            let _ = writeln!(
+                text,
+                "bgm_gateway_responses_total{{status=\"{status}\"}} {count}"
+            );
+        }
+        text
+    }
+}
+
+/// 调用方
+struct Caller<'a> {
+    /// 指标与缓存键中使用的身份
+    identity: String,
+    client: &'a BangumiClient,
+    /// 匿名调用方自带的 Authorization 头
+    authorization: Option<HeaderValue>,
+}
+
+/// 发往上游的传输层：先重试再限流，每次重试都占用限流配额
+type UpstreamTransport = RetryTransport<RateLimitTransport<ReqwestTransport>>;
+
+/// 缓存网关
+///
+/// 通过`BangumiClient`转发`/v0`请求：为每个 API Key 注入对应的访问令牌，
+/// 缓存 GET 与搜索请求的成功响应。限流与重试由共用的传输层完成：
+/// [`RateLimitTransport`] 限制发往上游的总请求速率，[`RetryTransport`]
+/// 在上游返回 429/5xx 或请求失败时按指数退避重试幂等请求，
+/// 相同的并发 GET 请求经 [`SingleFlightTransport`] 合并
+pub struct Gateway {
+    config: ServerConfig,
+    /// 匿名请求使用的客户端
+    anonymous: BangumiClient,
+    /// API Key 到名称与客户端的映射
+    clients: HashMap<String, (String, BangumiClient)>,
+    cache: Mutex<Cache>,
+    upstream: Arc<UpstreamTransport>,
+    metrics: Metrics,
+    /// 匿名令牌的散列密钥，每个网关随机生成，缓存键中不保存令牌原文
+    token_hasher: RandomState,
+}
+
+impl Gateway {
+    pub fn new(config: ServerConfig) -> Gateway {
+        // 所有客户端共用同一个连接池与限流配额
+        let sleep: Sleep = Arc::new(|duration| Box::pin(tokio::time::sleep(duration)));
+        let upstream = Arc::new(
+            RetryTransport::new(
+                RateLimitTransport::new(
+                    ReqwestTransport::default(),
+                    config.rate_limit,
+                    config.burst,
+                    sleep.clone(),
+                ),
+                config.retries,
+                Duration::from_millis(config.retry_backoff_ms),
+                sleep,
+            )
+            .with_idempotent(idempotent),
+        );
+        let transport = Arc::new(SingleFlightTransport::new(upstream.clone()));
+        let default = BangumiClient::default();
+        let client = |access_token: Option<String>| {
+            BangumiClient::with_transport(
+                config.upstream.trim_end_matches('/').to_string(),
+                config.user_agent.clone().or(default.user_agent.clone()),
+                access_token,
+                transport.clone(),
+            )
+        };
+        let clients = config
+            .keys
+            .iter()
+            .map(|key| {
+                (
+                    key.key.clone(),
+                    (key.name.clone(), client(key.token.clone())),
+                )
+            })
+            .collect();
+        Gateway {
+            anonymous: client(None),
+            clients,
+            cache: Mutex::new(Cache {
+                entries: HashMap::new(),
+                capacity: config.cache_capacity,
+            }),
+            upstream,
+            metrics: Metrics::default(),
+            token_hasher: RandomState::new(),
+            config,
+        }
+    }
+
+    /// 网关路由：`/v0/*`转发上游，`/metrics`输出监控指标
+    pub fn router(self: Arc<Self>) -> Router {
+        Router::new()
+            .route("/metrics", get(metrics))
+            .route("/v0/{*path}", any(proxy))
+            .with_state(self)
+    }
+
+    /// 按请求头识别调用方，API Key 无效或不允许匿名访问时返回None
+    fn caller(&self, headers: &HeaderMap) -> Option<Caller<'_>> {
+        if let Some(key) = headers.get(API_KEY_HEADER) {
+            let (name, client) = self.clients.get(key.to_str().ok()?)?;
+            return Some(Caller {
+                identity: name.clone(),
+                client,
+                authorization: None,
+            });
+        }
+        if !self.config.allow_anonymous {
+            return None;
+        }
+        let authorization = headers.get(header::AUTHORIZATION).cloned();
+        // 自带令牌的匿名请求按令牌的散列区分缓存
+        let identity = match &authorization {
+            Some(value) => format!(
+                "anonymous:{:016x}",
+                self.token_hasher.hash_one(value.as_bytes())
+            ),
+            None => "anonymous".to_string(),
+        };
+        Some(Caller {
+            identity,
+            client: &self.anonymous,
+            authorization,
+        })
+    }
+
+    /// 处理一个`/v0`请求并记录响应状态码
+    async fn forward(&self, method: Method, uri: Uri, headers: HeaderMap, body: Bytes) -> Response {
+        self.metrics.requests.fetch_add(1, Ordering::Relaxed);
+        let response = self.handle(method, uri, headers, body).await;
+        self.metrics.count_status(response.status());
+        response
+    }
+
+    async fn handle(&self, method: Method, uri: Uri, headers: HeaderMap, body: Bytes) -> Response {
+        let Some(caller) = self.caller(&headers) else {
+            self.metrics.unauthorized.fetch_add(1, Ordering::Relaxed);
+            self.metrics.count_caller("unauthorized");
+            return error_response(StatusCode::UNAUTHORIZED, "Unauthorized", "无效的 API Key");
+        };
+        self.metrics.count_caller(&caller.identity_name());
+
+        let path = uri
+            .path_and_query()
+            .map_or(uri.path(), |path| path.as_str());
+        let cacheable = self.config.cache_ttl > 0
+            && (method == Method::GET
+                || (method == Method::POST && uri.path().starts_with("/v0/search/")));
+        let cache_key = format!(
+            "{}\n{method} {path}\n{}",
+            caller.identity,
+            String::from_utf8_lossy(&body)
+        );
+        if cacheable {
+            let cached = self.cache.lock().unwrap().get(&cache_key, Instant::now());
+            if let Some(cached) = cached {
+                self.metrics.cache_hits.fetch_add(1, Ordering::Relaxed);
+                return cached_response(cached, "HIT");
+            }
+            self.metrics.cache_misses.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let url = format!("{}{path}", caller.client.base_path);
+        let mut request = caller.client.request_builder(method.clone(), &url);
+        if let Some(authorization) = &caller.authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        if let Some(content_type) = headers.get(header::CONTENT_TYPE) {
+            request = request.header(header::CONTENT_TYPE, content_type);
+        }
+        // 直接交给传输层而不是调用客户端的接口方法：网关需原样转发任意路径，
+        // 并把上游的错误响应透传给调用方，而不是解析为`BangumiError`
+        let response = match request.body(body).build() {
+            Ok(request) => caller.client.transport.send(request).await,
+            Err(error) => Err(error),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(error) => {
+                self.metrics.upstream_errors.fetch_add(1, Ordering::Relaxed);
+                return error_response(
+                    StatusCode::BAD_GATEWAY,
+                    "Bad Gateway",
+                    &format!("上游请求失败: {error}"),
+                );
+            }
+        };
+
+        let status = response.status;
+        let content_type = response.headers.get(header::CONTENT_TYPE).cloned();
+        let cached = CachedResponse {
+            status,
+            content_type,
+            body: response.body,
+            expires: Instant::now() + Duration::from_secs(self.config.cache_ttl),
+        };
+        let mut cache = self.cache.lock().unwrap();
+        if cacheable && status == StatusCode::OK {
+            cache.insert(cache_key, cached.clone(), Instant::now());
+        } else if !cacheable && status.is_success() {
+            // 修改操作成功后丢弃该调用方的缓存，避免读到旧的收藏状态
+            cache.invalidate(&caller.identity);
+        }
+        drop(cache);
+        cached_response(cached, if cacheable { "MISS" } else { "BYPASS" })
+    }
+}
+
+impl Caller<'_> {
+    /// 指标中使用的名称，不暴露匿名调用方的令牌
+    fn identity_name(&self) -> String {
+        if self.authorization.is_some() {
+            "anonymous".to_string()
+        } else {
+            self.identity.clone()
+        }
+    }
+}
+
+/// 幂等请求与搜索请求可以重试
+fn idempotent(request: &HttpRequest) -> bool {
+    is_idempotent(request)
+        || (request.method == Method::POST && request.url.contains("/v0/search/"))
+}
+
+fn cached_response(cached: CachedResponse, cache: &'static str) -> Response {
+    let mut response = (cached.status, cached.body).into_response();
+    if let Some(content_type) = cached.content_type {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type);
+    }
+    response
+        .headers_mut()
+        .insert(CACHE_HEADER, HeaderValue::from_static(cache));
+    response
+}
+
+/// 与 Bangumi API 错误格式一致的错误响应
+fn error_response(status: StatusCode, title: &str, description: &str) -> Response {
+    let body = serde_json::json!({
+        "title": title,
+        "description": description,
+        "details": null,
+    });
+    (status, axum::Json(body)).into_response()
+}
+
+async fn proxy(
+    State(gateway): State<Arc<Gateway>>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    gateway.forward(method, uri, headers, body).await
+}
+
+async fn metrics(State(gateway): State<Arc<Gateway>>) -> Response {
+    let entries = gateway.cache.lock().unwrap().entries.len();
+    (
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        gateway.metrics.render(entries, &gateway.upstream),
+    )
+        .into_response()
+}
@@ -0,0 +1,44 @@
+//! Bangumi API 缓存网关
+//!
+//! 对外提供与 api.bgm.tv 相同的`/v0`接口，多个内部服务共用同一份缓存与限流配额。
+//! 配置文件路径由第一个命令行参数或`BANGUMI_SERVER_CONFIG`环境变量指定，
+//! 未指定时使用默认配置，见 [`config::ServerConfig`]
+mod config;
+mod gateway;
+
+#[cfg(test)]
+mod test;
+
+use std::{env, path::PathBuf, process::ExitCode, sync::Arc};
+
+use anyhow::Result;
+
+use config::ServerConfig;
+use gateway::Gateway;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("错误: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run() -> Result<()> {
+    let path = env::args_os()
+        .nth(1)
+        .or_else(|| env::var_os("BANGUMI_SERVER_CONFIG"))
+        .map(PathBuf::from);
+    let config = match path {
+        Some(path) => ServerConfig::load(&path)?,
+        None => ServerConfig::default(),
+    };
+    let listener = tokio::net::TcpListener::bind(&config.listen).await?;
+    eprintln!("监听 {}，上游 {}", listener.local_addr()?, config.upstream);
+    let router = Arc::new(Gateway::new(config)).router();
+    axum::serve(listener, router).await?;
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Instant,
+};
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::IntoResponse,
+    routing::{get, patch, post},
+};
+use serde_json::{Value, json};
+
+use crate::{
+    config::ServerConfig,
+    gateway::{API_KEY_HEADER, CACHE_HEADER, Gateway},
+};
+
+const CONFIG: &str = r#"
+upstream = "http://127.0.0.1:1"
+cache_ttl = 60
+rate_limit = 1000.0
+burst = 100
+retry_backoff_ms = 5
+allow_anonymous = false
+
+[[keys]]
+name = "recommend"
+key = "key-a"
+token = "token-a"
+
+[[keys]]
+name = "stats"
+key = "key-b"
+"#;
+
+/// 模拟上游的请求计数
+#[derive(Default)]
+struct Upstream {
+    hits: AtomicU32,
+    flaky: AtomicU32,
+}
+
+fn authorization(headers: &HeaderMap) -> Value {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .map_or(Value::Null, |value| json!(value))
+}
+
+/// 启动模拟的 api.bgm.tv
+async fn upstream() -> (String, Arc<Upstream>) {
+    let state = Arc::new(Upstream::default());
+    let router =
+        Router::new()
+            .route(
+                "/v0/subjects/{id}",
+                get(
+                    |State(state): State<Arc<Upstream>>,
+                     Path(id): Path<u32>,
+                     headers: HeaderMap| async move {
+                        state.hits.fetch_add(1, Ordering::SeqCst);
+                        Json(json!({"id": id, "authorization": authorization(&headers)}))
+                    },
+                ),
+            )
+            .route(
+                "/v0/episodes/{id}",
+                get(|State(state): State<Arc<Upstream>>| async move {
+                    // 前两次返回 503
+                    if state.flaky.fetch_add(1, Ordering::SeqCst) < 2 {
+                        return (
+                            StatusCode::SERVICE_UNAVAILABLE,
+                            [(header::RETRY_AFTER, "0")],
+                        )
+                            .into_response();
+                    }
+                    Json(json!({"id": 1})).into_response()
+                }),
+            )
+            .route(
+                "/v0/search/subjects",
+                post(
+                    |State(state): State<Arc<Upstream>>, body: String| async move {
+                        state.hits.fetch_add(1, Ordering::SeqCst);
+                        Json(json!({"request": body}))
+                    },
+                ),
+            )
+            .route(
+                "/v0/users/-/collections/{id}",
+                patch(|| async { StatusCode::NO_CONTENT }),
+            )
+            .with_state(state.clone());
+    (serve(router).await, state)
+}
+
+async fn serve(router: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    format!("http://{address}")
+}
+
+async fn gateway(config: &str, upstream: &str) -> String {
+    let mut config = ServerConfig::parse(config).unwrap();
+    config.upstream = upstream.to_string();
+    serve(Arc::new(Gateway::new(config)).router()).await
+}
+
+#[test]
+fn test_parse_config() {
+    let config = ServerConfig::parse(CONFIG).unwrap();
+    assert_eq!(config.keys.len(), 2);
+    assert_eq!(config.keys[0].token.as_deref(), Some("token-a"));
+    assert_eq!(config.keys[1].token, None);
+    // 未填写的字段使用默认值
+    assert_eq!(config.retries, 3);
+    assert_eq!(config.listen, "127.0.0.1:8080");
+    assert!(ServerConfig::parse("rate_limit = 0.0").is_err());
+}
+
+#[tokio::test]
+async fn test_cache_and_token_injection() {
+    let (upstream, state) = upstream().await;
+    let gateway = gateway(CONFIG, &upstream).await;
+    let http = reqwest::Client::new();
+    let get = |path: &str, key: &str| {
+        http.get(format!("{gateway}{path}"))
+            .header(API_KEY_HEADER, key)
+            .send()
+    };
+
+    let response = get("/v0/subjects/1", "key-a").await.unwrap();
+    assert_eq!(response.headers()[CACHE_HEADER], "MISS");
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["authorization"], "Bearer token-a");
+
+    let response = get("/v0/subjects/1", "key-a").await.unwrap();
+    assert_eq!(response.headers()[CACHE_HEADER], "HIT");
+    assert_eq!(response.json::<Value>().await.unwrap(), body);
+    assert_eq!(state.hits.load(Ordering::SeqCst), 1);
+
+    // 不同的 API Key 不共享缓存，未配置令牌时不发送 Authorization
+    let body: Value = get("/v0/subjects/1", "key-b")
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["authorization"], Value::Null);
+    assert_eq!(state.hits.load(Ordering::SeqCst), 2);
+
+    // 搜索请求按请求体缓存
+    for keyword in ["a", "a", "b"] {
+        let response = http
+            .post(format!("{gateway}/v0/search/subjects"))
+            .header(API_KEY_HEADER, "key-a")
+            .json(&json!({"keyword": keyword}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    assert_eq!(state.hits.load(Ordering::SeqCst), 4);
+
+    // 修改操作成功后清除该调用方的缓存
+    let response = http
+        .patch(format!("{gateway}/v0/users/-/collections/1"))
+        .header(API_KEY_HEADER, "key-a")
+        .json(&json!({"rate": 8}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[CACHE_HEADER], "BYPASS");
+    let response = get("/v0/subjects/1", "key-a").await.unwrap();
+    assert_eq!(response.headers()[CACHE_HEADER], "MISS");
+    let response = get("/v0/subjects/1", "key-b").await.unwrap();
+    assert_eq!(response.headers()[CACHE_HEADER], "HIT");
+}
+
+#[tokio::test]
+async fn test_retry_auth_and_metrics() {
+    let (upstream, state) = upstream().await;
+    let gateway = gateway(CONFIG, &upstream).await;
+    let http = reqwest::Client::new();
+
+    let response = http
+        .get(format!("{gateway}/v0/episodes/1"))
+        .header(API_KEY_HEADER, "key-b")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(state.flaky.load(Ordering::SeqCst), 3);
+
+    // 不允许匿名访问，无效的 API Key 被拒绝
+    let response = http
+        .get(format!("{gateway}/v0/subjects/1"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = http
+        .get(format!("{gateway}/v0/subjects/1"))
+        .header(API_KEY_HEADER, "wrong")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(state.hits.load(Ordering::SeqCst), 0);
+
+    let metrics = http
+        .get(format!("{gateway}/metrics"))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    for line in [
+        "bgm_gateway_requests_total 3",
+        "bgm_gateway_unauthorized_total 2",
+        "bgm_gateway_upstream_requests_total 3",
+        "bgm_gateway_upstream_retries_total 2",
+        "bgm_gateway_cache_entries 1",
+        "bgm_gateway_caller_requests_total{caller=\"stats\"} 1",
+        "bgm_gateway_responses_total{status=\"401\"} 2",
+    ] {
+        assert!(metrics.lines().any(|metric| metric == line), "{line}");
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limit() {
+    let (upstream, _) = upstream().await;
+    let config = CONFIG.replace(
+        "rate_limit = 1000.0\nburst = 100",
+        "rate_limit = 20.0\nburst = 1",
+    );
+    let gateway = gateway(&config, &upstream).await;
+    let http = reqwest::Client::new();
+
+    let start = Instant::now();
+    for id in 1..=3 {
+        let response = http
+            .get(format!("{gateway}/v0/subjects/{id}"))
+            .header(API_KEY_HEADER, "key-a")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    // 第一次请求消耗突发额度，之后每次至少等待 50ms
+    assert!(start.elapsed().as_millis() >= 90);
+}
@@ -0,0 +1,364 @@
+use bangumi_api::module::{
+    collection::model::{CollectionEpisode, CollectionEpisodeType, CollectionSubject},
+    subject::model::{DailyCalendarItem, Subject, SubjectSmall},
+};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// 章节格子每行的列数
+pub const GRID_COLUMNS: usize = 10;
+
+/// 北京时间相对UTC的偏移（秒），放送日历按北京时间划分星期
+const UTC_OFFSET: i64 = 8 * 3600;
+
+/// 标签页
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    /// 在看列表
+    Doing,
+    /// 今日放送
+    Calendar,
+    /// 条目搜索
+    Search,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 3] = [Tab::Doing, Tab::Calendar, Tab::Search];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Tab::Doing => "在看",
+            Tab::Calendar => "今日放送",
+            Tab::Search => "搜索",
+        }
+    }
+}
+
+/// 按键处理后需要执行的网络操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    None,
+    Quit,
+    /// 加载在看列表
+    LoadDoing,
+    /// 加载今日放送
+    LoadCalendar,
+    /// 搜索条目
+    Search(String),
+    /// 打开条目的章节格子
+    OpenEpisodes {
+        subject_id: u32,
+        title: String,
+    },
+    /// 修改单集的收藏状态
+    SetEpisode {
+        episode_id: u32,
+        r#type: CollectionEpisodeType,
+    },
+}
+
+/// 条目的章节格子
+#[derive(Debug, Clone)]
+pub struct EpisodeGrid {
+    pub subject_id: u32,
+    pub title: String,
+    pub episodes: Vec<CollectionEpisode>,
+    pub selected: usize,
+}
+
+/// 搜索页状态
+#[derive(Debug, Clone, Default)]
+pub struct SearchView {
+    /// 输入框内容
+    pub input: String,
+    /// 是否正在输入
+    pub editing: bool,
+    pub results: Vec<Subject>,
+    pub selected: usize,
+}
+
+/// 界面状态
+///
+/// 只记录数据与选中位置，网络请求由主循环根据 [`Action`] 执行后回填
+#[derive(Debug, Clone)]
+pub struct App {
+    pub tab: Tab,
+    pub doing: Vec<CollectionSubject>,
+    pub doing_selected: usize,
+    /// 今日星期的中文名
+    pub weekday: String,
+    pub calendar: Vec<SubjectSmall>,
+    pub calendar_selected: usize,
+    pub search: SearchView,
+    /// 打开的章节格子，显示在当前标签页之上
+    pub episodes: Option<EpisodeGrid>,
+    /// 状态栏消息
+    pub status: String,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        App {
+            tab: Tab::Doing,
+            doing: Vec::new(),
+            doing_selected: 0,
+            weekday: String::new(),
+            calendar: Vec::new(),
+            calendar_selected: 0,
+            search: SearchView::default(),
+            episodes: None,
+            status: String::new(),
+        }
+    }
+}
+
+/// 按UTC时间戳计算北京时间的星期（周一为1，周日为7）
+pub fn weekday_of(timestamp: i64) -> u8 {
+    let days = (timestamp + UTC_OFFSET).div_euclid(86400);
+    // 1970-01-01 为星期四
+    ((days + 3).rem_euclid(7) + 1) as u8
+}
+
+/// 在列表中上下移动选中项
+fn step(selected: usize, len: usize, down: bool) -> usize {
+    if len == 0 {
+        0
+    } else if down {
+        (selected + 1).min(len - 1)
+    } else {
+        selected.saturating_sub(1)
+    }
+}
+
+fn display_name(name: &str, name_cn: &str) -> String {
+    if name_cn.is_empty() {
+        name.to_string()
+    } else {
+        name_cn.to_string()
+    }
+}
+
+impl App {
+    pub fn set_doing(&mut self, doing: Vec<CollectionSubject>) {
+        self.doing_selected = self.doing_selected.min(doing.len().saturating_sub(1));
+        self.status = format!("在看 {} 部", doing.len());
+        self.doing = doing;
+    }
+
+    /// 从每日放送中取出指定星期的条目
+    pub fn set_calendar(&mut self, calendar: Vec<DailyCalendarItem>, weekday: u8) {
+        let day = calendar.into_iter().find(|day| day.weekday.id == weekday);
+        self.weekday = day
+            .as_ref()
+            .map(|day| day.weekday.cn.clone())
+            .unwrap_or_default();
+        self.calendar = day.map(|day| day.items).unwrap_or_default();
+        self.calendar_selected = 0;
+        self.status = format!("{}放送 {} 部", self.weekday, self.calendar.len());
+    }
+
+    pub fn set_results(&mut self, results: Vec<Subject>) {
+        self.status = format!("找到 {} 个条目", results.len());
+        self.search.results = results;
+        self.search.selected = 0;
+    }
+
+    pub fn open_episodes(
+        &mut self,
+        subject_id: u32,
+        title: String,
+        episodes: Vec<CollectionEpisode>,
+    ) {
+        self.status = format!("{title}: 共 {} 集", episodes.len());
+        self.episodes = Some(EpisodeGrid {
+            subject_id,
+            title,
+            episodes,
+            selected: 0,
+        });
+    }
+
+    /// 回填单集收藏状态，并同步在看列表中的进度
+    pub fn set_episode(&mut self, episode_id: u32, r#type: CollectionEpisodeType) {
+        let Some(grid) = &mut self.episodes else {
+            return;
+        };
+        if let Some(episode) = grid
+            .episodes
+            .iter_mut()
+            .find(|episode| episode.episode.id == episode_id)
+        {
+            episode.r#type = r#type;
+        }
+        let watched = grid
+            .episodes
+            .iter()
+            .filter(|episode| episode.r#type == CollectionEpisodeType::Done)
+            .count() as u32;
+        if let Some(collection) = self
+            .doing
+            .iter_mut()
+            .find(|collection| collection.subject_id == grid.subject_id)
+        {
+            collection.ep_status = watched;
+        }
+        self.status = format!("{}: 已看 {watched} 集", grid.title);
+    }
+
+    pub fn set_error(&mut self, error: anyhow::Error) {
+        self.status = format!("错误: {error:#}");
+    }
+
+    /// 处理按键，返回需要执行的网络操作
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+        if self.episodes.is_some() {
+            return self.handle_grid_key(key);
+        }
+        if self.tab == Tab::Search && self.search.editing {
+            return self.handle_input_key(key);
+        }
+
+        match key.code {
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Tab => return self.switch(Tab::ALL[(self.tab as usize + 1) % Tab::ALL.len()]),
+            KeyCode::BackTab => {
+                return self
+                    .switch(Tab::ALL[(self.tab as usize + Tab::ALL.len() - 1) % Tab::ALL.len()]);
+            }
+            KeyCode::Char('1') => return self.switch(Tab::Doing),
+            KeyCode::Char('2') => return self.switch(Tab::Calendar),
+            KeyCode::Char('3') => return self.switch(Tab::Search),
+            KeyCode::Char('r') => {
+                return match self.tab {
+                    Tab::Doing => Action::LoadDoing,
+                    Tab::Calendar => Action::LoadCalendar,
+                    Tab::Search if !self.search.input.is_empty() => {
+                        Action::Search(self.search.input.clone())
+                    }
+                    Tab::Search => Action::None,
+                };
+            }
+            KeyCode::Char('/') | KeyCode::Char('i') if self.tab == Tab::Search => {
+                self.search.editing = true;
+                return Action::None;
+            }
+            _ => {}
+        }
+
+        let down = match key.code {
+            KeyCode::Down | KeyCode::Char('j') => true,
+            KeyCode::Up | KeyCode::Char('k') => false,
+            KeyCode::Enter => return self.open_selected(),
+            _ => return Action::None,
+        };
+        match self.tab {
+            Tab::Doing => self.doing_selected = step(self.doing_selected, self.doing.len(), down),
+            Tab::Calendar => {
+                self.calendar_selected = step(self.calendar_selected, self.calendar.len(), down)
+            }
+            Tab::Search => {
+                self.search.selected = step(self.search.selected, self.search.results.len(), down)
+            }
+        }
+        Action::None
+    }
+
+    /// 切换标签页，首次进入时加载数据
+    fn switch(&mut self, tab: Tab) -> Action {
+        self.tab = tab;
+        match tab {
+            Tab::Doing if self.doing.is_empty() => Action::LoadDoing,
+            Tab::Calendar if self.calendar.is_empty() => Action::LoadCalendar,
+            Tab::Search => {
+                self.search.editing = self.search.results.is_empty();
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    /// 打开当前选中条目的章节格子
+    fn open_selected(&self) -> Action {
+        let selected = match self.tab {
+            Tab::Doing => self.doing.get(self.doing_selected).map(|collection| {
+                let title = collection
+                    .subject
+                    .as_ref()
+                    .map(|subject| display_name(&subject.name, &subject.name_cn))
+                    .unwrap_or_else(|| collection.subject_id.to_string());
+                (collection.subject_id, title)
+            }),
+            Tab::Calendar => self
+                .calendar
+                .get(self.calendar_selected)
+                .map(|subject| (subject.id, display_name(&subject.name, &subject.name_cn))),
+            Tab::Search => self
+                .search
+                .results
+                .get(self.search.selected)
+                .map(|subject| (subject.id, display_name(&subject.name, &subject.name_cn))),
+        };
+        match selected {
+            Some((subject_id, title)) => Action::OpenEpisodes { subject_id, title },
+            None => Action::None,
+        }
+    }
+
+    fn handle_input_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter if !self.search.input.trim().is_empty() => {
+                self.search.editing = false;
+                Action::Search(self.search.input.trim().to_string())
+            }
+            KeyCode::Esc => {
+                self.search.editing = false;
+                Action::None
+            }
+            KeyCode::Backspace => {
+                self.search.input.pop();
+                Action::None
+            }
+            KeyCode::Char(c) => {
+                self.search.input.push(c);
+                Action::None
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn handle_grid_key(&mut self, key: KeyEvent) -> Action {
+        let Some(grid) = &mut self.episodes else {
+            return Action::None;
+        };
+        let last = grid.episodes.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Backspace => {
+                self.episodes = None;
+            }
+            KeyCode::Left | KeyCode::Char('h') => grid.selected = grid.selected.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => grid.selected = (grid.selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => {
+                grid.selected = grid.selected.saturating_sub(GRID_COLUMNS)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                grid.selected = (grid.selected + GRID_COLUMNS).min(last)
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                if let Some(episode) = grid.episodes.get(grid.selected) {
+                    let r#type = match episode.r#type {
+                        CollectionEpisodeType::Done => CollectionEpisodeType::Not,
+                        _ => CollectionEpisodeType::Done,
+                    };
+                    return Action::SetEpisode {
+                        episode_id: episode.episode.id,
+                        r#type,
+                    };
+                }
+            }
+            _ => {}
+        }
+        Action::None
+    }
+}
@@ -0,0 +1,175 @@
+//! Bangumi 终端界面
+//!
+//! 查看在看列表、在章节格子中切换单集看过状态、浏览今日放送与搜索条目。
+//! 访问令牌与配置档案和`bgm`命令行工具共用，依次从`BANGUMI_ACCESS_TOKEN`环境变量与
+//! `BANGUMI_PROFILE`指定（或默认）的配置档案中读取
+mod app;
+#[path = "../bgm/config.rs"]
+mod config;
+mod ui;
+
+#[cfg(test)]
+mod test;
+
+use std::{env, path::PathBuf, process::ExitCode};
+
+use anyhow::{Result, bail};
+use bangumi_api::{
+    common::{model::BangumiClient, time::now_timestamp},
+    module::{
+        collection::model::{CollectionEpisodeUpdate, CollectionType},
+        subject::model::SubjectSearch,
+    },
+};
+use ratatui::{
+    DefaultTerminal,
+    crossterm::event::{self, Event, KeyEventKind},
+};
+
+use app::{Action, App, weekday_of};
+use config::Config;
+
+/// 搜索结果数量
+const SEARCH_LIMIT: u32 = 30;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let client = match client() {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("错误: {error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut terminal = ratatui::init();
+    let result = Session::new(client).run(&mut terminal).await;
+    ratatui::restore();
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("错误: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 按环境变量与配置档案构建客户端
+fn client() -> Result<BangumiClient> {
+    let path = env::var_os("BANGUMI_CONFIG")
+        .map(PathBuf::from)
+        .or_else(config::default_path);
+    let config = match path {
+        Some(path) => Config::load(&path)?,
+        None => Config::default(),
+    };
+    let profile = config.profile(env::var("BANGUMI_PROFILE").ok().as_deref())?;
+    let client = profile.client(env::var("BANGUMI_ACCESS_TOKEN").ok());
+    if client.access_token.is_none() {
+        bail!("未设置访问令牌，请设置 BANGUMI_ACCESS_TOKEN 环境变量或在配置档案中填写 token");
+    }
+    Ok(client)
+}
+
+/// 客户端与界面状态
+struct Session {
+    client: BangumiClient,
+    /// 当前登录用户的用户名，首次加载在看列表时获取
+    username: Option<String>,
+    app: App,
+}
+
+impl Session {
+    fn new(client: BangumiClient) -> Session {
+        Session {
+            client,
+            username: None,
+            app: App::default(),
+        }
+    }
+
+    async fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        let mut action = Action::LoadDoing;
+        loop {
+            if !matches!(action, Action::None) {
+                self.app.status = "加载中…".to_string();
+                terminal.draw(|frame| ui::draw(frame, &self.app))?;
+                if let Err(error) = self.perform(action).await {
+                    self.app.set_error(error);
+                }
+            }
+            terminal.draw(|frame| ui::draw(frame, &self.app))?;
+
+            action = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => self.app.handle_key(key),
+                _ => Action::None,
+            };
+            if action == Action::Quit {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn username(&mut self) -> Result<String> {
+        if let Some(username) = &self.username {
+            return Ok(username.clone());
+        }
+        let username = self.client.get_me().await?.username;
+        self.username = Some(username.clone());
+        Ok(username)
+    }
+
+    /// 执行按键对应的网络操作并回填界面状态
+    async fn perform(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::None | Action::Quit => {}
+            Action::LoadDoing => {
+                let username = self.username().await?;
+                let doing = BangumiClient::fetch_all(|limit, offset| {
+                    self.client.get_collection_subjects(
+                        &username,
+                        None,
+                        Some(CollectionType::Doing),
+                        Some(limit),
+                        Some(offset),
+                    )
+                })
+                .await?;
+                self.app.set_doing(doing);
+            }
+            Action::LoadCalendar => {
+                let calendar = self.client.get_calendar().await?;
+                self.app.set_calendar(calendar, weekday_of(now_timestamp()));
+            }
+            Action::Search(keyword) => {
+                let payload = SubjectSearch {
+                    keyword,
+                    sort: None,
+                    filter: None,
+                };
+                let page = self
+                    .client
+                    .search_subjects(Some(SEARCH_LIMIT), None, Some(payload))
+                    .await?;
+                self.app.set_results(page.data.unwrap_or_default());
+            }
+            Action::OpenEpisodes { subject_id, title } => {
+                let episodes = BangumiClient::fetch_all(|limit, offset| {
+                    self.client
+                        .get_collection_episodes(subject_id, Some(offset), Some(limit), None)
+                })
+                .await?;
+                self.app.open_episodes(subject_id, title, episodes);
+            }
+            Action::SetEpisode { episode_id, r#type } => {
+                let payload = CollectionEpisodeUpdate {
+                    r#type: r#type.clone(),
+                };
+                self.client
+                    .put_collection_episode(episode_id, Some(payload))
+                    .await?;
+                self.app.set_episode(episode_id, r#type);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use bangumi_api::module::collection::model::{
+    CollectionEpisode, CollectionEpisodeType, CollectionSubject,
+};
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+};
+
+use crate::{
+    app::{Action, App, Tab, weekday_of},
+    ui,
+};
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+fn collection(subject_id: u32, name_cn: &str, ep_status: u32) -> CollectionSubject {
+    serde_json::from_value(serde_json::json!({
+        "subject_id": subject_id,
+        "subject_type": 2,
+        "rate": 0,
+        "type": 3,
+        "comment": null,
+        "tags": [],
+        "ep_status": ep_status,
+        "vol_status": 0,
+        "updated_at": "2024-01-01T00:00:00+08:00",
+        "private": false,
+        "subject": {
+            "id": subject_id,
+            "type": 2,
+            "name": "",
+            "name_cn": name_cn,
+            "short_summary": "",
+            "date": "",
+            "images": {"large": "", "common": "", "medium": "", "small": "", "grid": ""},
+            "volumes": 0,
+            "eps": 12,
+            "collection_total": 0,
+            "score": 0.0,
+            "rank": 0,
+            "tags": []
+        }
+    }))
+    .unwrap()
+}
+
+fn episodes(count: u32) -> Vec<CollectionEpisode> {
+    (1..=count)
+        .map(|sort| {
+            serde_json::from_value(serde_json::json!({
+                "episode": {
+                    "id": 100 + sort,
+                    "type": 0,
+                    "name": format!("第{sort}话"),
+                    "name_cn": "",
+                    "sort": sort,
+                    "ep": sort,
+                    "airdate": "2024-01-01",
+                    "comment": 0,
+                    "duration": "00:24:00",
+                    "desc": "",
+                    "disc": 0,
+                    "subject_id": 1,
+                    "duration_seconds": 1440
+                },
+                "type": if sort == 1 { 2 } else { 0 },
+                "updated_at": 0
+            }))
+            .unwrap()
+        })
+        .collect()
+}
+
+/// 渲染界面并按行拼接为文本，跳过宽字符占用的第二个单元格
+fn screen(app: &App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(80, 12)).unwrap();
+    terminal.draw(|frame| ui::draw(frame, app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content()
+        .chunks(buffer.area.width as usize)
+        .map(|row| {
+            let mut line = String::new();
+            let mut wide = false;
+            for cell in row {
+                if !std::mem::take(&mut wide) {
+                    line.push_str(cell.symbol());
+                    wide = cell.symbol().chars().any(|c| c >= '\u{2E80}');
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_weekday() {
+    // 2024-01-01 00:00 UTC 为周一
+    assert_eq!(weekday_of(1704067200), 1);
+    // 2023-12-31 16:00 UTC 时北京时间已是周一
+    assert_eq!(weekday_of(1704038400), 1);
+    assert_eq!(weekday_of(1704038399), 7);
+}
+
+#[test]
+fn test_doing_and_episode_grid() {
+    let mut app = App::default();
+    app.set_doing(vec![
+        collection(1, "星际牛仔", 1),
+        collection(2, "进击的巨人", 0),
+    ]);
+    assert!(screen(&app).contains("星际牛仔  [1/12]"));
+
+    assert_eq!(app.handle_key(key(KeyCode::Char('j'))), Action::None);
+    assert_eq!(app.doing_selected, 1);
+    assert_eq!(app.handle_key(key(KeyCode::Down)), Action::None);
+    assert_eq!(app.doing_selected, 1);
+    app.handle_key(key(KeyCode::Up));
+    assert_eq!(
+        app.handle_key(key(KeyCode::Enter)),
+        Action::OpenEpisodes {
+            subject_id: 1,
+            title: "星际牛仔".to_string()
+        }
+    );
+
+    app.open_episodes(1, "星际牛仔".to_string(), episodes(12));
+    // 第一集已看过，切换为未看
+    assert_eq!(
+        app.handle_key(key(KeyCode::Char(' '))),
+        Action::SetEpisode {
+            episode_id: 101,
+            r#type: CollectionEpisodeType::Not
+        }
+    );
+    app.handle_key(key(KeyCode::Char('j')));
+    app.handle_key(key(KeyCode::Char('l')));
+    assert_eq!(app.episodes.as_ref().unwrap().selected, 11);
+    assert_eq!(
+        app.handle_key(key(KeyCode::Enter)),
+        Action::SetEpisode {
+            episode_id: 112,
+            r#type: CollectionEpisodeType::Done
+        }
+    );
+    app.set_episode(112, CollectionEpisodeType::Done);
+    assert_eq!(app.doing[0].ep_status, 2);
+    let text = screen(&app);
+    assert!(text.contains("12. 第12话"));
+    assert!(text.contains("已看 2 集"));
+
+    assert_eq!(app.handle_key(key(KeyCode::Esc)), Action::None);
+    assert!(app.episodes.is_none());
+    assert_eq!(app.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+}
+
+#[test]
+fn test_tabs_and_search() {
+    let mut app = App::default();
+    assert_eq!(app.handle_key(key(KeyCode::Tab)), Action::LoadCalendar);
+    assert_eq!(app.tab, Tab::Calendar);
+    assert_eq!(app.handle_key(key(KeyCode::Char('3'))), Action::None);
+    assert_eq!(app.tab, Tab::Search);
+    assert!(app.search.editing);
+
+    // 输入时 q 作为普通字符
+    for c in "qa ".chars() {
+        app.handle_key(key(KeyCode::Char(c)));
+    }
+    app.handle_key(key(KeyCode::Backspace));
+    assert_eq!(app.search.input, "qa");
+    assert!(screen(&app).contains("qa"));
+    assert_eq!(
+        app.handle_key(key(KeyCode::Enter)),
+        Action::Search("qa".to_string())
+    );
+    assert!(!app.search.editing);
+    assert_eq!(app.handle_key(key(KeyCode::Enter)), Action::None);
+    assert_eq!(
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        Action::Quit
+    );
+}
@@ -0,0 +1,223 @@
+use bangumi_api::module::{
+    collection::model::{CollectionEpisode, CollectionEpisodeType},
+    episode::model::EpisodeType,
+};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs},
+};
+
+use crate::app::{App, EpisodeGrid, GRID_COLUMNS, Tab};
+
+/// 章节格子的宽度（含间隔）
+const CELL_WIDTH: u16 = 6;
+
+const SELECTED: Style = Style::new().add_modifier(Modifier::REVERSED);
+
+/// 绘制整个界面
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [tabs, body, status] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let titles: Vec<&str> = Tab::ALL.iter().map(Tab::title).collect();
+    frame.render_widget(
+        Tabs::new(titles)
+            .select(app.tab as usize)
+            .highlight_style(SELECTED),
+        tabs,
+    );
+
+    match app.tab {
+        Tab::Doing => draw_doing(frame, app, body),
+        Tab::Calendar => draw_calendar(frame, app, body),
+        Tab::Search => draw_search(frame, app, body),
+    }
+    if let Some(grid) = &app.episodes {
+        draw_grid(frame, grid, body);
+    }
+
+    let hint = if app.episodes.is_some() {
+        "←↓↑→/hjkl 移动  空格 切换看过  Esc 返回"
+    } else if app.tab == Tab::Search && app.search.editing {
+        "输入关键词  回车 搜索  Esc 结束输入"
+    } else {
+        "Tab/1-3 切换  ↑↓/jk 移动  回车 章节  r 刷新  / 输入  q 退出"
+    };
+    let line = if app.status.is_empty() {
+        Line::from(hint)
+    } else {
+        Line::from(vec![
+            Span::styled(app.status.as_str(), Style::new().fg(Color::Yellow)),
+            Span::raw("  "),
+            Span::styled(hint, Style::new().fg(Color::DarkGray)),
+        ])
+    };
+    frame.render_widget(Paragraph::new(line), status);
+}
+
+fn draw_list(frame: &mut Frame, area: Rect, title: String, items: Vec<String>, selected: usize) {
+    let empty = items.is_empty();
+    let list = List::new(items.into_iter().map(ListItem::new))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(SELECTED);
+    let mut state = ListState::default().with_selected((!empty).then_some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_doing(frame: &mut Frame, app: &App, area: Rect) {
+    let items = app
+        .doing
+        .iter()
+        .map(|collection| {
+            let (name, eps) = collection
+                .subject
+                .as_ref()
+                .map(|subject| {
+                    let name = if subject.name_cn.is_empty() {
+                        &subject.name
+                    } else {
+                        &subject.name_cn
+                    };
+                    (name.clone(), subject.eps)
+                })
+                .unwrap_or_else(|| (collection.subject_id.to_string(), 0));
+            format!("{name}  [{}/{eps}]", collection.ep_status)
+        })
+        .collect();
+    draw_list(frame, area, "在看".to_string(), items, app.doing_selected);
+}
+
+fn draw_calendar(frame: &mut Frame, app: &App, area: Rect) {
+    let items = app
+        .calendar
+        .iter()
+        .map(|subject| {
+            let name = if subject.name_cn.is_empty() {
+                &subject.name
+            } else {
+                &subject.name_cn
+            };
+            match &subject.rating {
+                Some(rating) if rating.score > 0.0 => format!("{name}  {:.1}", rating.score),
+                _ => name.clone(),
+            }
+        })
+        .collect();
+    let title = format!("今日放送 {}", app.weekday);
+    draw_list(frame, area, title, items, app.calendar_selected);
+}
+
+fn draw_search(frame: &mut Frame, app: &App, area: Rect) {
+    let [input, results] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+    let input_style = if app.search.editing {
+        Style::new().fg(Color::Yellow)
+    } else {
+        Style::new()
+    };
+    frame.render_widget(
+        Paragraph::new(app.search.input.as_str())
+            .style(input_style)
+            .block(Block::default().borders(Borders::ALL).title("关键词")),
+        input,
+    );
+    let items = app
+        .search
+        .results
+        .iter()
+        .map(|subject| {
+            let date = subject.date.as_deref().unwrap_or_default();
+            if subject.name_cn.is_empty() {
+                format!("{}  {date}", subject.name)
+            } else {
+                format!("{} / {}  {date}", subject.name_cn, subject.name)
+            }
+        })
+        .collect();
+    draw_list(
+        frame,
+        results,
+        "结果".to_string(),
+        items,
+        app.search.selected,
+    );
+}
+
+/// 章节格子中的标签，非正片带类型前缀
+fn cell_label(episode: &CollectionEpisode) -> String {
+    let prefix = match episode.episode.r#type {
+        EpisodeType::Normal => "",
+        EpisodeType::SP => "SP",
+        EpisodeType::OP => "OP",
+        EpisodeType::ED => "ED",
+    };
+    format!("{prefix}{}", episode.episode.sort)
+}
+
+fn cell_style(r#type: &CollectionEpisodeType) -> Style {
+    match r#type {
+        CollectionEpisodeType::Done => Style::new().fg(Color::Black).bg(Color::Green),
+        CollectionEpisodeType::Wish => Style::new().fg(Color::Black).bg(Color::Yellow),
+        CollectionEpisodeType::Dropped => Style::new().fg(Color::DarkGray),
+        CollectionEpisodeType::Not => Style::new(),
+    }
+}
+
+fn draw_grid(frame: &mut Frame, grid: &EpisodeGrid, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(grid.title.as_str());
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let [cells, detail] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(inner);
+    let lines: Vec<Line> = grid
+        .episodes
+        .chunks(GRID_COLUMNS)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let spans = chunk.iter().enumerate().flat_map(|(column, episode)| {
+                let mut style = cell_style(&episode.r#type);
+                if row * GRID_COLUMNS + column == grid.selected {
+                    style = style.patch(SELECTED);
+                }
+                let label = format!(
+                    "{:^width$}",
+                    cell_label(episode),
+                    width = CELL_WIDTH as usize - 1
+                );
+                [Span::styled(label, style), Span::raw(" ")]
+            });
+            Line::from(spans.collect::<Vec<_>>())
+        })
+        .collect();
+    // 保持选中行可见
+    let selected_row = (grid.selected / GRID_COLUMNS) as u16;
+    let scroll = selected_row.saturating_sub(cells.height.saturating_sub(1));
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), cells);
+
+    if let Some(episode) = grid.episodes.get(grid.selected) {
+        let name = if episode.episode.name_cn.is_empty() {
+            &episode.episode.name
+        } else {
+            &episode.episode.name_cn
+        };
+        let text = vec![
+            Line::from(format!("{}. {name}", cell_label(episode))),
+            Line::from(Span::styled(
+                format!("{}  {}", episode.episode.airdate, episode.episode.duration),
+                Style::new().fg(Color::DarkGray),
+            )),
+        ];
+        frame.render_widget(Paragraph::new(text), detail);
+    }
+}
@@ -0,0 +1,273 @@
+use std::path::PathBuf;
+
+use bangumi_api::module::{
+    collection::model::CollectionType, episode::model::EpisodeType, subject::model::SubjectType,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+
+/// Bangumi 命令行工具
+#[derive(Debug, Parser)]
+#[command(name = "bgm", version, about = "Bangumi 命令行工具")]
+pub struct Cli {
+    /// 使用的配置档案名称
+    #[arg(long, short, global = true, env = "BANGUMI_PROFILE")]
+    pub profile: Option<String>,
+    /// 配置文件路径（默认为用户配置目录下的 bgm/config.toml）
+    #[arg(long, global = true, env = "BANGUMI_CONFIG")]
+    pub config: Option<PathBuf>,
+    /// 访问令牌，优先于配置档案中的令牌
+    #[arg(
+        long,
+        global = true,
+        env = "BANGUMI_ACCESS_TOKEN",
+        hide_env_values = true
+    )]
+    pub token: Option<String>,
+    /// 以 JSON 格式输出
+    #[arg(long, global = true, conflicts_with = "yaml")]
+    pub json: bool,
+    /// 以 YAML 格式输出
+    #[arg(long, global = true)]
+    pub yaml: bool,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// 子命令
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 搜索条目、角色或人物
+    Search {
+        /// 关键词
+        keyword: String,
+        /// 搜索对象
+        #[arg(long, short, value_enum, default_value_t = SearchKind::Subject)]
+        kind: SearchKind,
+        /// 条目类型（仅搜索条目时有效）
+        #[arg(long = "type", short = 't', value_enum)]
+        subject_type: Vec<SubjectKind>,
+        #[command(flatten)]
+        page: Page,
+    },
+    /// 获取条目详情
+    Subject {
+        /// 条目ID
+        id: u32,
+    },
+    /// 获取条目的章节列表
+    Episodes {
+        /// 条目ID
+        id: u32,
+        /// 章节类型
+        #[arg(long = "type", short = 't', value_enum)]
+        episode_type: Option<EpisodeKind>,
+        #[command(flatten)]
+        page: Page,
+    },
+    /// 获取角色详情
+    Character {
+        /// 角色ID
+        id: u32,
+    },
+    /// 获取人物详情
+    Person {
+        /// 人物ID
+        id: u32,
+    },
+    /// 获取每日放送日历
+    Calendar,
+    /// 管理条目收藏
+    #[command(subcommand)]
+    Collection(CollectionCommand),
+    /// 将条目的正片标记为看到指定集数
+    Progress {
+        /// 条目ID
+        subject: u32,
+        /// 看到的集数
+        ep: f64,
+        /// 碟片编号（仅统计该碟片的章节）
+        #[arg(long)]
+        disc: Option<u32>,
+    },
+    /// 管理目录中的条目
+    #[command(subcommand)]
+    Index(IndexCommand),
+    /// 查看编辑历史
+    Revisions {
+        /// 实体类型
+        #[arg(value_enum)]
+        entity: RevisionEntity,
+        /// 实体ID
+        id: u32,
+        #[command(flatten)]
+        page: Page,
+    },
+    /// 生成 Shell 补全脚本
+    Completions {
+        /// Shell 类型
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+/// 收藏子命令
+#[derive(Debug, Subcommand)]
+pub enum CollectionCommand {
+    /// 列出用户的条目收藏
+    List {
+        /// 用户名（默认为配置档案中的用户名，或当前登录用户）
+        #[arg(long, short)]
+        user: Option<String>,
+        /// 条目类型
+        #[arg(long = "subject-type", short = 's', value_enum)]
+        subject_type: Option<SubjectKind>,
+        /// 收藏类型
+        #[arg(long = "type", short = 't', value_enum)]
+        collection_type: Option<CollectionKind>,
+        #[command(flatten)]
+        page: Page,
+    },
+    /// 添加或修改条目收藏
+    Set {
+        /// 条目ID
+        subject: u32,
+        /// 收藏类型
+        #[arg(value_enum)]
+        collection_type: CollectionKind,
+        /// 吐槽
+        #[arg(long)]
+        comment: Option<String>,
+        /// 标签（可多次指定）
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// 仅自己可见
+        #[arg(long)]
+        private: bool,
+    },
+    /// 为条目评分（0 表示删除评分）
+    Rate {
+        /// 条目ID
+        subject: u32,
+        /// 评分
+        #[arg(value_parser = clap::value_parser!(u32).range(0..=10))]
+        rate: u32,
+    },
+}
+
+/// 目录子命令
+#[derive(Debug, Subcommand)]
+pub enum IndexCommand {
+    /// 向目录添加条目
+    Add {
+        /// 目录ID
+        index: u32,
+        /// 条目ID
+        subject: u32,
+        /// 排序权重
+        #[arg(long)]
+        sort: Option<u32>,
+        /// 备注
+        #[arg(long)]
+        comment: Option<String>,
+    },
+    /// 从目录删除条目
+    Rm {
+        /// 目录ID
+        index: u32,
+        /// 条目ID
+        subject: u32,
+    },
+}
+
+/// 分页参数
+#[derive(Debug, Args)]
+pub struct Page {
+    /// 每页数量
+    #[arg(long, short)]
+    pub limit: Option<u32>,
+    /// 偏移量
+    #[arg(long, short)]
+    pub offset: Option<u32>,
+}
+
+/// 搜索对象
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SearchKind {
+    Subject,
+    Character,
+    Person,
+}
+
+/// 条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SubjectKind {
+    Book,
+    Anime,
+    Music,
+    Game,
+    Real,
+}
+
+impl From<SubjectKind> for SubjectType {
+    fn from(kind: SubjectKind) -> Self {
+        match kind {
+            SubjectKind::Book => SubjectType::Book,
+            SubjectKind::Anime => SubjectType::Anime,
+            SubjectKind::Music => SubjectType::Music,
+            SubjectKind::Game => SubjectType::Game,
+            SubjectKind::Real => SubjectType::Real,
+        }
+    }
+}
+
+/// 收藏类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CollectionKind {
+    Wish,
+    Done,
+    Doing,
+    OnHold,
+    Dropped,
+}
+
+impl From<CollectionKind> for CollectionType {
+    fn from(kind: CollectionKind) -> Self {
+        match kind {
+            CollectionKind::Wish => CollectionType::Wish,
+            CollectionKind::Done => CollectionType::Done,
+            CollectionKind::Doing => CollectionType::Doing,
+            CollectionKind::OnHold => CollectionType::OnHold,
+            CollectionKind::Dropped => CollectionType::Dropped,
+        }
+    }
+}
+
+/// 章节类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EpisodeKind {
+    Normal,
+    Sp,
+    Op,
+    Ed,
+}
+
+impl From<EpisodeKind> for EpisodeType {
+    fn from(kind: EpisodeKind) -> Self {
+        match kind {
+            EpisodeKind::Normal => EpisodeType::Normal,
+            EpisodeKind::Sp => EpisodeType::SP,
+            EpisodeKind::Op => EpisodeType::OP,
+            EpisodeKind::Ed => EpisodeType::ED,
+        }
+    }
+}
+
+/// 修订记录的实体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RevisionEntity {
+    Subject,
+    Character,
+    Person,
+    Episode,
+}
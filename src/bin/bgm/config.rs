@@ -0,0 +1,96 @@
+use std::{collections::HashMap, fs, io::ErrorKind, path::Path, path::PathBuf};
+
+use anyhow::{Result, bail};
+use bangumi_api::common::model::BangumiClient;
+use serde::Deserialize;
+
+/// 未指定档案且配置文件未设置默认档案时使用的档案名
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// 配置文件
+///
+/// ```toml
+/// default_profile = "work"
+///
+/// [profiles.work]
+/// token = "..."
+/// username = "sai"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// 默认使用的档案名
+    pub default_profile: Option<String>,
+    /// 命名档案
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+}
+
+/// 配置档案
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Profile {
+    /// 访问令牌
+    pub token: Option<String>,
+    /// 用户名，用于默认的收藏查询
+    pub username: Option<String>,
+    /// API 基础路径
+    pub base_path: Option<String>,
+    /// User-Agent
+    pub user_agent: Option<String>,
+}
+
+/// 默认配置文件路径
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("bgm").join("config.toml"))
+}
+
+impl Config {
+    /// 读取配置文件，文件不存在时返回空配置
+    pub fn load(path: &Path) -> Result<Config> {
+        match fs::read_to_string(path) {
+            Ok(text) => Config::parse(&text),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(error) => bail!(format!("读取配置文件{}失败: {error}", path.display())),
+        }
+    }
+
+    /// 解析配置文件内容
+    pub fn parse(text: &str) -> Result<Config> {
+        match toml::from_str(text) {
+            Ok(config) => Ok(config),
+            Err(error) => bail!(format!("配置文件格式错误: {error}")),
+        }
+    }
+
+    /// 选取配置档案
+    ///
+    /// 显式指定的档案必须存在；未指定时依次使用`default_profile`与`default`档案，
+    /// 均不存在时返回空档案
+    pub fn profile(&self, name: Option<&str>) -> Result<Profile> {
+        if let Some(name) = name {
+            return match self.profiles.get(name) {
+                Some(profile) => Ok(profile.clone()),
+                None => bail!(format!("配置档案不存在: {name}")),
+            };
+        }
+        if let Some(name) = &self.default_profile {
+            return self.profile(Some(name));
+        }
+        Ok(self
+            .profiles
+            .get(DEFAULT_PROFILE)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+impl Profile {
+    /// 按档案构建客户端，`token`不为空时覆盖档案中的令牌
+    pub fn client(&self, token: Option<String>) -> BangumiClient {
+        let default = BangumiClient::default();
+        BangumiClient::new(
+            self.base_path.clone().unwrap_or(default.base_path),
+            self.user_agent.clone().or(default.user_agent),
+            token.or_else(|| self.token.clone()),
+        )
+    }
+}
@@ -0,0 +1,251 @@
+//! Bangumi 命令行工具
+//!
+//! 访问令牌依次从`--token`参数、`BANGUMI_ACCESS_TOKEN`环境变量与配置档案中读取，
+//! 配置档案见 [`config::Config`]
+mod args;
+mod config;
+mod output;
+
+#[cfg(test)]
+mod test;
+
+use std::{io, process::ExitCode};
+
+use anyhow::Result;
+use bangumi_api::{
+    common::model::BangumiClient,
+    module::{
+        character::model::CharacterSearch,
+        collection::model::CollectionSubjectUpdate,
+        indice::model::IndexSubjectAddInfo,
+        person::model::PersonSearch,
+        subject::model::{SubjectSearch, SubjectSearchFilter},
+    },
+};
+use clap::{CommandFactory, Parser};
+
+use args::{Cli, CollectionCommand, Command, IndexCommand, RevisionEntity, SearchKind};
+use config::{Config, Profile};
+use output::{Format, emit};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("错误: {error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+impl Cli {
+    fn format(&self) -> Format {
+        if self.json {
+            Format::Json
+        } else if self.yaml {
+            Format::Yaml
+        } else {
+            Format::Table
+        }
+    }
+
+    /// 读取配置文件并选取档案
+    fn profile(&self) -> Result<Profile> {
+        let config = match self.config.clone().or_else(config::default_path) {
+            Some(path) => Config::load(&path)?,
+            None => Config::default(),
+        };
+        config.profile(self.profile.as_deref())
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    if let Command::Completions { shell } = &cli.command {
+        clap_complete::generate(*shell, &mut Cli::command(), "bgm", &mut io::stdout());
+        return Ok(());
+    }
+
+    let format = cli.format();
+    let profile = cli.profile()?;
+    let client = profile.client(cli.token.clone());
+
+    match cli.command {
+        Command::Search {
+            keyword,
+            kind,
+            subject_type,
+            page,
+        } => match kind {
+            SearchKind::Subject => {
+                let filter = (!subject_type.is_empty()).then(|| SubjectSearchFilter {
+                    r#type: subject_type.into_iter().map(Into::into).collect(),
+                    meta_tags: Vec::new(),
+                    tag: Vec::new(),
+                    air_date: Vec::new(),
+                    rating: Vec::new(),
+                    rank: Vec::new(),
+                    nsfw: false,
+                });
+                let payload = SubjectSearch {
+                    keyword,
+                    sort: None,
+                    filter,
+                };
+                let paged = client
+                    .search_subjects(page.limit, page.offset, Some(payload))
+                    .await?;
+                emit(format, &paged, output::subject_list_table)
+            }
+            SearchKind::Character => {
+                let payload = CharacterSearch {
+                    keyword,
+                    filter: None,
+                };
+                let paged = client
+                    .search_characters(page.limit, page.offset, Some(payload))
+                    .await?;
+                emit(format, &paged, output::character_list_table)
+            }
+            SearchKind::Person => {
+                let payload = PersonSearch {
+                    keyword,
+                    filter: None,
+                };
+                let paged = client
+                    .search_persons(page.limit, page.offset, Some(payload))
+                    .await?;
+                emit(format, &paged, output::person_list_table)
+            }
+        },
+        Command::Subject { id } => emit(
+            format,
+            &client.get_subject(id).await?,
+            output::subject_table,
+        ),
+        Command::Episodes {
+            id,
+            episode_type,
+            page,
+        } => {
+            let paged = client
+                .get_episodes(id, episode_type.map(Into::into), page.limit, page.offset)
+                .await?;
+            emit(format, &paged, output::episode_table)
+        }
+        Command::Character { id } => emit(
+            format,
+            &client.get_character(id).await?,
+            output::character_table,
+        ),
+        Command::Person { id } => emit(format, &client.get_person(id).await?, output::person_table),
+        Command::Calendar => emit(
+            format,
+            &client.get_calendar().await?,
+            output::calendar_table,
+        ),
+        Command::Collection(command) => collection(&client, &profile, format, command).await,
+        Command::Progress { subject, ep, disc } => emit(
+            format,
+            &client.mark_progress(subject, ep, disc).await?,
+            output::progress_table,
+        ),
+        Command::Index(IndexCommand::Add {
+            index,
+            subject,
+            sort,
+            comment,
+        }) => {
+            let payload = IndexSubjectAddInfo {
+                subject_id: Some(subject),
+                sort,
+                comment,
+            };
+            client.add_index_subject(index, Some(payload)).await?;
+            output::done(format, &format!("已将条目 {subject} 添加到目录 {index}"));
+            Ok(())
+        }
+        Command::Index(IndexCommand::Rm { index, subject }) => {
+            client.delete_index_subject(index, subject).await?;
+            output::done(format, &format!("已从目录 {index} 删除条目 {subject}"));
+            Ok(())
+        }
+        Command::Revisions { entity, id, page } => {
+            let (limit, offset) = (page.limit, page.offset);
+            let paged = match entity {
+                RevisionEntity::Subject => client.get_revision_subjects(id, limit, offset).await?,
+                RevisionEntity::Character => {
+                    client.get_revision_characters(id, limit, offset).await?
+                }
+                RevisionEntity::Person => client.get_revision_persons(id, limit, offset).await?,
+                RevisionEntity::Episode => client.get_revision_episodes(id, limit, offset).await?,
+            };
+            emit(format, &paged, output::revision_table)
+        }
+        Command::Completions { .. } => unreachable!("已在读取配置前处理"),
+    }
+}
+
+async fn collection(
+    client: &BangumiClient,
+    profile: &Profile,
+    format: Format,
+    command: CollectionCommand,
+) -> Result<()> {
+    match command {
+        CollectionCommand::List {
+            user,
+            subject_type,
+            collection_type,
+            page,
+        } => {
+            let username = match user.or_else(|| profile.username.clone()) {
+                Some(username) => username,
+                None => client.get_me().await?.username,
+            };
+            let paged = client
+                .get_collection_subjects(
+                    &username,
+                    subject_type.map(Into::into),
+                    collection_type.map(Into::into),
+                    page.limit,
+                    page.offset,
+                )
+                .await?;
+            emit(format, &paged, output::collection_table)
+        }
+        CollectionCommand::Set {
+            subject,
+            collection_type,
+            comment,
+            tags,
+            private,
+        } => {
+            let collection_type = collection_type.into();
+            let name = output::collection_type_name(&collection_type);
+            let payload = CollectionSubjectUpdate {
+                r#type: Some(collection_type),
+                comment,
+                tags: (!tags.is_empty()).then_some(tags),
+                private: private.then_some(true),
+                ..Default::default()
+            };
+            client
+                .post_collection_subject(subject, Some(payload))
+                .await?;
+            output::done(format, &format!("已将条目 {subject} 标记为{name}"));
+            Ok(())
+        }
+        CollectionCommand::Rate { subject, rate } => {
+            let payload = CollectionSubjectUpdate {
+                rate: Some(rate),
+                ..Default::default()
+            };
+            client
+                .patch_collection_subject(subject, Some(payload))
+                .await?;
+            output::done(format, &format!("已为条目 {subject} 评分 {rate}"));
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,463 @@
+use anyhow::Result;
+use bangumi_api::module::{
+    character::model::Character,
+    collection::model::{CollectionSubject, CollectionType, ProgressUpdate},
+    episode::model::{Episode, EpisodeType},
+    model::Paged,
+    person::model::PersonDetail,
+    revision::model::RevisionCommon,
+    subject::model::{DailyCalendarItem, Subject, SubjectType},
+};
+use serde::Serialize;
+
+/// 简介等长文本在表格中的最大显示宽度
+const SUMMARY_WIDTH: usize = 60;
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// 对齐的文本表格
+    Table,
+    Json,
+    Yaml,
+}
+
+/// 文本表格
+///
+/// 表头为空时不输出表头与分隔线，用于键值形式的详情展示
+#[derive(Debug, Default)]
+pub struct Table {
+    pub headers: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+    /// 表格下方的附注（如分页信息）
+    pub footer: Option<String>,
+}
+
+impl Table {
+    pub fn new(headers: Vec<&'static str>) -> Table {
+        Table {
+            headers,
+            rows: Vec::new(),
+            footer: None,
+        }
+    }
+
+    /// 键值表格
+    pub fn fields(fields: Vec<(&'static str, String)>) -> Table {
+        Table {
+            headers: Vec::new(),
+            rows: fields
+                .into_iter()
+                .map(|(key, value)| vec![key.to_string(), value])
+                .collect(),
+            footer: None,
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    /// 按显示宽度对齐各列，列间以两个空格分隔
+    pub fn render(&self) -> String {
+        let columns = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([self.headers.len()])
+            .max()
+            .unwrap_or(0);
+        let mut widths = vec![0; columns];
+        let headers: Vec<String> = self
+            .headers
+            .iter()
+            .map(|header| header.to_string())
+            .collect();
+        for row in self.rows.iter().chain([&headers]) {
+            for (index, cell) in row.iter().enumerate() {
+                widths[index] = widths[index].max(display_width(cell));
+            }
+        }
+
+        let mut lines = Vec::new();
+        if !headers.is_empty() {
+            lines.push(render_row(&headers, &widths));
+            let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+            lines.push(render_row(&rule, &widths));
+        }
+        for row in &self.rows {
+            lines.push(render_row(row, &widths));
+        }
+        lines.extend(self.footer.clone());
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+}
+
+fn render_row(row: &[String], widths: &[usize]) -> String {
+    let mut line = String::new();
+    for (index, cell) in row.iter().enumerate() {
+        if index > 0 {
+            line.push_str("  ");
+        }
+        line.push_str(cell);
+        if index + 1 < row.len() {
+            line.push_str(&" ".repeat(widths[index] - display_width(cell)));
+        }
+    }
+    line
+}
+
+/// 终端显示宽度，中日韩文字与全角符号计为 2
+pub fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x1100..=0x115F
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA000..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x20000..=0x3FFFD => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+/// 截断到指定显示宽度，并将换行替换为空格
+pub fn truncate(text: &str, width: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if display_width(&text) <= width {
+        return text;
+    }
+    let mut result = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let char_width = display_width(c.encode_utf8(&mut [0; 4]));
+        if used + char_width >= width {
+            break;
+        }
+        used += char_width;
+        result.push(c);
+    }
+    result.push('…');
+    result
+}
+
+/// 按格式输出数据，表格形式由`table`生成
+pub fn emit<T: Serialize>(
+    format: Format,
+    value: &T,
+    table: impl FnOnce(&T) -> Table,
+) -> Result<()> {
+    let text = match format {
+        Format::Table => table(value).render(),
+        Format::Json => serde_json::to_string_pretty(value)? + "\n",
+        Format::Yaml => serde_yaml::to_string(value)?,
+    };
+    print!("{text}");
+    Ok(())
+}
+
+/// 输出没有返回数据的操作结果，JSON/YAML 格式下不输出内容
+pub fn done(format: Format, message: &str) {
+    if format == Format::Table {
+        println!("{message}");
+    }
+}
+
+/// 未显示全部结果时在表格下方附注分页信息
+fn page_footer<T>(table: &mut Table, paged: &Paged<T>) {
+    let shown = paged.data.as_ref().map_or(0, Vec::len) as u32;
+    if paged.total > shown {
+        let end = paged.offset + shown;
+        table.footer = Some(format!("({}-{end} / {})", paged.offset + 1, paged.total));
+    }
+}
+
+pub fn subject_type_name(subject_type: &SubjectType) -> &'static str {
+    match subject_type {
+        SubjectType::Book => "书籍",
+        SubjectType::Anime => "动画",
+        SubjectType::Music => "音乐",
+        SubjectType::Game => "游戏",
+        SubjectType::Real => "三次元",
+    }
+}
+
+pub fn collection_type_name(collection_type: &CollectionType) -> &'static str {
+    match collection_type {
+        CollectionType::Wish => "想看",
+        CollectionType::Done => "看过",
+        CollectionType::Doing => "在看",
+        CollectionType::OnHold => "搁置",
+        CollectionType::Dropped => "抛弃",
+    }
+}
+
+pub fn episode_type_name(episode_type: &EpisodeType) -> &'static str {
+    match episode_type {
+        EpisodeType::Normal => "本篇",
+        EpisodeType::SP => "SP",
+        EpisodeType::OP => "OP",
+        EpisodeType::ED => "ED",
+    }
+}
+
+fn rank(rank: u32) -> String {
+    if rank == 0 {
+        "-".to_string()
+    } else {
+        rank.to_string()
+    }
+}
+
+pub fn subject_list_table(paged: &Paged<Subject>) -> Table {
+    let mut table = Table::new(vec!["ID", "类型", "名称", "中文名", "日期", "评分", "排名"]);
+    for subject in paged.data.iter().flatten() {
+        table.push(vec![
+            subject.id.to_string(),
+            subject_type_name(&subject.r#type).to_string(),
+            subject.name.clone(),
+            subject.name_cn.clone(),
+            subject.date.clone().unwrap_or_default(),
+            format!("{:.1}", subject.rating.score),
+            rank(subject.rating.rank),
+        ]);
+    }
+    page_footer(&mut table, paged);
+    table
+}
+
+pub fn subject_table(subject: &Subject) -> Table {
+    let tags: Vec<&str> = subject
+        .tags
+        .iter()
+        .take(8)
+        .map(|tag| tag.name.as_str())
+        .collect();
+    Table::fields(vec![
+        ("ID", subject.id.to_string()),
+        ("类型", subject_type_name(&subject.r#type).to_string()),
+        ("名称", subject.name.clone()),
+        ("中文名", subject.name_cn.clone()),
+        ("平台", subject.platform.clone()),
+        ("日期", subject.date.clone().unwrap_or_default()),
+        ("话数", subject.eps.to_string()),
+        (
+            "评分",
+            format!("{:.1} ({}人)", subject.rating.score, subject.rating.total),
+        ),
+        ("排名", rank(subject.rating.rank)),
+        ("标签", tags.join(" ")),
+        ("简介", truncate(&subject.summary, SUMMARY_WIDTH)),
+    ])
+}
+
+pub fn episode_table(paged: &Paged<Episode>) -> Table {
+    let mut table = Table::new(vec![
+        "ID",
+        "类型",
+        "序号",
+        "名称",
+        "中文名",
+        "放送日期",
+        "时长",
+    ]);
+    for episode in paged.data.iter().flatten() {
+        table.push(vec![
+            episode.id.to_string(),
+            episode_type_name(&episode.r#type).to_string(),
+            episode.sort.to_string(),
+            episode.name.clone(),
+            episode.name_cn.clone(),
+            episode.airdate.clone(),
+            episode.duration.clone(),
+        ]);
+    }
+    page_footer(&mut table, paged);
+    table
+}
+
+pub fn character_list_table(paged: &Paged<Character>) -> Table {
+    let mut table = Table::new(vec!["ID", "名称", "性别", "简介"]);
+    for character in paged.data.iter().flatten() {
+        table.push(vec![
+            character.id.to_string(),
+            character.name.clone(),
+            character.gender.clone(),
+            truncate(&character.summary, SUMMARY_WIDTH),
+        ]);
+    }
+    page_footer(&mut table, paged);
+    table
+}
+
+pub fn character_table(character: &Character) -> Table {
+    Table::fields(vec![
+        ("ID", character.id.to_string()),
+        ("名称", character.name.clone()),
+        ("类型", format!("{:?}", character.r#type)),
+        ("性别", character.gender.clone()),
+        (
+            "生日",
+            birthday(
+                character.birth_year,
+                character.birth_mon,
+                character.birth_day,
+            ),
+        ),
+        ("收藏", character.stat.collects.to_string()),
+        ("简介", truncate(&character.summary, SUMMARY_WIDTH)),
+    ])
+}
+
+fn careers(person: &PersonDetail) -> String {
+    person
+        .career
+        .iter()
+        .filter_map(|career| serde_json::to_value(career).ok())
+        .filter_map(|value| value.as_str().map(str::to_string))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn person_list_table(paged: &Paged<PersonDetail>) -> Table {
+    let mut table = Table::new(vec!["ID", "名称", "职业", "简介"]);
+    for person in paged.data.iter().flatten() {
+        table.push(vec![
+            person.id.to_string(),
+            person.name.clone(),
+            careers(person),
+            truncate(&person.summary, SUMMARY_WIDTH),
+        ]);
+    }
+    page_footer(&mut table, paged);
+    table
+}
+
+pub fn person_table(person: &PersonDetail) -> Table {
+    Table::fields(vec![
+        ("ID", person.id.to_string()),
+        ("名称", person.name.clone()),
+        ("类型", format!("{:?}", person.r#type)),
+        ("职业", careers(person)),
+        ("性别", person.gender.clone().unwrap_or_default()),
+        (
+            "生日",
+            birthday(person.birth_year, person.birth_mon, person.birth_day),
+        ),
+        ("收藏", person.stat.collects.to_string()),
+        ("简介", truncate(&person.summary, SUMMARY_WIDTH)),
+    ])
+}
+
+fn birthday(year: Option<u32>, month: Option<u32>, day: Option<u32>) -> String {
+    match (year, month, day) {
+        (Some(year), Some(month), Some(day)) => format!("{year}-{month:02}-{day:02}"),
+        (None, Some(month), Some(day)) => format!("{month:02}-{day:02}"),
+        (Some(year), _, _) => year.to_string(),
+        _ => String::new(),
+    }
+}
+
+pub fn calendar_table(calendar: &Vec<DailyCalendarItem>) -> Table {
+    let mut table = Table::new(vec!["星期", "ID", "名称", "评分"]);
+    for day in calendar {
+        for subject in &day.items {
+            let name = if subject.name_cn.is_empty() {
+                &subject.name
+            } else {
+                &subject.name_cn
+            };
+            table.push(vec![
+                day.weekday.cn.clone(),
+                subject.id.to_string(),
+                name.clone(),
+                subject
+                    .rating
+                    .as_ref()
+                    .map(|rating| format!("{:.1}", rating.score))
+                    .unwrap_or_default(),
+            ]);
+        }
+    }
+    table
+}
+
+pub fn collection_table(paged: &Paged<CollectionSubject>) -> Table {
+    let mut table = Table::new(vec![
+        "ID",
+        "类型",
+        "名称",
+        "状态",
+        "评分",
+        "进度",
+        "更新时间",
+    ]);
+    for collection in paged.data.iter().flatten() {
+        let name = collection
+            .subject
+            .as_ref()
+            .map(|subject| {
+                if subject.name_cn.is_empty() {
+                    subject.name.clone()
+                } else {
+                    subject.name_cn.clone()
+                }
+            })
+            .unwrap_or_default();
+        let eps = collection.subject.as_ref().map_or(0, |subject| subject.eps);
+        table.push(vec![
+            collection.subject_id.to_string(),
+            subject_type_name(&collection.subject_type).to_string(),
+            name,
+            collection_type_name(&collection.r#type).to_string(),
+            if collection.rate == 0 {
+                "-".to_string()
+            } else {
+                collection.rate.to_string()
+            },
+            format!("{}/{eps}", collection.ep_status),
+            collection.updated_at.clone(),
+        ]);
+    }
+    page_footer(&mut table, paged);
+    table
+}
+
+pub fn progress_table(progress: &ProgressUpdate) -> Table {
+    Table::fields(vec![
+        ("条目", progress.subject_id.to_string()),
+        ("标记章节数", progress.episode_ids.len().to_string()),
+        ("进度", progress.ep_status.to_string()),
+        (
+            "已看完",
+            if progress.completed { "是" } else { "否" }.to_string(),
+        ),
+    ])
+}
+
+pub fn revision_table(paged: &Paged<RevisionCommon>) -> Table {
+    let mut table = Table::new(vec!["ID", "编辑者", "摘要", "时间"]);
+    for revision in paged.data.iter().flatten() {
+        table.push(vec![
+            revision.id.to_string(),
+            revision
+                .creator
+                .as_ref()
+                .map(|creator| creator.username.clone())
+                .unwrap_or_default(),
+            truncate(&revision.summary, SUMMARY_WIDTH),
+            revision.created_at.clone(),
+        ]);
+    }
+    page_footer(&mut table, paged);
+    table
+}
@@ -0,0 +1,107 @@
+use clap::{CommandFactory, Parser};
+
+use crate::{
+    args::{Cli, CollectionCommand, CollectionKind, Command, RevisionEntity, SearchKind},
+    config::{Config, Profile},
+    output::{Format, Table, display_width, truncate},
+};
+
+const CONFIG: &str = r#"
+default_profile = "work"
+
+[profiles.work]
+token = "work-token"
+username = "sai"
+
+[profiles.test]
+token = "test-token"
+base_path = "http://127.0.0.1:8080"
+"#;
+
+#[test]
+fn test_parse_args() {
+    Cli::command().debug_assert();
+
+    let cli = Cli::try_parse_from(["bgm", "search", "巨人", "--kind", "person", "--json"]).unwrap();
+    assert_eq!(cli.format(), Format::Json);
+    assert!(matches!(
+        cli.command,
+        Command::Search {
+            kind: SearchKind::Person,
+            ..
+        }
+    ));
+
+    let cli =
+        Cli::try_parse_from(["bgm", "collection", "set", "8", "on-hold", "--tag", "科幻"]).unwrap();
+    match cli.command {
+        Command::Collection(CollectionCommand::Set {
+            subject,
+            collection_type,
+            tags,
+            ..
+        }) => {
+            assert_eq!(subject, 8);
+            assert_eq!(collection_type, CollectionKind::OnHold);
+            assert_eq!(tags, vec!["科幻"]);
+        }
+        command => panic!("解析结果错误: {command:?}"),
+    }
+
+    let cli = Cli::try_parse_from(["bgm", "revisions", "episode", "5", "--yaml"]).unwrap();
+    assert_eq!(cli.format(), Format::Yaml);
+    assert!(matches!(
+        cli.command,
+        Command::Revisions {
+            entity: RevisionEntity::Episode,
+            id: 5,
+            ..
+        }
+    ));
+
+    assert!(Cli::try_parse_from(["bgm", "collection", "rate", "8", "11"]).is_err());
+    assert!(Cli::try_parse_from(["bgm", "subject", "1", "--json", "--yaml"]).is_err());
+}
+
+#[test]
+fn test_config_profile() {
+    let config = Config::parse(CONFIG).unwrap();
+    let work = config.profile(None).unwrap();
+    assert_eq!(work.token.as_deref(), Some("work-token"));
+    assert_eq!(work.username.as_deref(), Some("sai"));
+
+    let test = config.profile(Some("test")).unwrap();
+    let client = test.client(None);
+    assert_eq!(client.base_path, "http://127.0.0.1:8080");
+    assert_eq!(client.access_token.as_deref(), Some("test-token"));
+    // 命令行与环境变量中的令牌优先
+    let client = test.client(Some("env-token".to_string()));
+    assert_eq!(client.access_token.as_deref(), Some("env-token"));
+
+    assert!(config.profile(Some("missing")).is_err());
+    assert_eq!(Config::default().profile(None).unwrap(), Profile::default());
+    assert!(Config::parse("profiles = 1").is_err());
+}
+
+#[test]
+fn test_render_table() {
+    assert_eq!(display_width("abc"), 3);
+    assert_eq!(display_width("星际牛仔"), 8);
+    assert_eq!(truncate("星际\n牛仔", 20), "星际 牛仔");
+    assert_eq!(truncate("星际牛仔", 6), "星际…");
+
+    let mut table = Table::new(vec!["ID", "名称"]);
+    table.push(vec!["1".to_string(), "星际牛仔".to_string()]);
+    table.push(vec!["253".to_string(), "Cowboy Bebop".to_string()]);
+    table.footer = Some("(1-2 / 5)".to_string());
+    assert_eq!(
+        table.render(),
+        "ID   名称\n---  ------------\n1    星际牛仔\n253  Cowboy Bebop\n(1-2 / 5)\n"
+    );
+
+    let fields = Table::fields(vec![
+        ("ID", "1".to_string()),
+        ("中文名", "星际牛仔".to_string()),
+    ]);
+    assert_eq!(fields.render(), "ID      1\n中文名  星际牛仔\n");
+}
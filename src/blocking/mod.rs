@@ -0,0 +1,8 @@
+/// 同步（阻塞）客户端（需启用`blocking`特性）
+pub mod model;
+pub mod service;
+
+#[cfg(test)]
+pub mod test;
+
+pub use model::BangumiClient;
@@ -0,0 +1,12 @@
+use crate::common::model;
+
+/// 同步（阻塞）的Bangumi API客户端
+///
+/// 内部持有一个异步的 [`model::BangumiClient`]，每个方法都在当前线程上执行对应的异步方法，
+/// 等待期间挂起线程直到被唤醒，不依赖异步运行时。模型与错误类型与异步接口完全相同。
+///
+/// 在异步运行时内部调用会阻塞所在的工作线程，此时应直接使用异步客户端
+pub struct BangumiClient {
+    /// 被封装的异步客户端
+    pub client: model::BangumiClient,
+}
@@ -0,0 +1,307 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use crate::{
+    common::model::{self, ApiResponse},
+    module::{
+        calendar::model::CalendarEvent,
+        character::model::{Character, CharacterPerson, CharacterSearch, CharacterSubject},
+        collection::model::{
+            CollectionCharacter, CollectionEpisode, CollectionEpisodeUpdate,
+            CollectionEpisodesUpdate, CollectionPerson, CollectionSubject, CollectionSubjectUpdate,
+            CollectionType, ProgressUpdate,
+        },
+        episode::model::{Episode, EpisodeType},
+        export::model::CollectionArchive,
+        franchise::model::Franchise,
+        import::model::{ImportEntry, ImportPlan, ImportState},
+        indice::model::{
+            Index, IndexBasicInfo, IndexSubject, IndexSubjectAddInfo, IndexSubjectEditInfo,
+        },
+        model::{ImageType, Paged, SimpleImageType},
+        person::model::{PersonCharacter, PersonDetail, PersonSearch, PersonSubject},
+        recommend::model::{RecommendOptions, Recommendation},
+        revision::model::{
+            RevisionCharacter, RevisionCommon, RevisionEpisode, RevisionPerson, RevisionSubject,
+        },
+        scrobble::model::{AliasCache, ParsedRelease, ScrobbleResult},
+        season::model::SeasonChart,
+        stats::model::UserStats,
+        subject::model::{
+            DailyCalendarItem, Subject, SubjectBrowseSort, SubjectCategory, SubjectCharacter,
+            SubjectPerson, SubjectSearch, SubjectSubject, SubjectType,
+        },
+        sync::model::{ConflictPolicy, SyncOperation, SyncPlan, SyncState},
+        user::model::{AvatarType, User, UserPublic},
+    },
+};
+
+use super::model::BangumiClient;
+
+/// 为BangumiClient提供默认实现
+///
+/// 使用与异步客户端相同的基础路径与User-Agent
+impl Default for BangumiClient {
+    fn default() -> Self {
+        BangumiClient::new(
+            model::DEFAULT_BASE_PATH.to_string(),
+            Some(model::DEFAULT_USER_AGENT.to_string()),
+            None,
+        )
+    }
+}
+
+/// 由异步客户端创建同步客户端
+///
+/// 请求在调用方的线程上执行，传输层不能依赖异步运行时（如需要tokio的`ReqwestTransport`），
+/// 可使用 [`UreqTransport`](crate::common::transport::UreqTransport) 或 [`TestTransport`](crate::common::transport::TestTransport)
+impl From<model::BangumiClient> for BangumiClient {
+    fn from(client: model::BangumiClient) -> Self {
+        BangumiClient { client }
+    }
+}
+
+/// 唤醒时恢复被挂起线程的Waker
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// 在当前线程上执行Future直到完成
+///
+/// 返回Pending时挂起线程，Future被唤醒后再次轮询
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+impl BangumiClient {
+    /// 创建一个新的同步客户端
+    ///
+    /// 参数与 [`model::BangumiClient::new`] 相同，使用
+    /// [`UreqTransport`](crate::common::transport::UreqTransport) 发送请求
+    pub fn new(
+        base_path: String,
+        user_agent: Option<String>,
+        access_token: Option<String>,
+    ) -> Self {
+        let transport = crate::common::transport::UreqTransport::default();
+        BangumiClient::from(model::BangumiClient::with_transport(
+            base_path,
+            user_agent,
+            access_token,
+            transport,
+        ))
+    }
+}
+
+/// 为异步客户端的方法生成同名的同步方法
+///
+/// 生成的方法直接转发到异步客户端，方法名、参数或返回值与异步方法不一致时无法通过编译
+macro_rules! blocking {
+    ($($(#[$meta:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)* $(,)?) -> $ret:ty;)*) => {
+        impl BangumiClient {
+            $(
+                $(#[$meta])*
+                pub fn $name(&self $(, $arg: $ty)*) -> Result<$ret> {
+                    block_on(self.client.$name($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+blocking! {
+    /// 获取即将播出的剧集，见 [`model::BangumiClient::get_calendar_events`]
+    fn get_calendar_events(&self, from_date: Option<&str>, username: Option<&str>) -> Vec<CalendarEvent>;
+    /// 导出放送日历，见 [`model::BangumiClient::export_calendar_ics`]
+    fn export_calendar_ics(&self, from_date: Option<&str>, username: Option<&str>) -> String;
+
+    /// 搜索角色
+    fn search_characters(&self, limit: Option<u32>, offset: Option<u32>, payload: Option<CharacterSearch>) -> Paged<Character>;
+    /// 获取角色详情
+    fn get_character(&self, character_id: u32) -> Character;
+    /// 获取角色图片
+    fn get_character_image(&self, character_id: u32, r#type: SimpleImageType) -> Bytes;
+    /// 获取角色关联的条目
+    fn get_character_subjects(&self, character_id: u32) -> Vec<CharacterSubject>;
+    /// 获取角色关联的人物
+    fn get_character_persons(&self, character_id: u32) -> Vec<CharacterPerson>;
+    /// 收藏角色
+    fn collect_character(&self, character_id: u32) -> ();
+    /// 取消收藏角色
+    fn uncollect_character(&self, character_id: u32) -> ();
+
+    /// 获取用户的条目收藏
+    fn get_collection_subjects(&self, username: &str, subject_type: Option<SubjectType>, r#type: Option<CollectionType>, limit: Option<u32>, offset: Option<u32>) -> Paged<CollectionSubject>;
+    /// 获取用户对单个条目的收藏
+    fn get_collection_subject(&self, username: &str, subject_id: u32) -> CollectionSubject;
+    /// 新增或修改条目收藏
+    fn post_collection_subject(&self, subject_id: u32, payload: Option<CollectionSubjectUpdate>) -> ();
+    /// 新增或修改条目收藏，并返回响应元信息
+    fn post_collection_subject_with_meta(&self, subject_id: u32, payload: Option<CollectionSubjectUpdate>) -> ApiResponse<()>;
+    /// 修改条目收藏
+    fn patch_collection_subject(&self, subject_id: u32, payload: Option<CollectionSubjectUpdate>) -> ();
+    /// 修改条目收藏，并返回响应元信息
+    fn patch_collection_subject_with_meta(&self, subject_id: u32, payload: Option<CollectionSubjectUpdate>) -> ApiResponse<()>;
+    /// 获取条目的章节收藏
+    fn get_collection_episodes(&self, subject_id: u32, offset: Option<u32>, limit: Option<u32>, episode_type: Option<EpisodeType>) -> Paged<CollectionEpisode>;
+    /// 批量修改章节收藏
+    fn patch_collection_episodes(&self, subject_id: u32, payload: Option<CollectionEpisodesUpdate>) -> ();
+    /// 批量修改章节收藏，并返回响应元信息
+    fn patch_collection_episodes_with_meta(&self, subject_id: u32, payload: Option<CollectionEpisodesUpdate>) -> ApiResponse<()>;
+    /// 获取单个章节的收藏
+    fn get_collection_episode(&self, episode_id: u32) -> CollectionEpisode;
+    /// 修改单个章节的收藏
+    fn put_collection_episode(&self, episode_id: u32, payload: Option<CollectionEpisodeUpdate>) -> ();
+    /// 修改单个章节的收藏，并返回响应元信息
+    fn put_collection_episode_with_meta(&self, episode_id: u32, payload: Option<CollectionEpisodeUpdate>) -> ApiResponse<()>;
+    /// 获取用户的角色收藏
+    fn get_collection_characters(&self, username: &str) -> Paged<CollectionCharacter>;
+    /// 获取用户对单个角色的收藏
+    fn get_collection_character(&self, username: &str, character_id: u32) -> CollectionCharacter;
+    /// 获取用户的人物收藏
+    fn get_collection_persons(&self, username: &str) -> Paged<CollectionPerson>;
+    /// 获取用户对单个人物的收藏
+    fn get_collection_person(&self, username: &str, person_id: u32) -> CollectionPerson;
+    /// 标记观看进度，见 [`model::BangumiClient::mark_progress`]
+    fn mark_progress(&self, subject_id: u32, up_to_ep: f64, disc: Option<u32>) -> ProgressUpdate;
+
+    /// 获取条目的剧集
+    fn get_episodes(&self, subject_id: u32, r#type: Option<EpisodeType>, limit: Option<u32>, offset: Option<u32>) -> Paged<Episode>;
+    /// 获取剧集详情
+    fn get_episode(&self, episode_id: u32) -> Episode;
+
+    /// 导出用户的全部收藏
+    fn export_collections(&self, username: &str, include_episodes: bool) -> CollectionArchive;
+
+    /// 解析条目所属的系列
+    fn get_franchise(&self, subject_id: u32, max_entries: Option<usize>) -> Franchise;
+
+    /// 为导入的记录匹配条目
+    fn plan_import(&self, entries: Vec<ImportEntry>, mapping: &HashMap<u32, u32>) -> ImportPlan;
+    /// 执行导入计划
+    fn apply_import(&self, plan: &ImportPlan, state: &mut ImportState) -> usize;
+
+    /// 创建目录
+    fn add_index(&self) -> Index;
+    /// 获取目录详情
+    fn get_index(&self, index_id: u32) -> Index;
+    /// 编辑目录信息
+    fn edit_index(&self, index_id: u32, payload: Option<IndexBasicInfo>) -> Index;
+    /// 获取目录中的条目
+    fn get_index_subjects(&self, index_id: u32, r#type: Option<SubjectType>, limit: Option<u32>, offset: Option<u32>) -> Paged<IndexSubject>;
+    /// 向目录添加条目
+    fn add_index_subject(&self, index_id: u32, payload: Option<IndexSubjectAddInfo>) -> ();
+    /// 编辑目录中的条目
+    fn edit_index_subject(&self, index_id: u32, subject_id: u32, payload: Option<IndexSubjectEditInfo>) -> ();
+    /// 从目录中删除条目
+    fn delete_index_subject(&self, index_id: u32, subject_id: u32) -> ();
+    /// 收藏目录
+    fn collect_index(&self, index_id: u32) -> ();
+    /// 取消收藏目录
+    fn uncollect_index(&self, index_id: u32) -> ();
+
+    /// 搜索人物
+    fn search_persons(&self, limit: Option<u32>, offset: Option<u32>, payload: Option<PersonSearch>) -> Paged<PersonDetail>;
+    /// 获取人物详情
+    fn get_person(&self, person_id: u32) -> PersonDetail;
+    /// 获取人物图片
+    fn get_person_image(&self, person_id: u32, r#type: SimpleImageType) -> Bytes;
+    /// 获取人物参与的条目
+    fn get_person_subjects(&self, person_id: u32) -> Vec<PersonSubject>;
+    /// 获取人物关联的角色
+    fn get_person_characters(&self, person_id: u32) -> Vec<PersonCharacter>;
+    /// 收藏人物
+    fn collect_person(&self, person_id: u32) -> ();
+    /// 取消收藏人物
+    fn uncollect_person(&self, person_id: u32) -> ();
+
+    /// 为用户生成推荐
+    fn recommend_for_user(&self, username: &str, options: &RecommendOptions) -> Vec<Recommendation>;
+
+    /// 获取人物的修订列表
+    fn get_revision_persons(&self, person_id: u32, limit: Option<u32>, offset: Option<u32>) -> Paged<RevisionCommon>;
+    /// 获取人物修订详情
+    fn get_revision_person(&self, revision_id: u32) -> RevisionPerson;
+    /// 获取角色的修订列表
+    fn get_revision_characters(&self, character_id: u32, limit: Option<u32>, offset: Option<u32>) -> Paged<RevisionCommon>;
+    /// 获取角色修订详情
+    fn get_revision_character(&self, revision_id: u32) -> RevisionCharacter;
+    /// 获取条目的修订列表
+    fn get_revision_subjects(&self, subject_id: u32, limit: Option<u32>, offset: Option<u32>) -> Paged<RevisionCommon>;
+    /// 获取条目修订详情
+    fn get_revision_subject(&self, revision_id: u32) -> RevisionSubject;
+    /// 获取剧集的修订列表
+    fn get_revision_episodes(&self, episode_id: u32, limit: Option<u32>, offset: Option<u32>) -> Paged<RevisionCommon>;
+    /// 获取剧集修订详情
+    fn get_revision_episode(&self, revision_id: u32) -> RevisionEpisode;
+
+    /// 为解析出的发布文件匹配条目
+    fn resolve_release(&self, release: &ParsedRelease, cache: &mut AliasCache) -> Option<u32>;
+    /// 解析文件名并标记为已看
+    fn scrobble(&self, filename: &str, cache: &mut AliasCache) -> ScrobbleResult;
+
+    /// 生成季度新番表
+    fn season_chart(&self, year: u32, quarter: u8) -> SeasonChart;
+
+    /// 统计用户收藏
+    fn get_user_stats(&self, username: &str, include_episodes: bool) -> UserStats;
+
+    /// 获取每日放送
+    fn get_calendar(&self) -> Vec<DailyCalendarItem>;
+    /// 搜索条目
+    fn search_subjects(&self, limit: Option<u32>, offset: Option<u32>, payload: Option<SubjectSearch>) -> Paged<Subject>;
+    /// 浏览条目
+    #[allow(clippy::too_many_arguments)]
+    fn get_subjects(&self, r#type: SubjectType, cat: Option<SubjectCategory>, series: Option<bool>, platform: Option<&str>, sort: Option<SubjectBrowseSort>, year: Option<u32>, month: Option<u32>, limit: Option<u32>, offset: Option<u32>) -> Paged<Subject>;
+    /// 获取条目详情
+    fn get_subject(&self, subject_id: u32) -> Subject;
+    /// 获取条目详情，并返回响应元信息
+    fn get_subject_with_meta(&self, subject_id: u32) -> ApiResponse<Subject>;
+    /// 获取条目封面
+    fn get_subject_image(&self, subject_id: u32, r#type: ImageType) -> Bytes;
+    /// 获取条目关联的人物
+    fn get_subject_persons(&self, subject_id: u32) -> Vec<SubjectPerson>;
+    /// 获取条目关联的角色
+    fn get_subject_characters(&self, subject_id: u32) -> Vec<SubjectCharacter>;
+    /// 获取条目关联的条目
+    fn get_subject_subjects(&self, subject_id: u32) -> Vec<SubjectSubject>;
+
+    /// 获取远端收藏的同步状态
+    fn fetch_sync_state(&self, username: &str, subject_type: Option<SubjectType>, include_episodes: bool) -> SyncState;
+    /// 执行同步操作
+    fn apply_sync_operations(&self, operations: &[SyncOperation]) -> ();
+    /// 双向同步收藏
+    fn sync_collections(&self, username: &str, local: &mut SyncState, policy: ConflictPolicy, include_episodes: bool) -> SyncPlan;
+
+    /// 获取用户信息
+    fn get_user(&self, username: &str) -> UserPublic;
+    /// 获取用户头像
+    fn get_user_avatar(&self, username: &str, r#type: AvatarType) -> Bytes;
+    /// 获取当前登录的用户
+    fn get_me(&self) -> User;
+}
@@ -0,0 +1,39 @@
+use crate::common::mock::{MockRoute, MockServer};
+
+use super::BangumiClient;
+
+#[test]
+fn test_blocking_request() {
+    let server = MockServer::start(vec![MockRoute::json(
+        "GET",
+        "/v0/users/sai",
+        200,
+        serde_json::json!({
+            "id": 1,
+            "url": "https://bgm.tv/user/sai",
+            "username": "sai",
+            "nickname": "Sai",
+            "user_group": 1,
+            "avatar": {"large": "l", "medium": "m", "small": "s"},
+            "sign": ""
+        }),
+    )]);
+    let client = BangumiClient::new(
+        server.base_path().to_string(),
+        None,
+        Some("token".to_string()),
+    );
+
+    let user = client.get_user("sai").unwrap();
+    assert_eq!(user.nickname, "Sai");
+    assert!(client.get_user("missing").is_err());
+    assert_eq!(
+        server.requests()[0]
+            .headers
+            .iter()
+            .find(|(name, _)| name == "authorization")
+            .unwrap()
+            .1,
+        "Bearer token"
+    );
+}
@@ -0,0 +1,172 @@
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+/// 测试用的图片链接
+pub const IMAGES: &str =
+    r#"{"large": "l", "common": "c", "medium": "m", "small": "s", "grid": "g"}"#;
+
+/// 以JSON对象表示的API响应数据
+///
+/// 构造函数填好全部必填字段，测试只需通过[`Fixture::with`]设置关心的字段
+#[derive(Debug, Clone, Serialize)]
+#[serde(transparent)]
+pub struct Fixture(Value);
+
+impl Fixture {
+    /// 设置字段，`.`分隔的路径用于设置嵌套字段（如`rating.score`）
+    pub fn with(mut self, path: &str, value: impl Serialize) -> Self {
+        let mut target = &mut self.0;
+        for key in path.split('.') {
+            target = &mut target[key];
+        }
+        *target = serde_json::to_value(value).unwrap();
+        self
+    }
+
+    /// 设置条目名称与中文名
+    pub fn named(self, name: &str, name_cn: &str) -> Self {
+        self.with("name", name).with("name_cn", name_cn)
+    }
+
+    /// 设置信息框中的别名
+    pub fn aliases(self, aliases: &[&str]) -> Self {
+        let aliases: Vec<_> = aliases.iter().map(|alias| json!({"v": alias})).collect();
+        self.with("infobox", [json!({"key": "别名", "value": aliases})])
+    }
+
+    /// 设置标签列表（`path`通常为`tags`或`subject.tags`），每个标签的数量相同
+    pub fn tags(self, path: &str, names: &[&str]) -> Self {
+        let tags: Vec<_> = names
+            .iter()
+            .map(|name| json!({"name": name, "count": 10, "total_cont": 0}))
+            .collect();
+        self.with(path, tags)
+    }
+
+    /// 转换为JSON值
+    pub fn json(self) -> Value {
+        self.0
+    }
+
+    /// 解析为模型类型
+    pub fn parse<T: DeserializeOwned>(self) -> T {
+        serde_json::from_value(self.0).unwrap()
+    }
+}
+
+/// 解析一组测试数据
+pub fn parse_all<T: DeserializeOwned>(fixtures: impl IntoIterator<Item = Fixture>) -> Vec<T> {
+    fixtures.into_iter().map(Fixture::parse).collect()
+}
+
+/// 图片链接
+pub fn images() -> Value {
+    serde_json::from_str(IMAGES).unwrap()
+}
+
+/// 条目详情（`Subject`），默认为12集的TV动画
+pub fn subject(id: u32) -> Fixture {
+    let count: serde_json::Map<String, Value> = (1..=10)
+        .map(|score| (score.to_string(), json!(0)))
+        .collect();
+    Fixture(json!({
+        "id": id,
+        "type": 2,
+        "name": format!("Subject {id}"),
+        "name_cn": "",
+        "summary": "",
+        "series": false,
+        "nsfw": false,
+        "locked": false,
+        "date": "2024-04-01",
+        "platform": "TV",
+        "images": images(),
+        "infobox": [],
+        "volumes": 0,
+        "eps": 12,
+        "rating": {"rank": 0, "total": 0, "count": count, "score": 7.5},
+        "collection": {"wish": 0, "collect": 0, "doing": 0, "on_hold": 0, "dropped": 0},
+        "meta_tags": [],
+        "tags": []
+    }))
+}
+
+/// 放送日历中的条目（`SubjectSmall`）
+pub fn subject_small(id: u32) -> Fixture {
+    Fixture(json!({
+        "id": id,
+        "url": format!("http://bgm.tv/subject/{id}"),
+        "type": 2,
+        "name": format!("Subject {id}"),
+        "name_cn": "",
+        "summary": "",
+        "air_date": "2024-04-01",
+        "air_weekday": 1,
+        "rating": null,
+        "rank": null,
+        "images": images(),
+        "collection": null
+    }))
+}
+
+/// 关联条目（`SubjectSubject`）
+pub fn related(id: u32, relation: &str) -> Fixture {
+    Fixture(json!({
+        "id": id,
+        "type": 2,
+        "name": format!("Subject {id}"),
+        "name_cn": "",
+        "images": images(),
+        "relation": relation
+    }))
+}
+
+/// 剧集（`Episode`），默认为条目1的正片，集数与排序相同
+pub fn episode(id: u32, sort: f64) -> Fixture {
+    Fixture(json!({
+        "id": id,
+        "type": 0,
+        "name": "",
+        "name_cn": "",
+        "sort": sort,
+        "ep": sort,
+        "airdate": "2024-04-01",
+        "comment": 0,
+        "duration": "00:24:00",
+        "desc": "",
+        "disc": 0,
+        "subject_id": 1,
+        "duration_seconds": 1440
+    }))
+}
+
+/// 条目收藏（`CollectionSubject`），默认为动画的"看过"收藏，附带条目简介
+pub fn collection(subject_id: u32) -> Fixture {
+    Fixture(json!({
+        "subject_id": subject_id,
+        "subject_type": 2,
+        "rate": 0,
+        "type": 2,
+        "comment": null,
+        "tags": [],
+        "ep_status": 0,
+        "vol_status": 0,
+        "updated_at": "2024-01-01T00:00:00+08:00",
+        "private": false,
+        "subject": {
+            "id": subject_id,
+            "type": 2,
+            "name": format!("Subject {subject_id}"),
+            "name_cn": "",
+            "short_summary": "",
+            "date": "2020-01-01",
+            "images": images(),
+            "volumes": 0,
+            "eps": 12,
+            "collection_total": 0,
+            "score": 7.0,
+            "rank": 0,
+            "tags": []
+        }
+    }))
+}
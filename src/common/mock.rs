@@ -0,0 +1,158 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use super::model::BangumiClient;
+
+/// 预设的响应
+#[derive(Debug, Clone)]
+pub struct MockRoute {
+    /// HTTP方法（如"GET"）
+    pub method: String,
+    /// 请求路径（不含查询参数）
+    pub path: String,
+    /// 响应状态码
+    pub status: u16,
+    /// 响应体
+    pub body: String,
+}
+
+impl MockRoute {
+    /// 创建一个返回JSON的预设响应
+    pub fn json(method: &str, path: &str, status: u16, body: serde_json::Value) -> Self {
+        MockRoute {
+            method: method.to_string(),
+            path: path.to_string(),
+            status,
+            body: body.to_string(),
+        }
+    }
+}
+
+/// 模拟服务收到的请求
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// HTTP方法
+    pub method: String,
+    /// 请求路径（不含查询参数）
+    pub path: String,
+    /// 查询参数字符串
+    pub query: String,
+    /// 请求头（名称为小写）
+    pub headers: Vec<(String, String)>,
+    /// 请求体
+    pub body: String,
+}
+
+/// 本地HTTP模拟服务
+///
+/// 在本地端口上按"方法 + 路径"返回预设的响应，并记录收到的请求，
+/// 使依赖网络的服务方法可以在离线环境下测试
+pub struct MockServer {
+    base_path: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+impl MockServer {
+    /// 在随机端口上启动模拟服务
+    ///
+    /// 未匹配到预设响应的请求返回404及Bangumi格式的错误体
+    pub fn start(routes: Vec<MockRoute>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("绑定本地端口失败");
+        let base_path = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let routes = routes.clone();
+                let recorded = recorded.clone();
+                thread::spawn(move || handle(stream, &routes, &recorded));
+            }
+        });
+
+        MockServer {
+            base_path,
+            requests,
+        }
+    }
+
+    /// 模拟服务的基础路径
+    pub fn base_path(&self) -> &str {
+        &self.base_path
+    }
+
+    /// 创建指向模拟服务的客户端
+    pub fn client(&self) -> BangumiClient {
+        BangumiClient::new(self.base_path.clone(), None, Some("token".to_string()))
+    }
+
+    /// 已收到的请求
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn handle(stream: TcpStream, routes: &[MockRoute], recorded: &Mutex<Vec<MockRequest>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+    let length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    let _ = reader.read_exact(&mut body);
+
+    let request = MockRequest {
+        method,
+        path: path.to_string(),
+        query: query.to_string(),
+        headers,
+        body: String::from_utf8_lossy(&body).to_string(),
+    };
+
+    let (status, body) = routes
+        .iter()
+        .find(|route| route.method == request.method && route.path == request.path)
+        .map(|route| (route.status, route.body.clone()))
+        .unwrap_or_else(|| {
+            let body = serde_json::json!({
+                "title": "Not Found",
+                "details": {"error": null, "path": request.path, "method": request.method},
+                "request_id": null,
+                "description": "mock route not found"
+            });
+            (404, body.to_string())
+        });
+    recorded.lock().unwrap().push(request);
+
+    let response = format!(
+        "HTTP/1.1 {status} MOCK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let mut stream = stream;
+    let _ = stream.write_all(response.as_bytes());
+}
@@ -1,2 +1,14 @@
 pub mod error;
 pub mod model;
+pub mod time;
+pub mod transport;
+
+/// 测试用的API响应数据
+#[cfg(test)]
+pub mod fixture;
+/// 测试用的本地HTTP模拟服务（需启用`reqwest`或`ureq`特性）
+#[cfg(all(test, any(feature = "reqwest", feature = "ureq")))]
+pub mod mock;
+
+#[cfg(test)]
+mod test;
@@ -1,6 +1,16 @@
+use std::{future::Future, sync::Arc, time::Duration};
+
 use anyhow::{Result, bail};
+use http::{HeaderMap, Method, StatusCode, header};
+use serde::de::DeserializeOwned;
+
+use crate::module::model::Paged;
 
-use super::error::BangumiError;
+use super::{
+    error::BangumiError,
+    time::monotonic_seconds,
+    transport::{HttpResponse, RequestBuilder, SingleFlightTransport, Transport},
+};
 
 /// 用于与Bangumi API进行交互的客户端
 ///
@@ -10,29 +20,123 @@ pub struct BangumiClient {
     pub base_path: String,
     /// 可选的User-Agent头，用于标识请求来源
     pub user_agent: Option<String>,
-    /// reqwest HTTP客户端，用于发送实际的网络请求
-    pub client: reqwest::Client,
+    /// HTTP传输层，用于发送实际的网络请求
+    pub transport: Arc<dyn Transport>,
     /// 可选的访问令牌，用于认证需要授权的API请求
     pub access_token: Option<String>,
 }
 
+/// 带有HTTP元信息的API响应
+///
+/// 由 [`BangumiClient::send_with_meta`] 及各`*_with_meta`方法返回，
+/// 除解析后的数据外还保留状态码、响应头、最终地址与耗时
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    /// 解析后的响应数据
+    pub data: T,
+    /// 响应状态码
+    pub status: StatusCode,
+    /// 响应头
+    pub headers: HeaderMap,
+    /// 最终的请求地址（跟随重定向后）
+    pub url: String,
+    /// 从发出请求到读完响应体的耗时
+    pub elapsed: Duration,
+    /// 请求ID（`x-request-id`或`cf-ray`响应头），向Bangumi反馈问题时使用
+    pub request_id: Option<String>,
+}
+
+/// 响应头中的限流信息
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimit {
+    /// 时间窗口内允许的请求数（`x-ratelimit-limit`）
+    pub limit: Option<u64>,
+    /// 时间窗口内剩余的请求数（`x-ratelimit-remaining`）
+    pub remaining: Option<u64>,
+    /// 距离额度重置的秒数（`x-ratelimit-reset`）
+    pub reset: Option<u64>,
+    /// 被限流时建议的重试等待秒数（`retry-after`）
+    pub retry_after: Option<u64>,
+}
+
+impl<T> ApiResponse<T> {
+    /// 解析响应头中的限流信息，缺失或无法解析的字段为None
+    pub fn rate_limit(&self) -> RateLimit {
+        let number = |name: &str| {
+            self.headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+        };
+        RateLimit {
+            limit: number("x-ratelimit-limit"),
+            remaining: number("x-ratelimit-remaining"),
+            reset: number("x-ratelimit-reset"),
+            retry_after: number(header::RETRY_AFTER.as_str()),
+        }
+    }
+
+    /// 转换响应数据，保留元信息
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ApiResponse<U> {
+        ApiResponse {
+            data: f(self.data),
+            status: self.status,
+            headers: self.headers,
+            url: self.url,
+            elapsed: self.elapsed,
+            request_id: self.request_id,
+        }
+    }
+}
+
+/// 分页拉取时每页的数量
+pub const PAGE_LIMIT: u32 = 100;
+
+/// 默认的API基础路径
+pub const DEFAULT_BASE_PATH: &str = "https://api.bgm.tv";
+
+/// 默认的User-Agent
+pub const DEFAULT_USER_AGENT: &str =
+    "waitsalt/bangumi-api (https://github.com/waitsalt/bangumi-api)";
+
+/// 是否由客户端设置User-Agent头
+///
+/// 浏览器会忽略或拒绝脚本设置的User-Agent，wasm32上改由浏览器自带的值标识请求来源
+pub(crate) const SEND_USER_AGENT: bool = !cfg!(target_arch = "wasm32");
+
+/// 默认的传输层
+///
+/// 启用`reqwest`特性时使用 [`ReqwestTransport`](super::transport::ReqwestTransport)，
+/// 否则使用 [`UreqTransport`](super::transport::UreqTransport)
+#[cfg(feature = "reqwest")]
+pub fn default_transport() -> super::transport::ReqwestTransport {
+    super::transport::ReqwestTransport::default()
+}
+
+/// 默认的传输层
+///
+/// 启用`reqwest`特性时使用`ReqwestTransport`，
+/// 否则使用 [`UreqTransport`](super::transport::UreqTransport)
+#[cfg(all(feature = "ureq", not(feature = "reqwest")))]
+pub fn default_transport() -> super::transport::UreqTransport {
+    super::transport::UreqTransport::default()
+}
+
 /// 为BangumiClient提供默认实现
 ///
 /// 默认配置：
 /// - 使用"https://api.bgm.tv"作为基础路径
 /// - 设置特定格式的User-Agent
-/// - 初始化一个基本的reqwest客户端
+/// - 使用 [`default_transport`] 传输层
 /// - 不包含访问令牌
+#[cfg(any(feature = "reqwest", feature = "ureq"))]
 impl Default for BangumiClient {
     fn default() -> Self {
-        BangumiClient {
-            base_path: "https://api.bgm.tv".to_string(),
-            user_agent: Some(
-                "waitsalt/bangumi-api (https://github.com/waitsalt/bangumi-api)".to_string(),
-            ),
-            client: reqwest::Client::new(),
-            access_token: None,
-        }
+        BangumiClient::new(
+            DEFAULT_BASE_PATH.to_string(),
+            Some(DEFAULT_USER_AGENT.to_string()),
+            None,
+        )
     }
 }
 
@@ -45,20 +149,88 @@ impl BangumiClient {
     /// - `access_token`: 可选的访问令牌
     ///
     /// # 返回
-    /// 返回一个使用指定参数配置的BangumiClient实例
+    /// 返回一个使用指定参数配置、基于 [`default_transport`] 的BangumiClient实例
+    #[cfg(any(feature = "reqwest", feature = "ureq"))]
     pub fn new(
         base_path: String,
         user_agent: Option<String>,
         access_token: Option<String>,
+    ) -> Self {
+        Self::with_transport(base_path, user_agent, access_token, default_transport())
+    }
+
+    /// 使用指定的传输层创建BangumiClient实例
+    ///
+    /// # 参数
+    /// - `base_path`: API的基础路径
+    /// - `user_agent`: 可选的User-Agent头
+    /// - `access_token`: 可选的访问令牌
+    /// - `transport`: 发送请求的传输层
+    pub fn with_transport(
+        base_path: String,
+        user_agent: Option<String>,
+        access_token: Option<String>,
+        transport: impl Transport + 'static,
     ) -> Self {
         BangumiClient {
             base_path,
             user_agent,
-            client: reqwest::Client::new(),
+            transport: Arc::new(transport),
             access_token,
         }
     }
 
+    /// 合并相同的并发GET请求
+    ///
+    /// 用 [`SingleFlightTransport`] 包装当前的传输层：地址与访问令牌都相同的GET请求
+    /// 同时进行时只发出一次，其余调用共享同一响应，可降低热门条目的突发请求量
+    pub fn with_single_flight(mut self) -> Self {
+        self.transport = Arc::new(SingleFlightTransport::new(self.transport));
+        self
+    }
+
+    /// 拉取分页接口的全部数据
+    ///
+    /// 以每页 [`PAGE_LIMIT`] 条依次调用`fetch(limit, offset)`，直到返回空页或达到总数
+    ///
+    /// # 参数
+    /// - `fetch`: 按(limit, offset)请求一页数据的函数
+    ///
+    /// # 返回
+    /// 返回所有页的数据
+    pub async fn fetch_all<T, F, Fut>(fetch: F) -> Result<Vec<T>>
+    where
+        F: FnMut(u32, u32) -> Fut,
+        Fut: Future<Output = Result<Paged<T>>>,
+    {
+        Self::fetch_up_to(u32::MAX, fetch).await
+    }
+
+    /// 拉取分页接口的数据，取得至少`max`条后停止
+    ///
+    /// 与 [`BangumiClient::fetch_all`] 相同，但已取得的数量达到`max`时不再请求下一页，
+    /// 最后一页不会被截断
+    pub async fn fetch_up_to<T, F, Fut>(max: u32, mut fetch: F) -> Result<Vec<T>>
+    where
+        F: FnMut(u32, u32) -> Fut,
+        Fut: Future<Output = Result<Paged<T>>>,
+    {
+        let mut items = Vec::new();
+        loop {
+            let page = fetch(PAGE_LIMIT, items.len() as u32).await?;
+            let data = page.data.unwrap_or_default();
+            if data.is_empty() {
+                break;
+            }
+            items.extend(data);
+            let fetched = items.len() as u32;
+            if fetched >= page.total || fetched >= max {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
     /// 构建HTTP请求
     ///
     /// 根据提供的HTTP方法和URL创建一个RequestBuilder实例，并设置必要的请求头
@@ -69,12 +241,14 @@ impl BangumiClient {
     ///
     /// # 返回
     /// 返回一个配置了User-Agent和Bearer Token(如果有)的RequestBuilder
-    pub fn request_builder(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
-        let mut builder = self.client.request(method, url);
+    pub fn request_builder(&self, method: Method, url: &str) -> RequestBuilder {
+        let mut builder = RequestBuilder::new(method, url);
 
-        // 设置User-Agent头(如果有)
-        if let Some(ua) = &self.user_agent {
-            builder = builder.header(reqwest::header::USER_AGENT, ua);
+        // 设置User-Agent头(如果有且运行环境允许)
+        if let Some(ua) = &self.user_agent
+            && SEND_USER_AGENT
+        {
+            builder = builder.header(header::USER_AGENT, ua);
         }
 
         // 设置Bearer Token(如果有)
@@ -87,7 +261,7 @@ impl BangumiClient {
 
     /// 发送HTTP请求并处理响应
     ///
-    /// 执行RequestBuilder构建的请求，并根据响应状态码进行相应处理
+    /// 通过传输层执行RequestBuilder构建的请求，并根据响应状态码进行相应处理
     ///
     /// # 参数
     /// - `request_builder`: 包含请求信息的RequestBuilder
@@ -95,14 +269,37 @@ impl BangumiClient {
     /// # 返回
     /// - 如果请求成功(状态码200-299)，返回包含响应的Result
     /// - 如果请求失败，将响应体解析为BangumiError并返回错误
-    pub async fn request_send(
-        &self,
-        request_builder: reqwest::RequestBuilder,
-    ) -> Result<reqwest::Response> {
+    pub async fn request_send(&self, request_builder: RequestBuilder) -> Result<HttpResponse> {
         // 发送请求并获取响应
-        let response = request_builder.send().await?;
+        let response = self.transport.send(request_builder.build()?).await?;
+        Self::check_response(response)
+    }
+
+    /// 发送HTTP请求，资源不存在时返回None
+    ///
+    /// 与 [`BangumiClient::request_send`] 相同，但将404响应视为资源不存在而不是错误，
+    /// 适用于"未收藏"等以404表示空结果的接口
+    ///
+    /// # 参数
+    /// - `request_builder`: 包含请求信息的RequestBuilder
+    ///
+    /// # 返回
+    /// 状态码为404时返回None，其余情况与`request_send`相同
+    pub async fn request_send_optional(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> Result<Option<HttpResponse>> {
+        let response = self.transport.send(request_builder.build()?).await?;
+        if response.status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Self::check_response(response).map(Some)
+    }
+
+    /// 检查响应状态码，失败时将响应体解析为BangumiError并返回错误
+    fn check_response(response: HttpResponse) -> Result<HttpResponse> {
         // 获取响应状态码
-        let status_code = response.status();
+        let status_code = response.status;
         // 判断请求是否成功
         match status_code.is_success() {
             true => {
@@ -111,9 +308,45 @@ impl BangumiClient {
             }
             false => {
                 // 请求失败，将响应体解析为错误信息
-                let res: BangumiError = response.json().await?;
+                let res: BangumiError = response.json()?;
                 bail!(format!("请求错误: {:?}", res))
             }
         }
     }
+
+    /// 发送HTTP请求并返回带有元信息的响应
+    ///
+    /// 与 [`BangumiClient::request_send`] 相同地处理错误，成功时将响应体解析为`T`，
+    /// 空响应体按JSON的`null`解析；不关心响应内容的写操作可以使用`T = serde::de::IgnoredAny`
+    ///
+    /// # 参数
+    /// - `request_builder`: 包含请求信息的RequestBuilder
+    ///
+    /// # 返回
+    /// 返回包含数据、状态码、响应头、最终地址、耗时与请求ID的ApiResponse
+    pub async fn send_with_meta<T: DeserializeOwned>(
+        &self,
+        request_builder: RequestBuilder,
+    ) -> Result<ApiResponse<T>> {
+        let start = monotonic_seconds();
+        let response = self.request_send(request_builder).await?;
+        let elapsed = Duration::from_secs_f64((monotonic_seconds() - start).max(0.0));
+
+        let data = match response.body.is_empty() {
+            true => serde_json::from_value(serde_json::Value::Null)?,
+            false => response.json()?,
+        };
+        let request_id = ["x-request-id", "cf-ray"]
+            .iter()
+            .find_map(|name| response.headers.get(*name)?.to_str().ok())
+            .map(str::to_string);
+        Ok(ApiResponse {
+            data,
+            status: response.status,
+            headers: response.headers,
+            url: response.url,
+            elapsed,
+            request_id,
+        })
+    }
 }
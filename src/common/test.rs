@@ -0,0 +1,446 @@
+use std::{
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+
+use super::{
+    model::{BangumiClient, RateLimit, SEND_USER_AGENT},
+    time::{civil_from_days, days_from_civil},
+    transport::{
+        HttpRequest, HttpResponse, RateLimitTransport, RequestBuilder, RetryTransport,
+        SingleFlightTransport, Sleep, TestTransport, Transport, TransportFuture,
+    },
+};
+use crate::module::{
+    collection::model::{CollectionSubjectUpdate, CollectionType},
+    episode::model::{Episode, EpisodeType},
+    model::Paged,
+};
+
+fn client<T: Transport + 'static>(transport: &Arc<T>) -> BangumiClient {
+    BangumiClient::with_transport(
+        "https://api.example.com".to_string(),
+        Some("test-agent".to_string()),
+        Some("token".to_string()),
+        transport.clone(),
+    )
+}
+
+#[tokio::test]
+async fn test_transport_canned_responses() {
+    let transport = Arc::new(
+        TestTransport::new()
+            .with_json(
+                Method::GET,
+                "/v0/episodes",
+                200,
+                serde_json::json!({"total": 0, "limit": 10, "offset": 0, "data": []}),
+            )
+            .with_json(
+                Method::POST,
+                "/v0/users/-/collections/1",
+                202,
+                serde_json::Value::Null,
+            ),
+    );
+    let client = client(&transport);
+
+    let page = client
+        .get_episodes(1, Some(EpisodeType::Normal), Some(10), None)
+        .await
+        .unwrap();
+    assert_eq!(page.limit, 10);
+    let update = CollectionSubjectUpdate {
+        r#type: Some(CollectionType::Done),
+        ..Default::default()
+    };
+    client
+        .post_collection_subject(1, Some(update))
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(
+        requests[0].url,
+        "https://api.example.com/v0/episodes?subject_id=1&type=0&limit=10"
+    );
+    assert_eq!(requests[0].headers[header::USER_AGENT], "test-agent");
+    assert_eq!(requests[0].headers[header::AUTHORIZATION], "Bearer token");
+    assert!(requests[0].body.is_none());
+    assert_eq!(requests[1].method, Method::POST);
+    assert_eq!(
+        requests[1].headers[header::CONTENT_TYPE],
+        "application/json"
+    );
+    let body: serde_json::Value =
+        serde_json::from_slice(requests[1].body.as_ref().unwrap()).unwrap();
+    assert_eq!(body["type"], 2);
+}
+
+#[tokio::test]
+async fn test_transport_error_response() {
+    let transport = Arc::new(TestTransport::new());
+    let error = client(&transport).get_me().await.unwrap_err();
+    assert!(error.to_string().contains("Not Found"));
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_send_with_meta() {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+    headers.insert("x-ratelimit-limit", HeaderValue::from_static("120"));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from_static("119"));
+    headers.insert("x-ratelimit-reset", HeaderValue::from_static("abc"));
+    let transport = Arc::new(
+        TestTransport::new()
+            .with_response(
+                Method::POST,
+                "/v0/users/-/collections/1",
+                HttpResponse {
+                    status: StatusCode::ACCEPTED,
+                    url: String::new(),
+                    headers,
+                    body: Bytes::new(),
+                },
+            )
+            .with_json(
+                Method::PATCH,
+                "/v0/users/-/collections/1",
+                200,
+                serde_json::json!({}),
+            )
+            .with_json(
+                Method::GET,
+                "/v0/episodes",
+                200,
+                serde_json::json!({"total": 0, "limit": 100, "offset": 0, "data": []}),
+            ),
+    );
+    let client = client(&transport);
+
+    // 写操作的空响应体解析为()
+    let response = client
+        .post_collection_subject_with_meta(1, None)
+        .await
+        .unwrap();
+    assert_eq!(response.status, StatusCode::ACCEPTED);
+    assert_eq!(response.request_id.as_deref(), Some("req-1"));
+    assert_eq!(
+        response.url,
+        "https://api.example.com/v0/users/-/collections/1"
+    );
+    assert_eq!(
+        response.rate_limit(),
+        RateLimit {
+            limit: Some(120),
+            remaining: Some(119),
+            ..Default::default()
+        }
+    );
+
+    // 写操作的非空响应体被忽略
+    let response = client
+        .patch_collection_subject_with_meta(1, None)
+        .await
+        .unwrap();
+    assert_eq!(response.status, StatusCode::OK);
+
+    let request = client.request_builder(Method::GET, "https://api.example.com/v0/episodes");
+    let response = client
+        .send_with_meta::<Paged<Episode>>(request)
+        .await
+        .unwrap();
+    assert_eq!(response.request_id, None);
+    let total = response.map(|page| page.total).data;
+    assert_eq!(total, 0);
+
+    // 失败时与request_send相同地返回Bangumi错误
+    assert!(client.get_subject_with_meta(1).await.is_err());
+}
+
+/// 延迟返回并统计实际发出次数的传输层
+#[derive(Default)]
+struct SlowTransport {
+    inner: TestTransport,
+    calls: AtomicUsize,
+}
+
+impl Transport for SlowTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            self.inner.send(request).await
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_single_flight() {
+    let transport = Arc::new(SingleFlightTransport::new(SlowTransport {
+        inner: TestTransport::new().with_json(
+            Method::GET,
+            "/v0/episodes",
+            200,
+            serde_json::json!({"total": 0, "limit": 100, "offset": 0, "data": []}),
+        ),
+        ..Default::default()
+    }));
+    let client = Arc::new(client(&transport));
+
+    let tasks: Vec<_> = (0..50)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.get_episodes(1, None, None, None).await })
+        })
+        .collect();
+    for task in tasks {
+        assert_eq!(task.await.unwrap().unwrap().total, 0);
+    }
+    assert_eq!(transport.coalesced(), 49);
+
+    // 令牌不同或非GET的请求不合并，完成后的请求不缓存
+    let other = BangumiClient::with_transport(
+        client.base_path.clone(),
+        None,
+        Some("other".to_string()),
+        transport.clone(),
+    );
+    let (first, second, third) = tokio::join!(
+        client.get_episodes(1, None, None, None),
+        other.get_episodes(1, None, None, None),
+        client.post_collection_subject(1, None),
+    );
+    assert!(first.is_ok() && second.is_ok() && third.is_err());
+    assert_eq!(transport.coalesced(), 49);
+    client.get_episodes(1, None, None, None).await.unwrap();
+    assert_eq!(transport.coalesced(), 49);
+}
+
+#[tokio::test]
+async fn test_single_flight_cancelled() {
+    let transport = SingleFlightTransport::new(SlowTransport::default());
+    let request = RequestBuilder::new(Method::GET, "https://api.example.com/v0/me")
+        .build()
+        .unwrap();
+
+    let leader = transport.send(request.clone());
+    let follower = transport.send(request.clone());
+    assert_eq!(transport.coalesced(), 1);
+    // 发起请求的一方被取消，等待的一方得到错误而不是永远挂起
+    assert!(
+        tokio::time::timeout(Duration::from_millis(10), leader)
+            .await
+            .is_err()
+    );
+    assert!(follower.await.is_err());
+
+    // 取消后的请求重新发出
+    assert_eq!(
+        transport.send(request).await.unwrap().status,
+        StatusCode::NOT_FOUND
+    );
+}
+
+/// 前两次分别返回错误与503的传输层
+#[derive(Default)]
+struct FlakyTransport {
+    inner: TestTransport,
+    calls: AtomicUsize,
+}
+
+impl Transport for FlakyTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async move {
+            match call {
+                0 => anyhow::bail!("connection reset"),
+                1 => {
+                    let mut headers = HeaderMap::new();
+                    headers.insert(header::RETRY_AFTER, HeaderValue::from_static("0"));
+                    Ok(HttpResponse {
+                        status: StatusCode::SERVICE_UNAVAILABLE,
+                        url: request.url,
+                        headers,
+                        body: Bytes::new(),
+                    })
+                }
+                _ => self.inner.send(request).await,
+            }
+        })
+    }
+}
+
+fn tokio_sleep() -> Sleep {
+    Arc::new(|duration| Box::pin(tokio::time::sleep(duration)))
+}
+
+#[tokio::test]
+async fn test_retry_transport() {
+    let flaky = || FlakyTransport {
+        inner: TestTransport::new().with_json(Method::GET, "/v0/me", 200, serde_json::json!({})),
+        ..Default::default()
+    };
+    let transport = RetryTransport::new(flaky(), 3, Duration::from_millis(1), tokio_sleep());
+    let request = RequestBuilder::new(Method::GET, "https://api.example.com/v0/me")
+        .build()
+        .unwrap();
+    // 与后端无关：任意传输层错误与可重试的状态码都会重试
+    assert_eq!(
+        transport.send(request.clone()).await.unwrap().status,
+        StatusCode::OK
+    );
+    assert_eq!(transport.retried(), 2);
+    assert_eq!(transport.inner().calls.load(Ordering::SeqCst), 3);
+
+    // 重试次数用尽时返回最后一次的结果
+    let transport = RetryTransport::new(flaky(), 1, Duration::from_millis(1), tokio_sleep());
+    assert_eq!(
+        transport.send(request).await.unwrap().status,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    // 非幂等请求不重试
+    let transport = RetryTransport::new(flaky(), 3, Duration::from_millis(1), tokio_sleep());
+    let request = RequestBuilder::new(Method::POST, "https://api.example.com/v0/me")
+        .build()
+        .unwrap();
+    assert!(transport.send(request).await.is_err());
+    assert_eq!(transport.retried(), 0);
+}
+
+#[tokio::test]
+async fn test_rate_limit_transport() {
+    let transport = RateLimitTransport::new(TestTransport::new(), 20.0, 1, tokio_sleep());
+    let request = RequestBuilder::new(Method::GET, "https://api.example.com/v0/me")
+        .build()
+        .unwrap();
+
+    let start = std::time::Instant::now();
+    let (first, second, third) = tokio::join!(
+        transport.send(request.clone()),
+        transport.send(request.clone()),
+        transport.send(request),
+    );
+    assert!(first.is_ok() && second.is_ok() && third.is_ok());
+    // 第一次请求消耗突发额度，之后每次至少等待 50ms
+    assert!(start.elapsed() >= Duration::from_millis(90));
+    assert_eq!(transport.sent(), 3);
+    assert!(transport.waited() >= Duration::from_millis(140));
+}
+
+/// 不依赖tokio，以wasm32上相同的方式构建并发送请求
+#[test]
+fn test_request_without_runtime() {
+    let transport = Arc::new(TestTransport::new().with_json(
+        Method::GET,
+        "/v0/episodes",
+        200,
+        serde_json::json!({"total": 0, "limit": 100, "offset": 0, "data": []}),
+    ));
+    let client = client(&transport);
+
+    let mut future = pin!(client.get_episodes(1, None, None, None));
+    let Poll::Ready(page) = future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    else {
+        panic!("测试传输层应立即完成");
+    };
+    assert_eq!(page.unwrap().total, 0);
+
+    let request = &transport.requests()[0];
+    assert_eq!(
+        request.url,
+        "https://api.example.com/v0/episodes?subject_id=1"
+    );
+    assert_eq!(
+        request.headers.contains_key(header::USER_AGENT),
+        SEND_USER_AGENT
+    );
+    assert_eq!(request.headers[header::AUTHORIZATION], "Bearer token");
+}
+
+#[test]
+fn test_request_builder() {
+    let request = RequestBuilder::new(Method::GET, "https://example.com/a?x=1")
+        .query(&[("q", "空 白")])
+        .query(&[("none", None::<u32>)])
+        .header("x-extra", "1")
+        .build()
+        .unwrap();
+    assert_eq!(
+        request.url,
+        "https://example.com/a?x=1&q=%E7%A9%BA+%E7%99%BD"
+    );
+    assert_eq!(request.headers["x-extra"], "1");
+
+    // 构建过程中的错误推迟到build时返回
+    let invalid = RequestBuilder::new(Method::GET, "https://example.com")
+        .header("x-extra", "换行\n")
+        .query(&[("q", "1")]);
+    assert!(invalid.build().is_err());
+}
+
+#[test]
+fn test_civil_days() {
+    assert_eq!(days_from_civil(1970, 1, 1), 0);
+    assert_eq!(days_from_civil(2024, 2, 29), 19782);
+    assert_eq!(days_from_civil(1969, 12, 31), -1);
+    for days in [-719468, -1, 0, 19782, 19783, 2932896] {
+        let (year, month, day) = civil_from_days(days);
+        assert_eq!(days_from_civil(year, month, day), days);
+    }
+    assert_eq!(civil_from_days(19783), (2024, 3, 1));
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn test_ureq_transport() {
+    use super::{
+        mock::{MockRoute, MockServer},
+        transport::UreqTransport,
+    };
+
+    let server = MockServer::start(vec![MockRoute::json(
+        "POST",
+        "/v0/users/-/collections/1",
+        202,
+        serde_json::Value::Null,
+    )]);
+    let client = crate::blocking::BangumiClient::from(BangumiClient::with_transport(
+        server.base_path().to_string(),
+        None,
+        Some("token".to_string()),
+        UreqTransport::default(),
+    ));
+    let update = CollectionSubjectUpdate {
+        rate: Some(8),
+        ..Default::default()
+    };
+    client
+        .post_collection_subject(1, Some(update.clone()))
+        .unwrap();
+    // 非2xx响应同样解析为Bangumi错误
+    assert!(client.get_me().is_err());
+
+    let requests = server.requests();
+    assert_eq!(
+        serde_json::from_str::<CollectionSubjectUpdate>(&requests[0].body).unwrap(),
+        update
+    );
+    assert!(
+        requests[0]
+            .headers
+            .contains(&("authorization".to_string(), "Bearer token".to_string()))
+    );
+}
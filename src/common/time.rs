@@ -0,0 +1,62 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 将公历年月日换算为距1970-01-01的天数
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// 将距1970-01-01的天数换算为公历年月日
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// 当前的Unix时间戳（秒）
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// 当前的Unix时间戳（秒）
+///
+/// 浏览器中没有系统时钟，`SystemTime::now`会panic，改用JavaScript的`Date.now()`
+#[cfg(target_arch = "wasm32")]
+pub fn now_timestamp() -> i64 {
+    (js_sys::Date::now() / 1000.0) as i64
+}
+
+/// 从固定起点开始经过的秒数，用于计时与限流
+#[cfg(not(target_arch = "wasm32"))]
+pub fn monotonic_seconds() -> f64 {
+    static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+    START
+        .get_or_init(std::time::Instant::now)
+        .elapsed()
+        .as_secs_f64()
+}
+
+/// 从固定起点开始经过的秒数，用于计时与限流
+///
+/// 浏览器中`Instant::now`会panic，改用JavaScript的`Date.now()`
+#[cfg(target_arch = "wasm32")]
+pub fn monotonic_seconds() -> f64 {
+    js_sys::Date::now() / 1000.0
+}
@@ -0,0 +1,708 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use anyhow::{Result, anyhow};
+use bytes::Bytes;
+use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, header};
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::time::monotonic_seconds;
+
+/// 传输层发送请求时返回的Future
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<HttpResponse>> + Send + 'a>>;
+
+/// 传输层发送请求时返回的Future
+///
+/// 浏览器中的fetch请求不能跨线程移动，因此wasm32上不要求`Send`
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<HttpResponse>> + 'a>>;
+
+/// 等待结束时完成的Future
+#[cfg(not(target_arch = "wasm32"))]
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// 等待结束时完成的Future
+#[cfg(target_arch = "wasm32")]
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// 等待指定时长的函数
+///
+/// 传输层不依赖特定的异步运行时，需要等待的包装层由调用方提供实现，
+/// 如`Arc::new(|duration| Box::pin(tokio::time::sleep(duration)))`
+pub type Sleep = Arc<dyn Fn(Duration) -> SleepFuture + Send + Sync>;
+
+/// 交给传输层发送的HTTP请求
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// HTTP方法
+    pub method: Method,
+    /// 完整的请求地址（含查询参数）
+    pub url: String,
+    /// 请求头
+    pub headers: HeaderMap,
+    /// 请求体
+    pub body: Option<Bytes>,
+}
+
+/// 传输层返回的HTTP响应，响应体已完整读入内存
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// 响应状态码
+    pub status: StatusCode,
+    /// 最终的请求地址（跟随重定向后）
+    pub url: String,
+    /// 响应头
+    pub headers: HeaderMap,
+    /// 响应体
+    pub body: Bytes,
+}
+
+impl HttpResponse {
+    /// 将响应体解析为JSON
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+
+    /// 响应体的原始字节
+    pub fn bytes(&self) -> Bytes {
+        self.body.clone()
+    }
+
+    /// 响应体文本（非UTF-8的部分以替换字符表示）
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).to_string()
+    }
+}
+
+/// HTTP传输层
+///
+/// 负责把 [`HttpRequest`] 发送出去并返回 [`HttpResponse`]，
+/// `BangumiClient`只负责构建请求与解析响应。非2xx的状态码不应视为错误，
+/// 由客户端统一解析为`BangumiError`
+pub trait Transport: Send + Sync {
+    /// 发送请求
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        (**self).send(request)
+    }
+}
+
+/// HTTP请求构建器
+///
+/// 提供与`reqwest::RequestBuilder`相同的常用方法，构建过程中的错误在
+/// [`RequestBuilder::build`] 时返回
+#[derive(Debug)]
+pub struct RequestBuilder {
+    request: Result<HttpRequest>,
+}
+
+impl RequestBuilder {
+    /// 创建请求构建器
+    pub fn new(method: Method, url: &str) -> Self {
+        RequestBuilder {
+            request: Ok(HttpRequest {
+                method,
+                url: url.to_string(),
+                headers: HeaderMap::new(),
+                body: None,
+            }),
+        }
+    }
+
+    fn map(self, f: impl FnOnce(HttpRequest) -> Result<HttpRequest>) -> Self {
+        RequestBuilder {
+            request: self.request.and_then(f),
+        }
+    }
+
+    /// 设置请求头
+    pub fn header<K, V>(self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.map(|mut request| {
+            let key = HeaderName::try_from(key).map_err(|error| anyhow!(error.into()))?;
+            let value = HeaderValue::try_from(value).map_err(|error| anyhow!(error.into()))?;
+            request.headers.insert(key, value);
+            Ok(request)
+        })
+    }
+
+    /// 设置Bearer令牌
+    pub fn bearer_auth(self, token: &str) -> Self {
+        self.header(header::AUTHORIZATION, format!("Bearer {token}"))
+    }
+
+    /// 追加查询参数
+    pub fn query<T: Serialize + ?Sized>(self, query: &T) -> Self {
+        self.map(|mut request| {
+            let query = serde_urlencoded::to_string(query)?;
+            if !query.is_empty() {
+                let separator = if request.url.contains('?') { '&' } else { '?' };
+                request.url = format!("{}{separator}{query}", request.url);
+            }
+            Ok(request)
+        })
+    }
+
+    /// 以JSON格式设置请求体
+    pub fn json<T: Serialize + ?Sized>(self, json: &T) -> Self {
+        self.map(|mut request| {
+            request.body = Some(Bytes::from(serde_json::to_vec(json)?));
+            request.headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            );
+            Ok(request)
+        })
+    }
+
+    /// 设置原始请求体
+    pub fn body(self, body: impl Into<Bytes>) -> Self {
+        self.map(|mut request| {
+            request.body = Some(body.into());
+            Ok(request)
+        })
+    }
+
+    /// 完成构建
+    pub fn build(self) -> Result<HttpRequest> {
+        self.request
+    }
+}
+
+/// 基于`reqwest`的传输层（需启用默认的`reqwest`特性）
+///
+/// 在wasm32上reqwest自动使用浏览器的fetch接口
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    /// reqwest HTTP客户端，多个传输层可共用同一个连接池
+    pub client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+    /// 使用已有的reqwest客户端
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers);
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+            let response = builder.send().await?;
+            let status = response.status();
+            let url = response.url().to_string();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?;
+            Ok(HttpResponse {
+                status,
+                url,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/// 基于`ureq`的同步传输层（需启用`ureq`特性）
+///
+/// 在Future内部以阻塞方式完成请求，不依赖异步运行时，
+/// 适合配合`blocking::BangumiClient`或在构建脚本中使用
+#[cfg(feature = "ureq")]
+#[derive(Debug, Clone)]
+pub struct UreqTransport {
+    /// ureq代理，需关闭`http_status_as_error`以便客户端解析错误响应
+    pub agent: ureq::Agent,
+}
+
+#[cfg(feature = "ureq")]
+impl Default for UreqTransport {
+    fn default() -> Self {
+        let config = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build();
+        UreqTransport {
+            agent: ureq::Agent::new_with_config(config),
+        }
+    }
+}
+
+#[cfg(feature = "ureq")]
+impl Transport for UreqTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut builder = http::Request::builder()
+                .method(request.method)
+                .uri(request.url);
+            if let Some(headers) = builder.headers_mut() {
+                *headers = request.headers;
+            }
+            let response = match request.body {
+                Some(body) => self.agent.run(builder.body(body.to_vec())?)?,
+                None => self.agent.run(builder.body(())?)?,
+            };
+            let url = ureq::ResponseExt::get_uri(&response).to_string();
+            let (parts, mut body) = response.into_parts();
+            Ok(HttpResponse {
+                status: parts.status,
+                url,
+                headers: parts.headers,
+                body: Bytes::from(body.read_to_vec()?),
+            })
+        })
+    }
+}
+
+/// 合并相同并发GET请求的传输层
+///
+/// 方法、地址与Authorization头都相同的GET请求在前一个请求完成前再次发出时，
+/// 不再交给内部传输层，而是等待进行中的请求并得到其响应的副本。
+/// 请求完成后即从表中移除，不缓存结果；其他方法的请求直接转发。
+/// 不依赖异步运行时，发起请求的任务被取消时等待中的请求返回错误
+#[derive(Debug, Default)]
+pub struct SingleFlightTransport<T> {
+    inner: T,
+    flights: Mutex<HashMap<String, Arc<Flight>>>,
+    coalesced: AtomicU64,
+}
+
+/// 进行中的请求
+#[derive(Debug, Default)]
+struct Flight {
+    state: Mutex<FlightState>,
+}
+
+#[derive(Debug, Default)]
+struct FlightState {
+    /// 请求结果，传输层错误以文本形式共享
+    result: Option<Result<HttpResponse, String>>,
+    /// 等待结果的任务
+    wakers: Vec<Waker>,
+}
+
+impl<T> SingleFlightTransport<T> {
+    /// 包装内部传输层
+    pub fn new(inner: T) -> Self {
+        SingleFlightTransport {
+            inner,
+            flights: Mutex::new(HashMap::new()),
+            coalesced: AtomicU64::new(0),
+        }
+    }
+
+    /// 被合并（未实际发出）的请求数量
+    pub fn coalesced(&self) -> u64 {
+        self.coalesced.load(Ordering::Relaxed)
+    }
+
+    fn flights(&self) -> MutexGuard<'_, HashMap<String, Arc<Flight>>> {
+        self.flights
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl Flight {
+    fn state(&self) -> MutexGuard<'_, FlightState> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// 写入结果并唤醒等待的任务，已有结果时忽略
+    fn complete(&self, result: Result<HttpResponse, String>) {
+        let mut state = self.state();
+        if state.result.is_none() {
+            state.result = Some(result);
+            state.wakers.drain(..).for_each(Waker::wake);
+        }
+    }
+}
+
+/// 等待进行中请求的Future
+struct Follower(Arc<Flight>);
+
+impl Future for Follower {
+    type Output = Result<HttpResponse>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.0.state();
+        match &state.result {
+            Some(Ok(response)) => Poll::Ready(Ok(response.clone())),
+            Some(Err(error)) => Poll::Ready(Err(anyhow!(error.clone()))),
+            None => {
+                if !state
+                    .wakers
+                    .iter()
+                    .any(|waker| waker.will_wake(context.waker()))
+                {
+                    state.wakers.push(context.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// 发起请求的一方结束（完成或被取消）时移除请求并通知等待的任务
+struct Leader<'a> {
+    flights: &'a Mutex<HashMap<String, Arc<Flight>>>,
+    key: String,
+    flight: Arc<Flight>,
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        let mut flights = self
+            .flights
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if flights
+            .get(&self.key)
+            .is_some_and(|flight| Arc::ptr_eq(flight, &self.flight))
+        {
+            flights.remove(&self.key);
+        }
+        drop(flights);
+        self.flight.complete(Err("合并的请求已被取消".to_string()));
+    }
+}
+
+/// 可合并请求的键，非GET请求返回None
+fn flight_key(request: &HttpRequest) -> Option<String> {
+    if request.method != Method::GET {
+        return None;
+    }
+    let authorization = request
+        .headers
+        .get(header::AUTHORIZATION)
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).to_string())
+        .unwrap_or_default();
+    Some(format!("{}\n{authorization}", request.url))
+}
+
+impl<T: Transport> Transport for SingleFlightTransport<T> {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let Some(key) = flight_key(&request) else {
+            return self.inner.send(request);
+        };
+        let mut flights = self.flights();
+        if let Some(flight) = flights.get(&key) {
+            self.coalesced.fetch_add(1, Ordering::Relaxed);
+            return Box::pin(Follower(flight.clone()));
+        }
+        let flight = Arc::new(Flight::default());
+        flights.insert(key.clone(), flight.clone());
+        drop(flights);
+
+        let leader = Leader {
+            flights: &self.flights,
+            key,
+            flight,
+        };
+        Box::pin(async move {
+            let result = self.inner.send(request).await;
+            leader.flight.complete(match &result {
+                Ok(response) => Ok(response.clone()),
+                Err(error) => Err(format!("{error:#}")),
+            });
+            drop(leader);
+            result
+        })
+    }
+}
+
+/// 请求方法是否幂等（重复发送不会产生额外的副作用）
+pub fn is_idempotent(request: &HttpRequest) -> bool {
+    matches!(
+        request.method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+    )
+}
+
+/// 失败时重试幂等请求的传输层
+///
+/// 内部传输层返回错误（未收到响应），或返回429/502/503/504时，
+/// 按指数退避重试，上游给出`Retry-After`（秒）时至少等待该时长。
+/// 判断只依赖 [`HttpResponse`] 与错误本身，与具体的HTTP后端无关
+pub struct RetryTransport<T> {
+    inner: T,
+    retries: u32,
+    backoff: Duration,
+    sleep: Sleep,
+    idempotent: fn(&HttpRequest) -> bool,
+    retried: AtomicU64,
+}
+
+impl<T> RetryTransport<T> {
+    /// 包装内部传输层
+    ///
+    /// # 参数
+    /// - `inner`: 内部传输层
+    /// - `retries`: 最大重试次数
+    /// - `backoff`: 首次重试前的等待时间，之后逐次翻倍
+    /// - `sleep`: 等待函数
+    pub fn new(inner: T, retries: u32, backoff: Duration, sleep: Sleep) -> Self {
+        RetryTransport {
+            inner,
+            retries,
+            backoff,
+            sleep,
+            idempotent: is_idempotent,
+            retried: AtomicU64::new(0),
+        }
+    }
+
+    /// 设置判断请求可否重试的函数，默认为 [`is_idempotent`]
+    pub fn with_idempotent(mut self, idempotent: fn(&HttpRequest) -> bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    /// 累计的重试次数
+    pub fn retried(&self) -> u64 {
+        self.retried.load(Ordering::Relaxed)
+    }
+
+    /// 内部传输层
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+/// 可以重试的响应状态码
+fn retryable(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+impl<T: Transport> Transport for RetryTransport<T> {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        if self.retries == 0 || !(self.idempotent)(&request) {
+            return self.inner.send(request);
+        }
+        Box::pin(async move {
+            let mut attempt = 0;
+            loop {
+                let backoff = self.backoff * 2u32.saturating_pow(attempt.min(16));
+                let wait = match self.inner.send(request.clone()).await {
+                    Ok(response) if attempt < self.retries && retryable(response.status) => {
+                        let retry_after = response
+                            .headers
+                            .get(header::RETRY_AFTER)
+                            .and_then(|value| value.to_str().ok())
+                            .and_then(|value| value.parse().ok())
+                            .map(Duration::from_secs);
+                        retry_after.map_or(backoff, |retry_after| retry_after.max(backoff))
+                    }
+                    Err(_) if attempt < self.retries => backoff,
+                    result => return result,
+                };
+                attempt += 1;
+                self.retried.fetch_add(1, Ordering::Relaxed);
+                (self.sleep)(wait).await;
+            }
+        })
+    }
+}
+
+/// 以令牌桶限制请求速率的传输层
+///
+/// 每个请求在发出前预约一个令牌，令牌不足时等待到预约的时刻，
+/// 排队的请求按到达顺序依次放行
+pub struct RateLimitTransport<T> {
+    inner: T,
+    rate: f64,
+    burst: f64,
+    sleep: Sleep,
+    /// 剩余令牌数（预约后可能为负）与上次补充的时刻
+    bucket: Mutex<(f64, f64)>,
+    sent: AtomicU64,
+    waited_ms: AtomicU64,
+}
+
+impl<T> RateLimitTransport<T> {
+    /// 包装内部传输层
+    ///
+    /// # 参数
+    /// - `inner`: 内部传输层
+    /// - `rate`: 每秒允许的请求数，需大于0
+    /// - `burst`: 令牌桶容量，允许的瞬时突发请求数
+    /// - `sleep`: 等待函数
+    pub fn new(inner: T, rate: f64, burst: u32, sleep: Sleep) -> Self {
+        RateLimitTransport {
+            inner,
+            rate,
+            burst: burst as f64,
+            sleep,
+            bucket: Mutex::new((burst as f64, monotonic_seconds())),
+            sent: AtomicU64::new(0),
+            waited_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// 预约一个令牌，返回需要等待的时间
+    fn reserve(&self) -> Duration {
+        let mut bucket = self
+            .bucket
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let now = monotonic_seconds();
+        let tokens = (bucket.0 + (now - bucket.1) * self.rate).min(self.burst) - 1.0;
+        *bucket = (tokens, now);
+        if tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-tokens / self.rate)
+        }
+    }
+
+    /// 经过限流发出的请求数
+    pub fn sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    /// 累计的限流等待时间
+    pub fn waited(&self) -> Duration {
+        Duration::from_millis(self.waited_ms.load(Ordering::Relaxed))
+    }
+}
+
+impl<T: Transport> Transport for RateLimitTransport<T> {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let wait = self.reserve();
+        Box::pin(async move {
+            if !wait.is_zero() {
+                self.waited_ms
+                    .fetch_add(wait.as_millis() as u64, Ordering::Relaxed);
+                (self.sleep)(wait).await;
+            }
+            self.sent.fetch_add(1, Ordering::Relaxed);
+            self.inner.send(request).await
+        })
+    }
+}
+
+/// 返回预设响应的传输层，不建立网络连接
+///
+/// 按"方法 + 路径"（不含查询参数）匹配预设的响应并记录收到的请求，
+/// 未匹配的请求返回404及Bangumi格式的错误体，用于测试依赖客户端的代码
+#[derive(Debug, Default)]
+pub struct TestTransport {
+    routes: Vec<(Method, String, HttpResponse)>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl TestTransport {
+    /// 创建没有预设响应的传输层
+    pub fn new() -> Self {
+        TestTransport::default()
+    }
+
+    /// 添加一个返回JSON的预设响应
+    pub fn with_json(
+        self,
+        method: Method,
+        path: &str,
+        status: u16,
+        body: serde_json::Value,
+    ) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        self.with_response(
+            method,
+            path,
+            HttpResponse {
+                status: StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                url: String::new(),
+                headers,
+                body: Bytes::from(body.to_string()),
+            },
+        )
+    }
+
+    /// 添加一个预设响应，响应的`url`在返回时替换为实际的请求地址
+    pub fn with_response(mut self, method: Method, path: &str, response: HttpResponse) -> Self {
+        self.routes.push((method, path.to_string(), response));
+        self
+    }
+
+    /// 已收到的请求
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+}
+
+/// 从完整地址中取出路径部分
+fn url_path(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = rest.find('/').map_or("/", |index| &rest[index..]);
+    path.split(['?', '#']).next().unwrap_or(path)
+}
+
+impl Transport for TestTransport {
+    fn send(&self, request: HttpRequest) -> TransportFuture<'_> {
+        let path = url_path(&request.url).to_string();
+        let mut response = self
+            .routes
+            .iter()
+            .find(|(method, route, _)| method == request.method && route == &path)
+            .map(|(_, _, response)| response.clone())
+            .unwrap_or_else(|| {
+                let body = serde_json::json!({
+                    "title": "Not Found",
+                    "details": {"error": null, "path": path, "method": request.method.as_str()},
+                    "request_id": null,
+                    "description": "test route not found"
+                });
+                HttpResponse {
+                    status: StatusCode::NOT_FOUND,
+                    url: String::new(),
+                    headers: HeaderMap::new(),
+                    body: Bytes::from(body.to_string()),
+                }
+            });
+        response.url = request.url.clone();
+        self.requests
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .push(request);
+        Box::pin(async move { Ok(response) })
+    }
+}
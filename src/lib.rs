@@ -1,2 +1,4 @@
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod common;
 pub mod module;
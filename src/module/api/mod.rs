@@ -0,0 +1,6 @@
+/// 按领域划分的接口特征及内存模拟客户端
+pub mod model;
+pub mod service;
+
+#[cfg(all(test, any(feature = "reqwest", feature = "ureq")))]
+pub mod test;
@@ -0,0 +1,372 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    future::Future,
+    sync::Mutex,
+};
+
+use anyhow::Result;
+use bytes::Bytes;
+
+use crate::module::{
+    collection::model::{
+        CollectionCharacter, CollectionEpisode, CollectionEpisodeType, CollectionEpisodeUpdate,
+        CollectionEpisodesUpdate, CollectionPerson, CollectionSubject, CollectionSubjectUpdate,
+        CollectionType,
+    },
+    episode::model::{Episode, EpisodeType},
+    indice::model::{
+        Index, IndexBasicInfo, IndexSubject, IndexSubjectAddInfo, IndexSubjectEditInfo,
+    },
+    model::{ImageType, Paged},
+    revision::model::{
+        RevisionCharacter, RevisionCommon, RevisionEpisode, RevisionPerson, RevisionSubject,
+    },
+    subject::model::{
+        DailyCalendarItem, Subject, SubjectBrowseSort, SubjectCategory, SubjectCharacter,
+        SubjectPerson, SubjectSearch, SubjectSubject, SubjectType,
+    },
+    user::model::{AvatarType, User, UserPublic},
+};
+
+/// 条目与剧集接口
+///
+/// 方法与 [`crate::common::model::BangumiClient`] 上的同名方法一致
+pub trait SubjectApi {
+    /// 获取每日放送
+    fn get_calendar(&self) -> impl Future<Output = Result<Vec<DailyCalendarItem>>> + Send;
+
+    /// 搜索条目
+    fn search_subjects(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        payload: Option<SubjectSearch>,
+    ) -> impl Future<Output = Result<Paged<Subject>>> + Send;
+
+    /// 浏览条目
+    #[allow(clippy::too_many_arguments)]
+    fn get_subjects(
+        &self,
+        r#type: SubjectType,
+        cat: Option<SubjectCategory>,
+        series: Option<bool>,
+        platform: Option<&str>,
+        sort: Option<SubjectBrowseSort>,
+        year: Option<u32>,
+        month: Option<u32>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> impl Future<Output = Result<Paged<Subject>>> + Send;
+
+    /// 获取条目详情
+    fn get_subject(&self, subject_id: u32) -> impl Future<Output = Result<Subject>> + Send;
+
+    /// 获取条目封面
+    fn get_subject_image(
+        &self,
+        subject_id: u32,
+        r#type: ImageType,
+    ) -> impl Future<Output = Result<Bytes>> + Send;
+
+    /// 获取条目关联的人物
+    fn get_subject_persons(
+        &self,
+        subject_id: u32,
+    ) -> impl Future<Output = Result<Vec<SubjectPerson>>> + Send;
+
+    /// 获取条目关联的角色
+    fn get_subject_characters(
+        &self,
+        subject_id: u32,
+    ) -> impl Future<Output = Result<Vec<SubjectCharacter>>> + Send;
+
+    /// 获取条目关联的条目
+    fn get_subject_subjects(
+        &self,
+        subject_id: u32,
+    ) -> impl Future<Output = Result<Vec<SubjectSubject>>> + Send;
+
+    /// 获取条目的剧集
+    fn get_episodes(
+        &self,
+        subject_id: u32,
+        r#type: Option<EpisodeType>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> impl Future<Output = Result<Paged<Episode>>> + Send;
+
+    /// 获取剧集详情
+    fn get_episode(&self, episode_id: u32) -> impl Future<Output = Result<Episode>> + Send;
+}
+
+/// 收藏接口
+pub trait CollectionApi {
+    /// 获取用户的条目收藏
+    fn get_collection_subjects(
+        &self,
+        username: &str,
+        subject_type: Option<SubjectType>,
+        r#type: Option<CollectionType>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> impl Future<Output = Result<Paged<CollectionSubject>>> + Send;
+
+    /// 获取用户对单个条目的收藏
+    fn get_collection_subject(
+        &self,
+        username: &str,
+        subject_id: u32,
+    ) -> impl Future<Output = Result<CollectionSubject>> + Send;
+
+    /// 新增或修改当前用户的条目收藏
+    fn post_collection_subject(
+        &self,
+        subject_id: u32,
+        payload: Option<CollectionSubjectUpdate>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// 修改当前用户已有的条目收藏
+    fn patch_collection_subject(
+        &self,
+        subject_id: u32,
+        payload: Option<CollectionSubjectUpdate>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// 获取当前用户对条目各剧集的收藏
+    fn get_collection_episodes(
+        &self,
+        subject_id: u32,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        episode_type: Option<EpisodeType>,
+    ) -> impl Future<Output = Result<Paged<CollectionEpisode>>> + Send;
+
+    /// 批量修改当前用户的剧集收藏
+    fn patch_collection_episodes(
+        &self,
+        subject_id: u32,
+        payload: Option<CollectionEpisodesUpdate>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// 获取当前用户对单个剧集的收藏
+    fn get_collection_episode(
+        &self,
+        episode_id: u32,
+    ) -> impl Future<Output = Result<CollectionEpisode>> + Send;
+
+    /// 修改当前用户对单个剧集的收藏
+    fn put_collection_episode(
+        &self,
+        episode_id: u32,
+        payload: Option<CollectionEpisodeUpdate>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// 获取用户的角色收藏
+    fn get_collection_characters(
+        &self,
+        username: &str,
+    ) -> impl Future<Output = Result<Paged<CollectionCharacter>>> + Send;
+
+    /// 获取用户对单个角色的收藏
+    fn get_collection_character(
+        &self,
+        username: &str,
+        character_id: u32,
+    ) -> impl Future<Output = Result<CollectionCharacter>> + Send;
+
+    /// 获取用户的人物收藏
+    fn get_collection_persons(
+        &self,
+        username: &str,
+    ) -> impl Future<Output = Result<Paged<CollectionPerson>>> + Send;
+
+    /// 获取用户对单个人物的收藏
+    fn get_collection_person(
+        &self,
+        username: &str,
+        person_id: u32,
+    ) -> impl Future<Output = Result<CollectionPerson>> + Send;
+}
+
+/// 目录接口
+pub trait IndexApi {
+    /// 创建目录
+    fn add_index(&self) -> impl Future<Output = Result<Index>> + Send;
+
+    /// 获取目录详情
+    fn get_index(&self, index_id: u32) -> impl Future<Output = Result<Index>> + Send;
+
+    /// 编辑目录信息
+    fn edit_index(
+        &self,
+        index_id: u32,
+        payload: Option<IndexBasicInfo>,
+    ) -> impl Future<Output = Result<Index>> + Send;
+
+    /// 获取目录中的条目
+    fn get_index_subjects(
+        &self,
+        index_id: u32,
+        r#type: Option<SubjectType>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> impl Future<Output = Result<Paged<IndexSubject>>> + Send;
+
+    /// 向目录添加条目
+    fn add_index_subject(
+        &self,
+        index_id: u32,
+        payload: Option<IndexSubjectAddInfo>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// 编辑目录中的条目
+    fn edit_index_subject(
+        &self,
+        index_id: u32,
+        subject_id: u32,
+        payload: Option<IndexSubjectEditInfo>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// 从目录中删除条目
+    fn delete_index_subject(
+        &self,
+        index_id: u32,
+        subject_id: u32,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// 收藏目录
+    fn collect_index(&self, index_id: u32) -> impl Future<Output = Result<()>> + Send;
+
+    /// 取消收藏目录
+    fn uncollect_index(&self, index_id: u32) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// 修订历史接口
+pub trait RevisionApi {
+    /// 获取人物的修订列表
+    fn get_revision_persons(
+        &self,
+        person_id: u32,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> impl Future<Output = Result<Paged<RevisionCommon>>> + Send;
+
+    /// 获取人物修订详情
+    fn get_revision_person(
+        &self,
+        revision_id: u32,
+    ) -> impl Future<Output = Result<RevisionPerson>> + Send;
+
+    /// 获取角色的修订列表
+    fn get_revision_characters(
+        &self,
+        character_id: u32,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> impl Future<Output = Result<Paged<RevisionCommon>>> + Send;
+
+    /// 获取角色修订详情
+    fn get_revision_character(
+        &self,
+        revision_id: u32,
+    ) -> impl Future<Output = Result<RevisionCharacter>> + Send;
+
+    /// 获取条目的修订列表
+    fn get_revision_subjects(
+        &self,
+        subject_id: u32,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> impl Future<Output = Result<Paged<RevisionCommon>>> + Send;
+
+    /// 获取条目修订详情
+    fn get_revision_subject(
+        &self,
+        revision_id: u32,
+    ) -> impl Future<Output = Result<RevisionSubject>> + Send;
+
+    /// 获取剧集的修订列表
+    fn get_revision_episodes(
+        &self,
+        episode_id: u32,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> impl Future<Output = Result<Paged<RevisionCommon>>> + Send;
+
+    /// 获取剧集修订详情
+    fn get_revision_episode(
+        &self,
+        revision_id: u32,
+    ) -> impl Future<Output = Result<RevisionEpisode>> + Send;
+}
+
+/// 用户接口
+pub trait UserApi {
+    /// 获取用户信息
+    fn get_user(&self, username: &str) -> impl Future<Output = Result<UserPublic>> + Send;
+
+    /// 获取用户头像
+    fn get_user_avatar(
+        &self,
+        username: &str,
+        r#type: AvatarType,
+    ) -> impl Future<Output = Result<Bytes>> + Send;
+
+    /// 获取当前登录的用户
+    fn get_me(&self) -> impl Future<Output = Result<User>> + Send;
+}
+
+/// 内存中的模拟客户端
+///
+/// 实现全部接口特征，供依赖这些特征的代码在测试中替换真实客户端。
+/// 条目、剧集、用户与修订记录通过`insert_*`方法预置；收藏与目录的写操作
+/// 保存在内存中，之后的读操作会反映这些修改。
+/// 以`-`表示的当前用户由 [`FakeClient::new`] 指定，未指定时写操作返回错误
+#[derive(Default)]
+pub struct FakeClient {
+    /// 模拟的服务端状态
+    pub(crate) state: Mutex<FakeState>,
+}
+
+/// 模拟客户端保存的数据
+#[derive(Default)]
+pub(crate) struct FakeState {
+    /// 当前登录的用户
+    pub(crate) me: Option<User>,
+    /// 其他用户，按用户名索引
+    pub(crate) users: HashMap<String, UserPublic>,
+    /// 条目
+    pub(crate) subjects: BTreeMap<u32, Subject>,
+    /// 剧集
+    pub(crate) episodes: BTreeMap<u32, Episode>,
+    /// 条目关联的人物
+    pub(crate) subject_persons: HashMap<u32, Vec<SubjectPerson>>,
+    /// 条目关联的角色
+    pub(crate) subject_characters: HashMap<u32, Vec<SubjectCharacter>>,
+    /// 条目关联的条目
+    pub(crate) subject_relations: HashMap<u32, Vec<SubjectSubject>>,
+    /// 条目收藏，按（用户名，条目ID）索引，值中的序号用于按修改先后排序
+    pub(crate) collections: BTreeMap<(String, u32), (u64, CollectionSubject)>,
+    /// 剧集收藏，按（用户名，剧集ID）索引
+    pub(crate) episode_collections: HashMap<(String, u32), (CollectionEpisodeType, u32)>,
+    /// 角色收藏，按用户名索引
+    pub(crate) character_collections: HashMap<String, Vec<CollectionCharacter>>,
+    /// 人物收藏，按用户名索引
+    pub(crate) person_collections: HashMap<String, Vec<CollectionPerson>>,
+    /// 目录
+    pub(crate) indices: BTreeMap<u32, Index>,
+    /// 目录中的条目及其排序值
+    pub(crate) index_subjects: HashMap<u32, Vec<(u32, IndexSubject)>>,
+    /// 收藏了目录的用户
+    pub(crate) index_collectors: HashMap<u32, BTreeSet<String>>,
+    /// 人物修订，值为（人物ID，修订详情）
+    pub(crate) person_revisions: BTreeMap<u32, (u32, RevisionPerson)>,
+    /// 角色修订，值为（角色ID，修订详情）
+    pub(crate) character_revisions: BTreeMap<u32, (u32, RevisionCharacter)>,
+    /// 条目修订，值为（条目ID，修订详情）
+    pub(crate) subject_revisions: BTreeMap<u32, (u32, RevisionSubject)>,
+    /// 剧集修订，值为（剧集ID，修订详情）
+    pub(crate) episode_revisions: BTreeMap<u32, (u32, RevisionEpisode)>,
+    /// 下一个写操作的序号
+    pub(crate) sequence: u64,
+}
@@ -0,0 +1,1022 @@
+use std::{
+    cmp::Reverse,
+    sync::{Mutex, MutexGuard},
+};
+
+use anyhow::{Result, bail};
+use bytes::Bytes;
+
+use crate::{
+    common::{
+        model::BangumiClient,
+        time::{civil_from_days, now_timestamp},
+    },
+    module::{
+        collection::model::{
+            CollectionCharacter, CollectionEpisode, CollectionEpisodeType, CollectionEpisodeUpdate,
+            CollectionEpisodesUpdate, CollectionPerson, CollectionSubject, CollectionSubjectUpdate,
+            CollectionType, UserSubject,
+        },
+        episode::model::{Episode, EpisodeType},
+        indice::model::{
+            Index, IndexBasicInfo, IndexSubject, IndexSubjectAddInfo, IndexSubjectEditInfo,
+        },
+        model::{ImageType, Paged, Stat, Tag},
+        revision::model::{
+            Creator, RevisionCharacter, RevisionCommon, RevisionEpisode, RevisionPerson,
+            RevisionSubject,
+        },
+        subject::model::{
+            DailyCalendarItem, Subject, SubjectBrowseSort, SubjectCategory, SubjectCharacter,
+            SubjectPerson, SubjectSearch, SubjectSubject, SubjectType,
+        },
+        user::model::{AvatarType, User, UserPublic},
+    },
+};
+
+use super::model::{
+    CollectionApi, FakeClient, FakeState, IndexApi, RevisionApi, SubjectApi, UserApi,
+};
+
+/// 未指定`limit`时列表接口返回的数量
+const DEFAULT_LIMIT: u32 = 30;
+
+/// 为BangumiClient实现接口特征，直接转发到同名的固有方法
+macro_rules! forward {
+    ($api:ident { $($(#[$meta:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)* }) => {
+        impl $api for BangumiClient {
+            $(
+                $(#[$meta])*
+                async fn $name(&self $(, $arg: $ty)*) -> Result<$ret> {
+                    BangumiClient::$name(self $(, $arg)*).await
+                }
+            )*
+        }
+    };
+}
+
+forward!(SubjectApi {
+    fn get_calendar(&self) -> Vec<DailyCalendarItem>;
+    fn search_subjects(&self, limit: Option<u32>, offset: Option<u32>, payload: Option<SubjectSearch>) -> Paged<Subject>;
+    #[allow(clippy::too_many_arguments)]
+    fn get_subjects(&self, r#type: SubjectType, cat: Option<SubjectCategory>, series: Option<bool>, platform: Option<&str>, sort: Option<SubjectBrowseSort>, year: Option<u32>, month: Option<u32>, limit: Option<u32>, offset: Option<u32>) -> Paged<Subject>;
+    fn get_subject(&self, subject_id: u32) -> Subject;
+    fn get_subject_image(&self, subject_id: u32, r#type: ImageType) -> Bytes;
+    fn get_subject_persons(&self, subject_id: u32) -> Vec<SubjectPerson>;
+    fn get_subject_characters(&self, subject_id: u32) -> Vec<SubjectCharacter>;
+    fn get_subject_subjects(&self, subject_id: u32) -> Vec<SubjectSubject>;
+    fn get_episodes(&self, subject_id: u32, r#type: Option<EpisodeType>, limit: Option<u32>, offset: Option<u32>) -> Paged<Episode>;
+    fn get_episode(&self, episode_id: u32) -> Episode;
+});
+
+forward!(CollectionApi {
+    fn get_collection_subjects(&self, username: &str, subject_type: Option<SubjectType>, r#type: Option<CollectionType>, limit: Option<u32>, offset: Option<u32>) -> Paged<CollectionSubject>;
+    fn get_collection_subject(&self, username: &str, subject_id: u32) -> CollectionSubject;
+    fn post_collection_subject(&self, subject_id: u32, payload: Option<CollectionSubjectUpdate>) -> ();
+    fn patch_collection_subject(&self, subject_id: u32, payload: Option<CollectionSubjectUpdate>) -> ();
+    fn get_collection_episodes(&self, subject_id: u32, offset: Option<u32>, limit: Option<u32>, episode_type: Option<EpisodeType>) -> Paged<CollectionEpisode>;
+    fn patch_collection_episodes(&self, subject_id: u32, payload: Option<CollectionEpisodesUpdate>) -> ();
+    fn get_collection_episode(&self, episode_id: u32) -> CollectionEpisode;
+    fn put_collection_episode(&self, episode_id: u32, payload: Option<CollectionEpisodeUpdate>) -> ();
+    fn get_collection_characters(&self, username: &str) -> Paged<CollectionCharacter>;
+    fn get_collection_character(&self, username: &str, character_id: u32) -> CollectionCharacter;
+    fn get_collection_persons(&self, username: &str) -> Paged<CollectionPerson>;
+    fn get_collection_person(&self, username: &str, person_id: u32) -> CollectionPerson;
+});
+
+forward!(IndexApi {
+    fn add_index(&self) -> Index;
+    fn get_index(&self, index_id: u32) -> Index;
+    fn edit_index(&self, index_id: u32, payload: Option<IndexBasicInfo>) -> Index;
+    fn get_index_subjects(&self, index_id: u32, r#type: Option<SubjectType>, limit: Option<u32>, offset: Option<u32>) -> Paged<IndexSubject>;
+    fn add_index_subject(&self, index_id: u32, payload: Option<IndexSubjectAddInfo>) -> ();
+    fn edit_index_subject(&self, index_id: u32, subject_id: u32, payload: Option<IndexSubjectEditInfo>) -> ();
+    fn delete_index_subject(&self, index_id: u32, subject_id: u32) -> ();
+    fn collect_index(&self, index_id: u32) -> ();
+    fn uncollect_index(&self, index_id: u32) -> ();
+});
+
+forward!(RevisionApi {
+    fn get_revision_persons(&self, person_id: u32, limit: Option<u32>, offset: Option<u32>) -> Paged<RevisionCommon>;
+    fn get_revision_person(&self, revision_id: u32) -> RevisionPerson;
+    fn get_revision_characters(&self, character_id: u32, limit: Option<u32>, offset: Option<u32>) -> Paged<RevisionCommon>;
+    fn get_revision_character(&self, revision_id: u32) -> RevisionCharacter;
+    fn get_revision_subjects(&self, subject_id: u32, limit: Option<u32>, offset: Option<u32>) -> Paged<RevisionCommon>;
+    fn get_revision_subject(&self, revision_id: u32) -> RevisionSubject;
+    fn get_revision_episodes(&self, episode_id: u32, limit: Option<u32>, offset: Option<u32>) -> Paged<RevisionCommon>;
+    fn get_revision_episode(&self, revision_id: u32) -> RevisionEpisode;
+});
+
+forward!(UserApi {
+    fn get_user(&self, username: &str) -> UserPublic;
+    fn get_user_avatar(&self, username: &str, r#type: AvatarType) -> Bytes;
+    fn get_me(&self) -> User;
+});
+
+/// 按`limit`与`offset`截取一页数据
+fn page<T>(items: Vec<T>, limit: Option<u32>, offset: Option<u32>) -> Paged<T> {
+    let total = items.len() as u32;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let offset = offset.unwrap_or(0);
+    let data = items
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect();
+    Paged {
+        total,
+        limit,
+        offset,
+        data: Some(data),
+    }
+}
+
+/// 当前时间，格式与API返回的时间一致（`YYYY-MM-DDTHH:MM:SSZ`）
+fn now() -> String {
+    let timestamp = now_timestamp();
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+    let seconds = timestamp.rem_euclid(86400);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// 收藏条目中附带的条目信息
+fn user_subject(subject: &Subject) -> UserSubject {
+    let stats = &subject.collection;
+    UserSubject {
+        id: subject.id,
+        r#type: subject.r#type.clone(),
+        name: subject.name.clone(),
+        name_cn: subject.name_cn.clone(),
+        short_summary: subject.summary.chars().take(120).collect(),
+        date: subject.date.clone().unwrap_or_default(),
+        images: subject.images.clone(),
+        volumes: subject.volumes,
+        eps: subject.eps,
+        collection_total: [
+            stats.wish,
+            stats.collect,
+            stats.doing,
+            stats.on_hold,
+            stats.dropped,
+        ]
+        .into_iter()
+        .flatten()
+        .sum(),
+        score: subject.rating.score,
+        rank: subject.rating.rank,
+        tags: subject
+            .tags
+            .iter()
+            .map(|tag| Tag {
+                name: tag.name.clone(),
+                count: tag.count,
+                total_cont: tag.total_cont,
+            })
+            .collect(),
+    }
+}
+
+/// 将收藏修改应用到已有的收藏记录
+fn apply_update(
+    collection: &mut CollectionSubject,
+    payload: CollectionSubjectUpdate,
+) -> Result<()> {
+    if let Some(rate) = payload.rate {
+        if rate > 10 {
+            bail!(format!("评分必须在0到10之间: {rate}"));
+        }
+        collection.rate = rate;
+    }
+    if let Some(r#type) = payload.r#type {
+        collection.r#type = r#type;
+    }
+    if let Some(ep_status) = payload.ep_status {
+        collection.ep_status = ep_status;
+    }
+    if let Some(vol_status) = payload.vol_status {
+        collection.vol_status = vol_status;
+    }
+    if let Some(comment) = payload.comment {
+        collection.comment = Some(comment);
+    }
+    if let Some(private) = payload.private {
+        collection.private = private;
+    }
+    if let Some(tags) = payload.tags {
+        collection.tags = tags;
+    }
+    collection.updated_at = now();
+    Ok(())
+}
+
+/// 修订列表中的通用字段
+macro_rules! revision_common {
+    ($revision:expr) => {
+        RevisionCommon {
+            id: $revision.id,
+            r#type: $revision.r#type,
+            creator: $revision.creator.clone(),
+            summary: $revision.summary.clone(),
+            created_at: $revision.created_at.clone(),
+        }
+    };
+}
+
+impl FakeState {
+    /// 当前登录的用户
+    fn me(&self) -> Result<&User> {
+        match &self.me {
+            Some(me) => Ok(me),
+            None => bail!("模拟客户端未设置当前用户"),
+        }
+    }
+
+    fn subject(&self, subject_id: u32) -> Result<&Subject> {
+        match self.subjects.get(&subject_id) {
+            Some(subject) => Ok(subject),
+            None => bail!(format!("条目{subject_id}不存在")),
+        }
+    }
+
+    fn episode(&self, episode_id: u32) -> Result<&Episode> {
+        match self.episodes.get(&episode_id) {
+            Some(episode) => Ok(episode),
+            None => bail!(format!("剧集{episode_id}不存在")),
+        }
+    }
+
+    /// 当前用户有权编辑的目录
+    fn own_index(&mut self, index_id: u32) -> Result<&mut Index> {
+        let username = self.me()?.username.clone();
+        match self.indices.get_mut(&index_id) {
+            Some(index) if index.creator.username == username => Ok(index),
+            Some(_) => bail!(format!("无权修改目录{index_id}")),
+            None => bail!(format!("目录{index_id}不存在")),
+        }
+    }
+
+    /// 可被指定用户看到的条目收藏
+    fn visible_collection(&self, username: &str, subject_id: u32) -> Option<&CollectionSubject> {
+        let own = self.me.as_ref().is_some_and(|me| me.username == username);
+        self.collections
+            .get(&(username.to_string(), subject_id))
+            .map(|(_, collection)| collection)
+            .filter(|collection| own || !collection.private)
+    }
+
+    fn next_sequence(&mut self) -> u64 {
+        self.sequence += 1;
+        self.sequence
+    }
+
+    fn collection_episode(&self, username: &str, episode: &Episode) -> CollectionEpisode {
+        let (r#type, updated_at) = self
+            .episode_collections
+            .get(&(username.to_string(), episode.id))
+            .cloned()
+            .unwrap_or((CollectionEpisodeType::Not, 0));
+        CollectionEpisode {
+            episode: episode.clone(),
+            r#type,
+            updated_at,
+        }
+    }
+}
+
+impl FakeClient {
+    /// 创建以`me`为当前用户的模拟客户端
+    pub fn new(me: User) -> Self {
+        let client = FakeClient::default();
+        client.lock().me = Some(me);
+        client
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FakeState> {
+        lock(&self.state)
+    }
+
+    /// 预置用户
+    pub fn insert_user(&self, user: UserPublic) {
+        self.lock().users.insert(user.username.clone(), user);
+    }
+
+    /// 预置条目
+    pub fn insert_subject(&self, subject: Subject) {
+        self.lock().subjects.insert(subject.id, subject);
+    }
+
+    /// 预置剧集
+    pub fn insert_episode(&self, episode: Episode) {
+        self.lock().episodes.insert(episode.id, episode);
+    }
+
+    /// 预置条目关联的人物
+    pub fn insert_subject_persons(&self, subject_id: u32, persons: Vec<SubjectPerson>) {
+        self.lock().subject_persons.insert(subject_id, persons);
+    }
+
+    /// 预置条目关联的角色
+    pub fn insert_subject_characters(&self, subject_id: u32, characters: Vec<SubjectCharacter>) {
+        self.lock()
+            .subject_characters
+            .insert(subject_id, characters);
+    }
+
+    /// 预置条目关联的条目
+    pub fn insert_subject_relations(&self, subject_id: u32, subjects: Vec<SubjectSubject>) {
+        self.lock().subject_relations.insert(subject_id, subjects);
+    }
+
+    /// 预置任意用户的条目收藏
+    pub fn insert_collection(&self, username: &str, collection: CollectionSubject) {
+        let mut state = self.lock();
+        let sequence = state.next_sequence();
+        state.collections.insert(
+            (username.to_string(), collection.subject_id),
+            (sequence, collection),
+        );
+    }
+
+    /// 预置用户的角色收藏
+    pub fn insert_collection_character(&self, username: &str, character: CollectionCharacter) {
+        self.lock()
+            .character_collections
+            .entry(username.to_string())
+            .or_default()
+            .push(character);
+    }
+
+    /// 预置用户的人物收藏
+    pub fn insert_collection_person(&self, username: &str, person: CollectionPerson) {
+        self.lock()
+            .person_collections
+            .entry(username.to_string())
+            .or_default()
+            .push(person);
+    }
+
+    /// 预置人物修订
+    pub fn insert_person_revision(&self, person_id: u32, revision: RevisionPerson) {
+        self.lock()
+            .person_revisions
+            .insert(revision.id, (person_id, revision));
+    }
+
+    /// 预置角色修订
+    pub fn insert_character_revision(&self, character_id: u32, revision: RevisionCharacter) {
+        self.lock()
+            .character_revisions
+            .insert(revision.id, (character_id, revision));
+    }
+
+    /// 预置条目修订
+    pub fn insert_subject_revision(&self, subject_id: u32, revision: RevisionSubject) {
+        self.lock()
+            .subject_revisions
+            .insert(revision.id, (subject_id, revision));
+    }
+
+    /// 预置剧集修订
+    pub fn insert_episode_revision(&self, episode_id: u32, revision: RevisionEpisode) {
+        self.lock()
+            .episode_revisions
+            .insert(revision.id, (episode_id, revision));
+    }
+}
+
+/// 获取锁，忽略其他线程panic造成的中毒状态
+fn lock(state: &Mutex<FakeState>) -> MutexGuard<'_, FakeState> {
+    state.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+impl SubjectApi for FakeClient {
+    /// 模拟客户端没有放送数据，总是返回空列表
+    async fn get_calendar(&self) -> Result<Vec<DailyCalendarItem>> {
+        Ok(Vec::new())
+    }
+
+    /// 按名称与中文名匹配关键词，并按筛选条件中的类型与标签过滤
+    async fn search_subjects(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        payload: Option<SubjectSearch>,
+    ) -> Result<Paged<Subject>> {
+        let state = self.lock();
+        let keyword = payload
+            .as_ref()
+            .map(|search| search.keyword.to_lowercase())
+            .unwrap_or_default();
+        let filter = payload.and_then(|search| search.filter);
+        let subjects = state
+            .subjects
+            .values()
+            .filter(|subject| {
+                subject.name.to_lowercase().contains(&keyword)
+                    || subject.name_cn.to_lowercase().contains(&keyword)
+            })
+            .filter(|subject| {
+                filter.as_ref().is_none_or(|filter| {
+                    (filter.r#type.is_empty() || filter.r#type.contains(&subject.r#type))
+                        && filter
+                            .tag
+                            .iter()
+                            .all(|tag| subject.tags.iter().any(|own| &own.name == tag))
+                })
+            })
+            .cloned()
+            .collect();
+        Ok(page(subjects, limit, offset))
+    }
+
+    /// 按类型、系列、平台与年月过滤，忽略分类与排序参数
+    async fn get_subjects(
+        &self,
+        r#type: SubjectType,
+        _cat: Option<SubjectCategory>,
+        series: Option<bool>,
+        platform: Option<&str>,
+        _sort: Option<SubjectBrowseSort>,
+        year: Option<u32>,
+        month: Option<u32>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Paged<Subject>> {
+        let state = self.lock();
+        let prefix = match (year, month) {
+            (Some(year), Some(month)) => format!("{year:04}-{month:02}"),
+            (Some(year), None) => format!("{year:04}-"),
+            _ => String::new(),
+        };
+        let subjects = state
+            .subjects
+            .values()
+            .filter(|subject| subject.r#type == r#type)
+            .filter(|subject| series.is_none_or(|series| subject.series == series))
+            .filter(|subject| platform.is_none_or(|platform| subject.platform == platform))
+            .filter(|subject| {
+                subject
+                    .date
+                    .as_deref()
+                    .unwrap_or_default()
+                    .starts_with(&prefix)
+            })
+            .cloned()
+            .collect();
+        Ok(page(subjects, limit, offset))
+    }
+
+    async fn get_subject(&self, subject_id: u32) -> Result<Subject> {
+        self.lock().subject(subject_id).cloned()
+    }
+
+    async fn get_subject_image(&self, _subject_id: u32, _type: ImageType) -> Result<Bytes> {
+        bail!("模拟客户端不提供图片")
+    }
+
+    async fn get_subject_persons(&self, subject_id: u32) -> Result<Vec<SubjectPerson>> {
+        let state = self.lock();
+        state.subject(subject_id)?;
+        Ok(state
+            .subject_persons
+            .get(&subject_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn get_subject_characters(&self, subject_id: u32) -> Result<Vec<SubjectCharacter>> {
+        let state = self.lock();
+        state.subject(subject_id)?;
+        Ok(state
+            .subject_characters
+            .get(&subject_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn get_subject_subjects(&self, subject_id: u32) -> Result<Vec<SubjectSubject>> {
+        let state = self.lock();
+        state.subject(subject_id)?;
+        Ok(state
+            .subject_relations
+            .get(&subject_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn get_episodes(
+        &self,
+        subject_id: u32,
+        r#type: Option<EpisodeType>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Paged<Episode>> {
+        let state = self.lock();
+        let mut episodes: Vec<Episode> = state
+            .episodes
+            .values()
+            .filter(|episode| episode.subject_id == subject_id)
+            .filter(|episode| r#type.as_ref().is_none_or(|kind| &episode.r#type == kind))
+            .cloned()
+            .collect();
+        episodes.sort_by(|a, b| {
+            (a.r#type.clone() as u8)
+                .cmp(&(b.r#type.clone() as u8))
+                .then(a.sort.total_cmp(&b.sort))
+        });
+        Ok(page(episodes, Some(limit.unwrap_or(100)), offset))
+    }
+
+    async fn get_episode(&self, episode_id: u32) -> Result<Episode> {
+        self.lock().episode(episode_id).cloned()
+    }
+}
+
+impl CollectionApi for FakeClient {
+    /// 按修改时间从新到旧排列，非当前用户的私密收藏不可见
+    async fn get_collection_subjects(
+        &self,
+        username: &str,
+        subject_type: Option<SubjectType>,
+        r#type: Option<CollectionType>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Paged<CollectionSubject>> {
+        let state = self.lock();
+        let mut collections: Vec<(u64, CollectionSubject)> = state
+            .collections
+            .iter()
+            .filter(|((owner, subject_id), _)| {
+                owner == username && state.visible_collection(owner, *subject_id).is_some()
+            })
+            .map(|(_, entry)| entry.clone())
+            .filter(|(_, collection)| {
+                subject_type
+                    .as_ref()
+                    .is_none_or(|kind| &collection.subject_type == kind)
+                    && r#type
+                        .as_ref()
+                        .is_none_or(|kind| &collection.r#type == kind)
+            })
+            .collect();
+        collections.sort_by_key(|(sequence, _)| Reverse(*sequence));
+        let collections = collections
+            .into_iter()
+            .map(|(_, collection)| collection)
+            .collect();
+        Ok(page(collections, limit, offset))
+    }
+
+    async fn get_collection_subject(
+        &self,
+        username: &str,
+        subject_id: u32,
+    ) -> Result<CollectionSubject> {
+        match self.lock().visible_collection(username, subject_id) {
+            Some(collection) => Ok(collection.clone()),
+            None => bail!(format!("用户{username}未收藏条目{subject_id}")),
+        }
+    }
+
+    /// 未收藏时以“想看”新建收藏，再应用修改
+    async fn post_collection_subject(
+        &self,
+        subject_id: u32,
+        payload: Option<CollectionSubjectUpdate>,
+    ) -> Result<()> {
+        let mut state = self.lock();
+        let username = state.me()?.username.clone();
+        let subject = state.subject(subject_id)?;
+        let key = (username, subject_id);
+        let mut collection = match state.collections.get(&key) {
+            Some((_, collection)) => collection.clone(),
+            None => CollectionSubject {
+                subject_id,
+                subject_type: subject.r#type.clone(),
+                rate: 0,
+                r#type: CollectionType::Wish,
+                comment: None,
+                tags: Vec::new(),
+                ep_status: 0,
+                vol_status: 0,
+                updated_at: String::new(),
+                private: false,
+                subject: Some(user_subject(subject)),
+            },
+        };
+        apply_update(&mut collection, payload.unwrap_or_default())?;
+        let sequence = state.next_sequence();
+        state.collections.insert(key, (sequence, collection));
+        Ok(())
+    }
+
+    async fn patch_collection_subject(
+        &self,
+        subject_id: u32,
+        payload: Option<CollectionSubjectUpdate>,
+    ) -> Result<()> {
+        let mut state = self.lock();
+        let username = state.me()?.username.clone();
+        let sequence = state.next_sequence();
+        match state.collections.get_mut(&(username, subject_id)) {
+            Some(entry) => {
+                apply_update(&mut entry.1, payload.unwrap_or_default())?;
+                entry.0 = sequence;
+                Ok(())
+            }
+            None => bail!(format!("未收藏条目{subject_id}")),
+        }
+    }
+
+    async fn get_collection_episodes(
+        &self,
+        subject_id: u32,
+        offset: Option<u32>,
+        limit: Option<u32>,
+        episode_type: Option<EpisodeType>,
+    ) -> Result<Paged<CollectionEpisode>> {
+        let episodes = self
+            .get_episodes(subject_id, episode_type, Some(u32::MAX), None)
+            .await?
+            .data
+            .unwrap_or_default();
+        let state = self.lock();
+        let username = state.me()?.username.clone();
+        if !state
+            .collections
+            .contains_key(&(username.clone(), subject_id))
+        {
+            bail!(format!("未收藏条目{subject_id}"));
+        }
+        let episodes = episodes
+            .iter()
+            .map(|episode| state.collection_episode(&username, episode))
+            .collect();
+        Ok(page(episodes, Some(limit.unwrap_or(100)), offset))
+    }
+
+    async fn patch_collection_episodes(
+        &self,
+        subject_id: u32,
+        payload: Option<CollectionEpisodesUpdate>,
+    ) -> Result<()> {
+        let Some(payload) = payload else {
+            bail!("缺少剧集收藏修改内容");
+        };
+        let mut state = self.lock();
+        let username = state.me()?.username.clone();
+        for episode_id in &payload.episode_id {
+            if state.episode(*episode_id)?.subject_id != subject_id {
+                bail!(format!("剧集{episode_id}不属于条目{subject_id}"));
+            }
+        }
+        let updated_at = now_timestamp() as u32;
+        for episode_id in payload.episode_id {
+            state.episode_collections.insert(
+                (username.clone(), episode_id),
+                (payload.r#type.clone(), updated_at),
+            );
+        }
+        Ok(())
+    }
+
+    async fn get_collection_episode(&self, episode_id: u32) -> Result<CollectionEpisode> {
+        let state = self.lock();
+        let username = state.me()?.username.clone();
+        let episode = state.episode(episode_id)?;
+        Ok(state.collection_episode(&username, episode))
+    }
+
+    async fn put_collection_episode(
+        &self,
+        episode_id: u32,
+        payload: Option<CollectionEpisodeUpdate>,
+    ) -> Result<()> {
+        let Some(payload) = payload else {
+            bail!("缺少剧集收藏修改内容");
+        };
+        let mut state = self.lock();
+        let username = state.me()?.username.clone();
+        state.episode(episode_id)?;
+        state.episode_collections.insert(
+            (username, episode_id),
+            (payload.r#type, now_timestamp() as u32),
+        );
+        Ok(())
+    }
+
+    async fn get_collection_characters(
+        &self,
+        username: &str,
+    ) -> Result<Paged<CollectionCharacter>> {
+        let characters = self
+            .lock()
+            .character_collections
+            .get(username)
+            .cloned()
+            .unwrap_or_default();
+        Ok(page(characters, None, None))
+    }
+
+    async fn get_collection_character(
+        &self,
+        username: &str,
+        character_id: u32,
+    ) -> Result<CollectionCharacter> {
+        let state = self.lock();
+        let character = state
+            .character_collections
+            .get(username)
+            .and_then(|characters| characters.iter().find(|c| c.id == character_id));
+        match character {
+            Some(character) => Ok(character.clone()),
+            None => bail!(format!("用户{username}未收藏角色{character_id}")),
+        }
+    }
+
+    async fn get_collection_persons(&self, username: &str) -> Result<Paged<CollectionPerson>> {
+        let persons = self
+            .lock()
+            .person_collections
+            .get(username)
+            .cloned()
+            .unwrap_or_default();
+        Ok(page(persons, None, None))
+    }
+
+    async fn get_collection_person(
+        &self,
+        username: &str,
+        person_id: u32,
+    ) -> Result<CollectionPerson> {
+        let state = self.lock();
+        let person = state
+            .person_collections
+            .get(username)
+            .and_then(|persons| persons.iter().find(|p| p.id == person_id));
+        match person {
+            Some(person) => Ok(person.clone()),
+            None => bail!(format!("用户{username}未收藏人物{person_id}")),
+        }
+    }
+}
+
+impl IndexApi for FakeClient {
+    async fn add_index(&self) -> Result<Index> {
+        let mut state = self.lock();
+        let me = state.me()?;
+        let creator = Creator {
+            username: me.username.clone(),
+            nickname: me.nickname.clone(),
+        };
+        let id = state.indices.keys().next_back().map_or(1, |id| id + 1);
+        let created_at = now();
+        let index = Index {
+            id,
+            title: String::new(),
+            desc: String::new(),
+            total: Some(0),
+            stat: Stat {
+                comments: 0,
+                collects: 0,
+            },
+            created_at: created_at.clone(),
+            updated_at: created_at,
+            creator,
+            ban: false,
+            nsfw: false,
+        };
+        state.indices.insert(id, index.clone());
+        Ok(index)
+    }
+
+    async fn get_index(&self, index_id: u32) -> Result<Index> {
+        match self.lock().indices.get(&index_id) {
+            Some(index) => Ok(index.clone()),
+            None => bail!(format!("目录{index_id}不存在")),
+        }
+    }
+
+    async fn edit_index(&self, index_id: u32, payload: Option<IndexBasicInfo>) -> Result<Index> {
+        let mut state = self.lock();
+        let index = state.own_index(index_id)?;
+        if let Some(payload) = payload {
+            if let Some(title) = payload.title {
+                index.title = title;
+            }
+            if let Some(description) = payload.description {
+                index.desc = description;
+            }
+        }
+        index.updated_at = now();
+        Ok(index.clone())
+    }
+
+    /// 按排序值从小到大排列，排序值相同时按添加顺序
+    async fn get_index_subjects(
+        &self,
+        index_id: u32,
+        r#type: Option<SubjectType>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Paged<IndexSubject>> {
+        let state = self.lock();
+        if !state.indices.contains_key(&index_id) {
+            bail!(format!("目录{index_id}不存在"));
+        }
+        let mut entries: Vec<(u32, IndexSubject)> = state
+            .index_subjects
+            .get(&index_id)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, subject)| r#type.as_ref().is_none_or(|kind| &subject.r#type == kind))
+            .collect();
+        entries.sort_by_key(|(sort, _)| *sort);
+        let subjects = entries.into_iter().map(|(_, subject)| subject).collect();
+        Ok(page(subjects, limit, offset))
+    }
+
+    async fn add_index_subject(
+        &self,
+        index_id: u32,
+        payload: Option<IndexSubjectAddInfo>,
+    ) -> Result<()> {
+        let mut state = self.lock();
+        state.own_index(index_id)?;
+        let Some(payload) = payload else {
+            bail!("缺少要添加的条目");
+        };
+        let Some(subject_id) = payload.subject_id else {
+            bail!("缺少要添加的条目ID");
+        };
+        let subject = state.subject(subject_id)?;
+        let entry = IndexSubject {
+            id: subject.id,
+            r#type: subject.r#type.clone(),
+            name: subject.name.clone(),
+            name_cn: subject.name_cn.clone(),
+            date: subject.date.clone(),
+            images: subject.images.clone(),
+            infobox: subject.infobox.clone(),
+            add_at: Some(now()),
+            comment: payload.comment.unwrap_or_default(),
+        };
+        let entries = state.index_subjects.entry(index_id).or_default();
+        if entries.iter().any(|(_, subject)| subject.id == subject_id) {
+            bail!(format!("条目{subject_id}已在目录{index_id}中"));
+        }
+        entries.push((payload.sort.unwrap_or(0), entry));
+        let total = entries.len() as u32;
+        let index = state.own_index(index_id)?;
+        index.total = Some(total);
+        index.updated_at = now();
+        Ok(())
+    }
+
+    async fn edit_index_subject(
+        &self,
+        index_id: u32,
+        subject_id: u32,
+        payload: Option<IndexSubjectEditInfo>,
+    ) -> Result<()> {
+        let mut state = self.lock();
+        state.own_index(index_id)?;
+        let entry = state.index_subjects.get_mut(&index_id).and_then(|entries| {
+            entries
+                .iter_mut()
+                .find(|(_, subject)| subject.id == subject_id)
+        });
+        let Some((sort, subject)) = entry else {
+            bail!(format!("条目{subject_id}不在目录{index_id}中"));
+        };
+        if let Some(payload) = payload {
+            if let Some(value) = payload.sort {
+                *sort = value;
+            }
+            if let Some(comment) = payload.comment {
+                subject.comment = comment;
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete_index_subject(&self, index_id: u32, subject_id: u32) -> Result<()> {
+        let mut state = self.lock();
+        state.own_index(index_id)?;
+        let entries = state.index_subjects.entry(index_id).or_default();
+        let before = entries.len();
+        entries.retain(|(_, subject)| subject.id != subject_id);
+        if entries.len() == before {
+            bail!(format!("条目{subject_id}不在目录{index_id}中"));
+        }
+        let total = entries.len() as u32;
+        let index = state.own_index(index_id)?;
+        index.total = Some(total);
+        index.updated_at = now();
+        Ok(())
+    }
+
+    async fn collect_index(&self, index_id: u32) -> Result<()> {
+        let mut state = self.lock();
+        let username = state.me()?.username.clone();
+        if !state.indices.contains_key(&index_id) {
+            bail!(format!("目录{index_id}不存在"));
+        }
+        let collectors = state.index_collectors.entry(index_id).or_default();
+        collectors.insert(username);
+        let collects = collectors.len() as i32;
+        if let Some(index) = state.indices.get_mut(&index_id) {
+            index.stat.collects = collects;
+        }
+        Ok(())
+    }
+
+    async fn uncollect_index(&self, index_id: u32) -> Result<()> {
+        let mut state = self.lock();
+        let username = state.me()?.username.clone();
+        let collectors = state.index_collectors.entry(index_id).or_default();
+        if !collectors.remove(&username) {
+            bail!(format!("未收藏目录{index_id}"));
+        }
+        let collects = collectors.len() as i32;
+        if let Some(index) = state.indices.get_mut(&index_id) {
+            index.stat.collects = collects;
+        }
+        Ok(())
+    }
+}
+
+/// 为修订接口生成列表与详情方法，修订列表按修订ID从新到旧排列
+macro_rules! fake_revisions {
+    ($($list:ident, $detail:ident, $field:ident, $revision:ty;)*) => {
+        impl RevisionApi for FakeClient {
+            $(
+                async fn $list(
+                    &self,
+                    id: u32,
+                    limit: Option<u32>,
+                    offset: Option<u32>,
+                ) -> Result<Paged<RevisionCommon>> {
+                    let revisions = self
+                        .lock()
+                        .$field
+                        .values()
+                        .rev()
+                        .filter(|(target, _)| *target == id)
+                        .map(|(_, revision)| revision_common!(revision))
+                        .collect();
+                    Ok(page(revisions, limit, offset))
+                }
+
+                async fn $detail(&self, revision_id: u32) -> Result<$revision> {
+                    match self.lock().$field.get(&revision_id) {
+                        Some((_, revision)) => Ok(revision.clone()),
+                        None => bail!(format!("修订{revision_id}不存在")),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+fake_revisions! {
+    get_revision_persons, get_revision_person, person_revisions, RevisionPerson;
+    get_revision_characters, get_revision_character, character_revisions, RevisionCharacter;
+    get_revision_subjects, get_revision_subject, subject_revisions, RevisionSubject;
+    get_revision_episodes, get_revision_episode, episode_revisions, RevisionEpisode;
+}
+
+impl UserApi for FakeClient {
+    async fn get_user(&self, username: &str) -> Result<UserPublic> {
+        let state = self.lock();
+        if let Some(me) = state.me.as_ref().filter(|me| me.username == username) {
+            return Ok(UserPublic {
+                id: me.id,
+                url: me.url.clone(),
+                username: me.username.clone(),
+                nickname: me.nickname.clone(),
+                user_group: me.user_group.clone(),
+                avatar: me.avatar.clone(),
+                sign: me.sign.clone(),
+            });
+        }
+        match state.users.get(username) {
+            Some(user) => Ok(user.clone()),
+            None => bail!(format!("用户{username}不存在")),
+        }
+    }
+
+    async fn get_user_avatar(&self, _username: &str, _type: AvatarType) -> Result<Bytes> {
+        bail!("模拟客户端不提供图片")
+    }
+
+    async fn get_me(&self) -> Result<User> {
+        self.lock().me().cloned()
+    }
+}
@@ -0,0 +1,282 @@
+use std::sync::Arc;
+
+use crate::{
+    common::{
+        fixture::{self, Fixture},
+        mock::{MockRoute, MockServer},
+    },
+    module::{
+        api::model::{CollectionApi, FakeClient, IndexApi, RevisionApi, SubjectApi, UserApi},
+        collection::model::{
+            CollectionEpisodeType, CollectionEpisodesUpdate, CollectionSubject,
+            CollectionSubjectUpdate, CollectionType,
+        },
+        episode::model::Episode,
+        indice::model::{IndexBasicInfo, IndexSubjectAddInfo, IndexSubjectEditInfo},
+        revision::model::RevisionEpisode,
+        user::model::User,
+    },
+};
+
+/// 收藏人数合计为15的条目
+fn subject(id: u32, name: &str) -> Fixture {
+    fixture::subject(id).named(name, "").with("eps", 2).with(
+        "collection",
+        serde_json::json!({"wish": 1, "collect": 2, "doing": 3, "on_hold": 4, "dropped": 5}),
+    )
+}
+
+fn episode(id: u32, subject_id: u32, sort: f64) -> Episode {
+    fixture::episode(id, sort)
+        .with("subject_id", subject_id)
+        .parse()
+}
+
+fn me() -> User {
+    serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "url": "https://bgm.tv/user/sai",
+        "username": "sai",
+        "nickname": "Sai",
+        "user_group": 10,
+        "avatar": {"large": "l", "medium": "m", "small": "s"},
+        "sign": "",
+        "email": "sai@example.com",
+        "reg_time": "2020-01-01T00:00:00+08:00",
+        "time_offset": 8
+    }))
+    .unwrap()
+}
+
+fn fake() -> FakeClient {
+    let client = FakeClient::new(me());
+    client.insert_subject(subject(1, "first").parse());
+    client.insert_subject(subject(2, "second").parse());
+    client.insert_episode(episode(11, 1, 1.0));
+    client.insert_episode(episode(12, 1, 2.0));
+    client
+}
+
+/// 只依赖接口特征的业务代码
+async fn mark_watched<C: SubjectApi + CollectionApi>(client: &C, subject_id: u32) -> u32 {
+    let episodes = client
+        .get_episodes(subject_id, None, None, None)
+        .await
+        .unwrap()
+        .data
+        .unwrap_or_default();
+    let update = CollectionSubjectUpdate {
+        r#type: Some(CollectionType::Done),
+        ep_status: Some(episodes.len() as u32),
+        ..Default::default()
+    };
+    client
+        .post_collection_subject(subject_id, Some(update))
+        .await
+        .unwrap();
+    episodes.len() as u32
+}
+
+#[tokio::test]
+async fn test_real_client_implements_traits() {
+    let server = MockServer::start(vec![
+        MockRoute::json("GET", "/v0/subjects/1", 200, subject(1, "first").json()),
+        MockRoute::json(
+            "GET",
+            "/v0/episodes",
+            200,
+            serde_json::json!({"total": 0, "limit": 100, "offset": 0, "data": []}),
+        ),
+        MockRoute::json(
+            "POST",
+            "/v0/users/-/collections/1",
+            202,
+            serde_json::Value::Null,
+        ),
+    ]);
+    let client = server.client();
+
+    let subject = SubjectApi::get_subject(&client, 1).await.unwrap();
+    assert_eq!(subject.name, "first");
+    assert_eq!(mark_watched(&client, 1).await, 0);
+    assert!(server.requests().iter().any(|r| r.method == "POST"));
+}
+
+#[tokio::test]
+async fn test_fake_collection_writes_are_visible() {
+    let client = Arc::new(fake());
+
+    // 接口返回的Future可以在多线程运行时中执行
+    let handle = tokio::spawn({
+        let client = client.clone();
+        async move { mark_watched(client.as_ref(), 1).await }
+    });
+    assert_eq!(handle.await.unwrap(), 2);
+
+    let collection = client.get_collection_subject("sai", 1).await.unwrap();
+    assert_eq!(collection.r#type, CollectionType::Done);
+    assert_eq!(collection.ep_status, 2);
+    assert_eq!(collection.subject.unwrap().collection_total, 15);
+
+    let update = CollectionSubjectUpdate {
+        rate: Some(8),
+        private: Some(true),
+        ..Default::default()
+    };
+    client
+        .patch_collection_subject(1, Some(update))
+        .await
+        .unwrap();
+    client.post_collection_subject(2, None).await.unwrap();
+    assert!(client.patch_collection_subject(3, None).await.is_err());
+    let rate = CollectionSubjectUpdate {
+        rate: Some(11),
+        ..Default::default()
+    };
+    assert!(client.post_collection_subject(2, Some(rate)).await.is_err());
+
+    // 最近修改的在前
+    let page = client
+        .get_collection_subjects("sai", None, None, None, None)
+        .await
+        .unwrap();
+    let data = page.data.unwrap();
+    assert_eq!(page.total, 2);
+    assert_eq!(data[0].subject_id, 2);
+    assert_eq!(data[0].r#type, CollectionType::Wish);
+    assert_eq!(data[1].rate, 8);
+    let page = client
+        .get_collection_subjects("sai", None, Some(CollectionType::Done), None, None)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+
+    // 其他用户的私密收藏不可见
+    let mut private: CollectionSubject = data[1].clone();
+    private.private = true;
+    client.insert_collection("other", private);
+    assert!(client.get_collection_subject("other", 1).await.is_err());
+
+    // 剧集收藏
+    let payload = CollectionEpisodesUpdate {
+        episode_id: vec![11],
+        r#type: CollectionEpisodeType::Done,
+    };
+    client
+        .patch_collection_episodes(1, Some(payload))
+        .await
+        .unwrap();
+    let episodes = client
+        .get_collection_episodes(1, None, None, None)
+        .await
+        .unwrap()
+        .data
+        .unwrap();
+    assert_eq!(episodes[0].r#type, CollectionEpisodeType::Done);
+    assert_eq!(episodes[1].r#type, CollectionEpisodeType::Not);
+    let payload = CollectionEpisodesUpdate {
+        episode_id: vec![11],
+        r#type: CollectionEpisodeType::Done,
+    };
+    assert!(
+        client
+            .patch_collection_episodes(2, Some(payload))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_fake_index_round_trip() {
+    let client = fake();
+
+    let index = client.add_index().await.unwrap();
+    assert_eq!(index.creator.username, "sai");
+    let info = IndexBasicInfo {
+        title: Some("年度最佳".to_string()),
+        description: None,
+    };
+    let edited = client.edit_index(index.id, Some(info)).await.unwrap();
+    assert_eq!(edited.title, "年度最佳");
+
+    for (subject_id, sort) in [(1, 2), (2, 1)] {
+        let payload = IndexSubjectAddInfo {
+            subject_id: Some(subject_id),
+            sort: Some(sort),
+            comment: None,
+        };
+        client
+            .add_index_subject(index.id, Some(payload))
+            .await
+            .unwrap();
+    }
+    let duplicate = IndexSubjectAddInfo {
+        subject_id: Some(1),
+        sort: None,
+        comment: None,
+    };
+    assert!(
+        client
+            .add_index_subject(index.id, Some(duplicate))
+            .await
+            .is_err()
+    );
+
+    let edit = IndexSubjectEditInfo {
+        sort: Some(0),
+        comment: Some("必看".to_string()),
+    };
+    client
+        .edit_index_subject(index.id, 1, Some(edit))
+        .await
+        .unwrap();
+    let subjects = client
+        .get_index_subjects(index.id, None, None, None)
+        .await
+        .unwrap()
+        .data
+        .unwrap();
+    assert_eq!(
+        subjects.iter().map(|s| s.id).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(subjects[0].comment, "必看");
+
+    client.delete_index_subject(index.id, 2).await.unwrap();
+    assert!(client.delete_index_subject(index.id, 2).await.is_err());
+    client.collect_index(index.id).await.unwrap();
+    let index = client.get_index(index.id).await.unwrap();
+    assert_eq!(index.total, Some(1));
+    assert_eq!(index.stat.collects, 1);
+    client.uncollect_index(index.id).await.unwrap();
+    assert_eq!(client.get_index(index.id).await.unwrap().stat.collects, 0);
+}
+
+#[tokio::test]
+async fn test_fake_users_and_revisions() {
+    let client = fake();
+    assert_eq!(client.get_me().await.unwrap().nickname, "Sai");
+    assert_eq!(client.get_user("sai").await.unwrap().id, 1);
+    assert!(client.get_user("nobody").await.is_err());
+    assert!(FakeClient::default().get_me().await.is_err());
+
+    for id in [5, 6] {
+        let revision: RevisionEpisode = serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": 1,
+            "creator": {"username": "sai", "nickname": "Sai"},
+            "summary": format!("修订{id}"),
+            "created_at": "2024-01-01T00:00:00Z",
+            "data": null
+        }))
+        .unwrap();
+        client.insert_episode_revision(11, revision);
+    }
+    let page = client.get_revision_episodes(11, None, None).await.unwrap();
+    assert_eq!(page.data.unwrap()[0].id, 6);
+    assert_eq!(
+        client.get_revision_episode(5).await.unwrap().summary,
+        "修订5"
+    );
+    assert!(client.get_revision_subject(5).await.is_err());
+}
@@ -0,0 +1,6 @@
+/// Bangumi Archive数据包导入（需启用`archive`特性）
+pub mod model;
+pub mod service;
+
+#[cfg(test)]
+pub mod test;
@@ -0,0 +1,283 @@
+use std::{fs::File, io::Lines, marker::PhantomData, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::module::{
+    character::model::CharacterType,
+    person::model::{PersonCareer, PersonType},
+    subject::model::{RatingCount, SubjectType},
+};
+
+/// 条目数据文件名
+pub const SUBJECT_FILE: &str = "subject.jsonlines";
+/// 人物数据文件名
+pub const PERSON_FILE: &str = "person.jsonlines";
+/// 角色数据文件名
+pub const CHARACTER_FILE: &str = "character.jsonlines";
+/// 剧集数据文件名
+pub const EPISODE_FILE: &str = "episode.jsonlines";
+/// 条目关联数据文件名
+pub const SUBJECT_RELATION_FILE: &str = "subject-relations.jsonlines";
+/// 条目与人物关联数据文件名
+pub const SUBJECT_PERSON_FILE: &str = "subject-persons.jsonlines";
+/// 条目与角色关联数据文件名
+pub const SUBJECT_CHARACTER_FILE: &str = "subject-characters.jsonlines";
+/// 人物与角色关联数据文件名
+pub const PERSON_CHARACTER_FILE: &str = "person-characters.jsonlines";
+
+/// Bangumi Archive数据包
+///
+/// 可以是官方发布的zip压缩包，也可以是解压后的目录。
+/// 各数据文件按行流式读取，不会整体载入内存
+pub struct Archive {
+    /// 数据来源
+    pub(crate) source: ArchiveSource,
+}
+
+/// 数据包来源
+pub(crate) enum ArchiveSource {
+    /// zip压缩包
+    Zip(ZipArchive<File>),
+    /// 解压后的目录
+    Directory(PathBuf),
+}
+
+/// JSON Lines流式解析器
+///
+/// 逐行反序列化为`T`，跳过空行，解析失败时错误信息中包含行号
+pub struct JsonLines<R, T> {
+    /// 行迭代器
+    pub(crate) lines: Lines<R>,
+    /// 已读取的行数
+    pub(crate) line: usize,
+    pub(crate) marker: PhantomData<T>,
+}
+
+/// 数据包中的标签
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveTag {
+    /// 标签名
+    pub name: String,
+    /// 标注人数
+    pub count: u32,
+}
+
+/// 数据包中的收藏人数统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveFavorite {
+    /// 想看
+    #[serde(default)]
+    pub wish: u32,
+    /// 看过
+    #[serde(default)]
+    pub done: u32,
+    /// 在看
+    #[serde(default)]
+    pub doing: u32,
+    /// 搁置
+    #[serde(default)]
+    pub on_hold: u32,
+    /// 抛弃
+    #[serde(default)]
+    pub dropped: u32,
+}
+
+/// 数据包中的条目记录（`subject.jsonlines`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveSubject {
+    /// 条目ID
+    pub id: u32,
+    /// 条目类型
+    pub r#type: SubjectType,
+    /// 条目原名
+    pub name: String,
+    /// 条目中文名
+    #[serde(default)]
+    pub name_cn: String,
+    /// Wiki格式的信息框原文
+    #[serde(default)]
+    pub infobox: String,
+    /// 平台编号
+    #[serde(default)]
+    pub platform: u32,
+    /// 简介
+    #[serde(default)]
+    pub summary: String,
+    /// 是否为NSFW内容
+    #[serde(default)]
+    pub nsfw: bool,
+    /// 用户标签
+    #[serde(default)]
+    pub tags: Vec<ArchiveTag>,
+    /// 公共标签
+    #[serde(default)]
+    pub meta_tags: Vec<String>,
+    /// 评分
+    #[serde(default)]
+    pub score: f64,
+    /// 各分数的评分人数
+    pub score_details: Option<RatingCount>,
+    /// 排名（0表示无排名）
+    #[serde(default)]
+    pub rank: u32,
+    /// 发售或放送日期
+    #[serde(default)]
+    pub date: Option<String>,
+    /// 收藏人数统计
+    #[serde(default)]
+    pub favorite: ArchiveFavorite,
+    /// 是否为系列（仅书籍）
+    #[serde(default)]
+    pub series: bool,
+}
+
+/// 数据包中的人物记录（`person.jsonlines`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivePerson {
+    /// 人物ID
+    pub id: u32,
+    /// 人物姓名
+    pub name: String,
+    /// 人物类型
+    pub r#type: PersonType,
+    /// 职业列表
+    #[serde(default)]
+    pub career: Vec<PersonCareer>,
+    /// Wiki格式的信息框原文
+    #[serde(default)]
+    pub infobox: String,
+    /// 简介
+    #[serde(default)]
+    pub summary: String,
+    /// 评论数
+    #[serde(default)]
+    pub comments: i32,
+    /// 收藏数
+    #[serde(default)]
+    pub collects: i32,
+}
+
+/// 数据包中的角色记录（`character.jsonlines`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveCharacter {
+    /// 角色ID
+    pub id: u32,
+    /// 角色类型
+    pub role: CharacterType,
+    /// 角色名称
+    pub name: String,
+    /// Wiki格式的信息框原文
+    #[serde(default)]
+    pub infobox: String,
+    /// 简介
+    #[serde(default)]
+    pub summary: String,
+    /// 评论数
+    #[serde(default)]
+    pub comments: i32,
+    /// 收藏数
+    #[serde(default)]
+    pub collects: i32,
+}
+
+/// 数据包中的剧集记录（`episode.jsonlines`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEpisode {
+    /// 剧集ID
+    pub id: u32,
+    /// 剧集原名
+    #[serde(default)]
+    pub name: String,
+    /// 剧集中文名
+    #[serde(default)]
+    pub name_cn: String,
+    /// 简介
+    #[serde(default)]
+    pub description: String,
+    /// 播出日期
+    #[serde(default)]
+    pub airdate: String,
+    /// 光盘编号
+    #[serde(default)]
+    pub disc: u32,
+    /// 时长描述（通常为`HH:MM:SS`）
+    #[serde(default)]
+    pub duration: String,
+    /// 所属条目ID
+    pub subject_id: u32,
+    /// 排序
+    #[serde(default)]
+    pub sort: f64,
+    /// 剧集类型编号（0正片、1SP、2OP、3ED、4预告/宣传/广告、5MAD、6其他）
+    #[serde(default)]
+    pub r#type: u8,
+}
+
+/// 数据包中的条目关联记录（`subject-relations.jsonlines`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveSubjectRelation {
+    /// 条目ID
+    pub subject_id: u32,
+    /// 关联类型编号
+    pub relation_type: u32,
+    /// 关联条目ID
+    pub related_subject_id: u32,
+    /// 排序
+    #[serde(default)]
+    pub order: u32,
+}
+
+/// 数据包中的条目与人物关联记录（`subject-persons.jsonlines`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveSubjectPerson {
+    /// 人物ID
+    pub person_id: u32,
+    /// 条目ID
+    pub subject_id: u32,
+    /// 职位编号
+    pub position: u32,
+}
+
+/// 数据包中的条目与角色关联记录（`subject-characters.jsonlines`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveSubjectCharacter {
+    /// 角色ID
+    pub character_id: u32,
+    /// 条目ID
+    pub subject_id: u32,
+    /// 角色类型编号（1主角、2配角、3客串）
+    pub r#type: u32,
+    /// 排序
+    #[serde(default)]
+    pub order: u32,
+}
+
+/// 数据包中的人物与角色关联记录（`person-characters.jsonlines`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivePersonCharacter {
+    /// 人物ID
+    pub person_id: u32,
+    /// 条目ID
+    pub subject_id: u32,
+    /// 角色ID
+    pub character_id: u32,
+    /// 备注
+    #[serde(default)]
+    pub summary: String,
+}
+
+/// 导入本地镜像的结果统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveLoadSummary {
+    /// 导入的条目数
+    pub subjects: usize,
+    /// 导入的人物数
+    pub persons: usize,
+    /// 导入的角色数
+    pub characters: usize,
+    /// 导入的剧集数
+    pub episodes: usize,
+    /// 写入关联列表的条目数
+    pub subject_relations: usize,
+}
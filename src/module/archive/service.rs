@@ -0,0 +1,651 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    marker::PhantomData,
+    path::Path,
+};
+
+use anyhow::{Result, bail};
+use serde::de::DeserializeOwned;
+use zip::ZipArchive;
+
+use crate::module::{
+    character::model::Character,
+    collection::model::CollectionStats,
+    episode::model::{Episode, EpisodeType},
+    model::{BloodType, Image, InfoBox, InfoBoxItem, SimpleImage, Stat},
+    person::model::PersonDetail,
+    subject::model::{Rating, RatingCount, Subject, SubjectRelation, SubjectTag, SubjectType},
+};
+
+use super::model::{
+    Archive, ArchiveCharacter, ArchiveEpisode, ArchivePerson, ArchivePersonCharacter,
+    ArchiveSource, ArchiveSubject, ArchiveSubjectCharacter, ArchiveSubjectPerson,
+    ArchiveSubjectRelation, CHARACTER_FILE, EPISODE_FILE, JsonLines, PERSON_CHARACTER_FILE,
+    PERSON_FILE, SUBJECT_CHARACTER_FILE, SUBJECT_FILE, SUBJECT_PERSON_FILE, SUBJECT_RELATION_FILE,
+};
+
+/// 解析Wiki格式的信息框
+///
+/// 数据包中的信息框为`{{Infobox ...}}`格式的原文，单值字段形如`|键= 值`，
+/// 多值字段形如`|键={`后跟若干行`[值]`或`[名称|值]`并以`}`结束。
+/// 解析结果与API返回的信息框结构一致：单值为字符串，多值为`{"v": ...}`或`{"k": ..., "v": ...}`的数组
+pub fn parse_infobox(wiki: &str) -> InfoBox {
+    let mut items = Vec::new();
+    let mut list: Option<(String, Vec<serde_json::Value>)> = None;
+
+    for line in wiki.lines().map(str::trim) {
+        if let Some((key, values)) = list.as_mut() {
+            if line == "}" {
+                items.push(InfoBoxItem {
+                    key: std::mem::take(key),
+                    value: serde_json::Value::Array(std::mem::take(values)),
+                });
+                list = None;
+            } else if let Some(inner) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let value = match inner.split_once('|') {
+                    Some((k, v)) => serde_json::json!({"k": k.trim(), "v": v.trim()}),
+                    None => serde_json::json!({"v": inner.trim()}),
+                };
+                values.push(value);
+            }
+            continue;
+        }
+
+        let Some((key, value)) = line.strip_prefix('|').and_then(|l| l.split_once('=')) else {
+            continue;
+        };
+        let (key, value) = (key.trim().to_string(), value.trim());
+        if value == "{" {
+            list = Some((key, Vec::new()));
+        } else {
+            items.push(InfoBoxItem {
+                key,
+                value: serde_json::Value::String(value.to_string()),
+            });
+        }
+    }
+    items
+}
+
+/// 读取信息框中的单值字段，空字符串视为不存在
+fn infobox_text<'a>(infobox: &'a InfoBox, key: &str) -> Option<&'a str> {
+    infobox
+        .iter()
+        .find(|item| item.key == key)
+        .and_then(|item| item.value.as_str())
+        .filter(|value| !value.is_empty())
+}
+
+/// 取出字符串中所有的数字段
+fn numbers(value: &str) -> Vec<u32> {
+    value
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+/// 读取信息框中以数字开头的字段（如`话数`、`册数`）
+fn infobox_count(infobox: &InfoBox, key: &str) -> u32 {
+    infobox_text(infobox, key)
+        .and_then(|value| numbers(value).first().copied())
+        .unwrap_or_default()
+}
+
+/// 解析生日，支持`1989-02-25`、`1989年2月25日`与`2月25日`等格式
+///
+/// # 返回
+/// 返回(年, 月, 日)，无法识别的部分为None
+pub fn parse_birthday(value: &str) -> (Option<u32>, Option<u32>, Option<u32>) {
+    let numbers = numbers(value);
+    let (year, rest) = match numbers.split_first() {
+        Some((year, rest)) if *year >= 1000 => (Some(*year), rest),
+        _ => (None, numbers.as_slice()),
+    };
+    let mon = rest.first().copied().filter(|mon| (1..=12).contains(mon));
+    let day = rest.get(1).copied().filter(|day| (1..=31).contains(day));
+    (year, mon, day)
+}
+
+/// 解析血型
+fn parse_blood_type(value: &str) -> Option<BloodType> {
+    match value
+        .trim()
+        .trim_end_matches('型')
+        .to_ascii_uppercase()
+        .as_str()
+    {
+        "A" => Some(BloodType::A),
+        "B" => Some(BloodType::B),
+        "AB" => Some(BloodType::AB),
+        "O" => Some(BloodType::O),
+        _ => None,
+    }
+}
+
+/// 将信息框中的性别转换为API使用的取值
+fn parse_gender(value: &str) -> String {
+    match value.trim() {
+        "男" => "male".to_string(),
+        "女" => "female".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// 解析`HH:MM:SS`或`MM:SS`格式的时长
+///
+/// # 返回
+/// 返回秒数，无法解析时返回0
+pub fn parse_duration(value: &str) -> u32 {
+    let parts: Option<Vec<u32>> = value
+        .trim()
+        .split(':')
+        .map(|part| part.parse().ok())
+        .collect();
+    match parts.as_deref() {
+        Some([hours, minutes, seconds]) => hours * 3600 + minutes * 60 + seconds,
+        Some([minutes, seconds]) => minutes * 60 + seconds,
+        _ => 0,
+    }
+}
+
+/// 将平台编号转换为API使用的平台名称
+///
+/// 未知编号返回空字符串
+pub fn platform_name(subject_type: &SubjectType, platform: u32) -> &'static str {
+    match (subject_type, platform) {
+        (SubjectType::Anime, 1) => "TV",
+        (SubjectType::Anime, 2) => "OVA",
+        (SubjectType::Anime, 3) => "剧场版",
+        (SubjectType::Anime, 5) => "WEB",
+        (SubjectType::Book, 1001) => "漫画",
+        (SubjectType::Book, 1002) => "小说",
+        (SubjectType::Book, 1003) => "画集",
+        (SubjectType::Game, 4001) => "游戏",
+        (SubjectType::Game, 4002) => "软件",
+        (SubjectType::Game, 4003) => "扩展包",
+        (SubjectType::Game, 4005) => "桌游",
+        (SubjectType::Real, 1) => "日剧",
+        (SubjectType::Real, 2) => "欧美剧",
+        (SubjectType::Real, 3) => "华语剧",
+        (_, 0) => "其他",
+        _ => "",
+    }
+}
+
+impl ArchiveSubjectRelation {
+    /// 关联类型编号对应的条目关系
+    ///
+    /// 动画使用1起的编号，书籍使用1000起的编号，音乐使用3000起的编号，
+    /// 游戏使用4000起的编号；未收录的编号保留原始编号，归为`Other`
+    pub fn relation(&self) -> SubjectRelation {
+        match self.relation_type {
+            1 => SubjectRelation::Adaptation,
+            2 | 1005 | 4002 => SubjectRelation::Prequel,
+            3 | 1006 | 4003 => SubjectRelation::Sequel,
+            4 => SubjectRelation::Summary,
+            5 => SubjectRelation::FullStory,
+            6 | 1007 | 4006 => SubjectRelation::SideStory,
+            7 | 1011 | 4007 => SubjectRelation::Character,
+            8 | 1012 | 4008 => SubjectRelation::SameSetting,
+            9 | 1013 | 4009 => SubjectRelation::AlternativeSetting,
+            10 | 1015 | 4010 => SubjectRelation::AlternativeVersion,
+            11 => SubjectRelation::SpinOff,
+            12 | 1008 | 4012 => SubjectRelation::ParentStory,
+            14 | 1014 | 4014 => SubjectRelation::Collaboration,
+            1002 => SubjectRelation::Series,
+            1003 => SubjectRelation::Offprint,
+            1004 => SubjectRelation::Artbook,
+            1010 => SubjectRelation::AlternativeEdition,
+            3001 => SubjectRelation::Soundtrack,
+            3002 => SubjectRelation::CharacterSong,
+            3003 => SubjectRelation::OpeningSong,
+            3004 => SubjectRelation::EndingSong,
+            3005 => SubjectRelation::InsertSong,
+            3006 => SubjectRelation::ImageSong,
+            3007 => SubjectRelation::DramaCd,
+            4015 => SubjectRelation::Expansion,
+            code => SubjectRelation::Other(code.to_string()),
+        }
+    }
+}
+
+/// 数据包中没有图片信息，统一使用空地址
+fn empty_image() -> Image {
+    Image {
+        large: String::new(),
+        common: String::new(),
+        medium: String::new(),
+        small: String::new(),
+        grid: String::new(),
+    }
+}
+
+/// 数据包中没有图片信息，统一使用空地址
+fn empty_simple_image() -> SimpleImage {
+    SimpleImage {
+        large: String::new(),
+        medium: String::new(),
+        small: String::new(),
+        grid: String::new(),
+    }
+}
+
+impl From<ArchiveSubject> for Subject {
+    fn from(subject: ArchiveSubject) -> Self {
+        let infobox = parse_infobox(&subject.infobox);
+        let count = subject
+            .score_details
+            .unwrap_or_else(|| RatingCount::from([0; 10]));
+        Subject {
+            id: subject.id,
+            platform: platform_name(&subject.r#type, subject.platform).to_string(),
+            r#type: subject.r#type,
+            name: subject.name,
+            name_cn: subject.name_cn,
+            summary: subject.summary,
+            series: subject.series,
+            nsfw: subject.nsfw,
+            locked: false,
+            date: subject.date.filter(|date| !date.is_empty()),
+            images: empty_image(),
+            volumes: infobox_count(&infobox, "册数"),
+            eps: infobox_count(&infobox, "话数"),
+            infobox,
+            rating: Rating {
+                rank: subject.rank,
+                total: count.total(),
+                count,
+                score: subject.score,
+            },
+            collection: CollectionStats {
+                wish: Some(subject.favorite.wish),
+                collect: Some(subject.favorite.done),
+                doing: Some(subject.favorite.doing),
+                on_hold: Some(subject.favorite.on_hold),
+                dropped: Some(subject.favorite.dropped),
+            },
+            meta_tags: subject.meta_tags,
+            tags: subject
+                .tags
+                .into_iter()
+                .map(|tag| SubjectTag {
+                    name: tag.name,
+                    count: tag.count,
+                    total_cont: 0,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<ArchivePerson> for PersonDetail {
+    fn from(person: ArchivePerson) -> Self {
+        let infobox = parse_infobox(&person.infobox);
+        let (birth_year, birth_mon, birth_day) = infobox_text(&infobox, "生日")
+            .map(parse_birthday)
+            .unwrap_or_default();
+        PersonDetail {
+            id: person.id,
+            name: person.name,
+            r#type: person.r#type,
+            career: person.career,
+            images: empty_simple_image(),
+            summary: person.summary,
+            locked: false,
+            last_modified: String::new(),
+            gender: infobox_text(&infobox, "性别").map(parse_gender),
+            blood_type: infobox_text(&infobox, "血型").and_then(parse_blood_type),
+            birth_year,
+            birth_mon,
+            birth_day,
+            infobox: infobox
+                .into_iter()
+                .map(|item| serde_json::json!({"key": item.key, "value": item.value}))
+                .collect(),
+            stat: Box::new(Stat {
+                comments: person.comments,
+                collects: person.collects,
+            }),
+        }
+    }
+}
+
+impl From<ArchiveCharacter> for Character {
+    fn from(character: ArchiveCharacter) -> Self {
+        let infobox = parse_infobox(&character.infobox);
+        let (birth_year, birth_mon, birth_day) = infobox_text(&infobox, "生日")
+            .map(parse_birthday)
+            .unwrap_or_default();
+        Character {
+            id: character.id,
+            name: character.name,
+            r#type: character.role,
+            images: empty_simple_image(),
+            summary: character.summary,
+            locked: false,
+            gender: infobox_text(&infobox, "性别")
+                .map(parse_gender)
+                .unwrap_or_default(),
+            blood_type: infobox_text(&infobox, "血型").and_then(parse_blood_type),
+            birth_year,
+            birth_mon,
+            birth_day,
+            infobox,
+            stat: Stat {
+                comments: character.comments,
+                collects: character.collects,
+            },
+            nsfw: false,
+        }
+    }
+}
+
+impl From<ArchiveEpisode> for Episode {
+    /// 数据包中的预告/宣传/广告、MAD及其他类型在API模型中没有对应的值，归为SP
+    fn from(episode: ArchiveEpisode) -> Self {
+        let r#type = match episode.r#type {
+            0 => EpisodeType::Normal,
+            2 => EpisodeType::OP,
+            3 => EpisodeType::ED,
+            _ => EpisodeType::SP,
+        };
+        Episode {
+            id: episode.id,
+            r#type,
+            name: episode.name,
+            name_cn: episode.name_cn,
+            sort: episode.sort,
+            ep: episode.sort,
+            airdate: episode.airdate,
+            comment: 0,
+            duration_seconds: parse_duration(&episode.duration),
+            duration: episode.duration,
+            desc: episode.description,
+            disc: episode.disc,
+            subject_id: episode.subject_id,
+        }
+    }
+}
+
+impl<R: BufRead, T: DeserializeOwned> JsonLines<R, T> {
+    /// 从按行读取的数据源创建解析器
+    pub fn new(reader: R) -> Self {
+        JsonLines {
+            lines: reader.lines(),
+            line: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: BufRead, T: DeserializeOwned> Iterator for JsonLines<R, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(error) => return Some(Err(error.into())),
+            };
+            self.line += 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            return Some(match serde_json::from_str(line) {
+                Ok(record) => Ok(record),
+                Err(error) => Err(anyhow::anyhow!(format!(
+                    "第{}行解析失败: {error}",
+                    self.line
+                ))),
+            });
+        }
+    }
+}
+
+/// 按行读取的数据文件
+pub type ArchiveReader<'a> = Box<dyn BufRead + 'a>;
+
+impl Archive {
+    /// 打开数据包，路径为目录时按解压后的目录读取，否则按zip压缩包读取
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = if path.is_dir() {
+            ArchiveSource::Directory(path.to_path_buf())
+        } else {
+            ArchiveSource::Zip(ZipArchive::new(File::open(path)?)?)
+        };
+        Ok(Archive { source })
+    }
+
+    /// 打开数据包中的文件
+    ///
+    /// zip压缩包中的文件可以位于任意子目录下
+    pub fn reader(&mut self, name: &str) -> Result<ArchiveReader<'_>> {
+        match &mut self.source {
+            ArchiveSource::Directory(directory) => {
+                let path = directory.join(name);
+                if !path.is_file() {
+                    bail!(format!("数据包中缺少文件: {name}"));
+                }
+                Ok(Box::new(BufReader::new(File::open(path)?)))
+            }
+            ArchiveSource::Zip(zip) => {
+                let Some(entry) = zip
+                    .file_names()
+                    .find(|entry| *entry == name || entry.ends_with(&format!("/{name}")))
+                    .map(str::to_string)
+                else {
+                    bail!(format!("数据包中缺少文件: {name}"));
+                };
+                Ok(Box::new(BufReader::new(zip.by_name(&entry)?)))
+            }
+        }
+    }
+
+    /// 判断数据包中是否包含指定文件
+    pub fn contains(&mut self, name: &str) -> bool {
+        self.reader(name).is_ok()
+    }
+
+    /// 按原始结构逐行读取数据包中的文件
+    pub fn records<T: DeserializeOwned>(
+        &mut self,
+        name: &str,
+    ) -> Result<JsonLines<ArchiveReader<'_>, T>> {
+        Ok(JsonLines::new(self.reader(name)?))
+    }
+
+    /// 逐条读取条目
+    pub fn subjects(&mut self) -> Result<impl Iterator<Item = Result<Subject>> + '_> {
+        Ok(self
+            .records::<ArchiveSubject>(SUBJECT_FILE)?
+            .map(|record| record.map(Subject::from)))
+    }
+
+    /// 逐条读取人物
+    pub fn persons(&mut self) -> Result<impl Iterator<Item = Result<PersonDetail>> + '_> {
+        Ok(self
+            .records::<ArchivePerson>(PERSON_FILE)?
+            .map(|record| record.map(PersonDetail::from)))
+    }
+
+    /// 逐条读取角色
+    pub fn characters(&mut self) -> Result<impl Iterator<Item = Result<Character>> + '_> {
+        Ok(self
+            .records::<ArchiveCharacter>(CHARACTER_FILE)?
+            .map(|record| record.map(Character::from)))
+    }
+
+    /// 逐条读取剧集
+    pub fn episodes(&mut self) -> Result<impl Iterator<Item = Result<Episode>> + '_> {
+        Ok(self
+            .records::<ArchiveEpisode>(EPISODE_FILE)?
+            .map(|record| record.map(Episode::from)))
+    }
+
+    /// 逐条读取条目关联
+    pub fn subject_relations(
+        &mut self,
+    ) -> Result<JsonLines<ArchiveReader<'_>, ArchiveSubjectRelation>> {
+        self.records(SUBJECT_RELATION_FILE)
+    }
+
+    /// 逐条读取条目与人物关联
+    pub fn subject_persons(
+        &mut self,
+    ) -> Result<JsonLines<ArchiveReader<'_>, ArchiveSubjectPerson>> {
+        self.records(SUBJECT_PERSON_FILE)
+    }
+
+    /// 逐条读取条目与角色关联
+    pub fn subject_characters(
+        &mut self,
+    ) -> Result<JsonLines<ArchiveReader<'_>, ArchiveSubjectCharacter>> {
+        self.records(SUBJECT_CHARACTER_FILE)
+    }
+
+    /// 逐条读取人物与角色关联
+    pub fn person_characters(
+        &mut self,
+    ) -> Result<JsonLines<ArchiveReader<'_>, ArchivePersonCharacter>> {
+        self.records(PERSON_CHARACTER_FILE)
+    }
+}
+
+#[cfg(feature = "store")]
+mod load {
+    use anyhow::{Result, bail};
+    use rusqlite::{OptionalExtension, params};
+
+    use crate::module::{
+        archive::model::{Archive, ArchiveLoadSummary, ArchiveSubjectRelation},
+        store::model::Store,
+        subject::model::{SubjectSubject, SubjectType},
+    };
+
+    use super::empty_image;
+
+    /// 批量写入时每个事务包含的记录数
+    const BATCH_SIZE: usize = 1000;
+
+    /// 分批写入迭代器中的记录
+    fn load_batches<T>(
+        records: impl Iterator<Item = Result<T>>,
+        mut write: impl FnMut(&[T]) -> Result<()>,
+    ) -> Result<usize> {
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        let mut total = 0;
+        for record in records {
+            batch.push(record?);
+            if batch.len() == BATCH_SIZE {
+                write(&batch)?;
+                total += batch.len();
+                batch.clear();
+            }
+        }
+        if !batch.is_empty() {
+            write(&batch)?;
+            total += batch.len();
+        }
+        Ok(total)
+    }
+
+    impl Store {
+        /// 将数据包导入本地镜像
+        ///
+        /// 依次导入条目、人物、角色与剧集，数据包中缺少的文件会被跳过。
+        /// 包含条目关联文件时，以已导入的条目补全关联条目信息并写入关联列表；
+        /// 关联的条目不在镜像中时跳过该关联。
+        /// 条目的剧集不会标记为完整列表，`CachedClient`仍会在首次读取时请求API
+        ///
+        /// # 返回
+        /// 返回各类数据的导入数量
+        pub fn load_archive(&self, archive: &mut Archive) -> Result<ArchiveLoadSummary> {
+            let mut summary = ArchiveLoadSummary::default();
+            if archive.contains(super::SUBJECT_FILE) {
+                summary.subjects =
+                    load_batches(archive.subjects()?, |batch| self.upsert_subjects(batch))?;
+            }
+            if archive.contains(super::PERSON_FILE) {
+                summary.persons =
+                    load_batches(archive.persons()?, |batch| self.upsert_persons(batch))?;
+            }
+            if archive.contains(super::CHARACTER_FILE) {
+                summary.characters =
+                    load_batches(archive.characters()?, |batch| self.upsert_characters(batch))?;
+            }
+            if archive.contains(super::EPISODE_FILE) {
+                summary.episodes =
+                    load_batches(archive.episodes()?, |batch| self.upsert_episodes(batch))?;
+            }
+
+            if archive.contains(super::SUBJECT_RELATION_FILE) {
+                // 数据包按条目ID排序，逐条读取并在条目ID变化时写入上一个条目的关联
+                let mut group: Vec<ArchiveSubjectRelation> = Vec::new();
+                for relation in archive.subject_relations()? {
+                    let relation = relation?;
+                    if let Some(last) = group.last()
+                        && last.subject_id != relation.subject_id
+                    {
+                        if relation.subject_id < last.subject_id {
+                            bail!(format!(
+                                "条目关联未按条目ID排序: {} 出现在 {} 之后",
+                                relation.subject_id, last.subject_id
+                            ))
+                        }
+                        self.load_subject_relations(&mut group)?;
+                        summary.subject_relations += 1;
+                        group.clear();
+                    }
+                    group.push(relation);
+                }
+                if !group.is_empty() {
+                    self.load_subject_relations(&mut group)?;
+                    summary.subject_relations += 1;
+                }
+            }
+            Ok(summary)
+        }
+
+        /// 写入同一条目的全部关联
+        fn load_subject_relations(&self, group: &mut [ArchiveSubjectRelation]) -> Result<()> {
+            group.sort_by_key(|relation| relation.order);
+            let mut related = Vec::with_capacity(group.len());
+            for relation in group.iter() {
+                if let Some(subject) = self.related_subject(relation)? {
+                    related.push(subject);
+                }
+            }
+            self.upsert_subject_subjects(group[0].subject_id, &related)
+        }
+
+        /// 以镜像中的条目补全关联条目信息
+        fn related_subject(
+            &self,
+            relation: &ArchiveSubjectRelation,
+        ) -> Result<Option<SubjectSubject>> {
+            let row: Option<(u8, String, String)> = self.with_connection(|connection| {
+                connection
+                    .prepare_cached("SELECT type, name, name_cn FROM subjects WHERE id = ?1")?
+                    .query_row(params![relation.related_subject_id], |row| {
+                        Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+                    })
+                    .optional()
+            })?;
+            let Some((r#type, name, name_cn)) = row else {
+                return Ok(None);
+            };
+            let r#type: SubjectType = serde_json::from_value(serde_json::json!(r#type))?;
+            Ok(Some(SubjectSubject {
+                id: relation.related_subject_id,
+                r#type,
+                name,
+                name_cn,
+                images: empty_image(),
+                relation: relation.relation().label_cn().to_string(),
+            }))
+        }
+    }
+}
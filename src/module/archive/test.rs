@@ -0,0 +1,249 @@
+use std::{
+    fs,
+    io::{Cursor, Write},
+    path::PathBuf,
+};
+
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+use crate::module::{
+    archive::{
+        model::{
+            Archive, ArchiveSubject, ArchiveSubjectRelation, CHARACTER_FILE, EPISODE_FILE,
+            JsonLines, PERSON_FILE, SUBJECT_FILE, SUBJECT_RELATION_FILE,
+        },
+        service::{parse_birthday, parse_duration, parse_infobox},
+    },
+    episode::model::EpisodeType,
+    model::BloodType,
+    subject::model::SubjectRelation,
+};
+
+const SUBJECTS: &str = r#"{"id":1,"type":2,"name":"Cowboy Bebop","name_cn":"星际牛仔","infobox":"{{Infobox animanga/TVAnime\r\n|中文名= 星际牛仔\r\n|别名={\r\n[Space Warrior]\r\n[简体中文名|星际牛仔]\r\n}\r\n|话数= 26\r\n}}","platform":1,"summary":"","nsfw":false,"tags":[{"name":"科幻","count":100}],"score":9.1,"score_details":{"1":1,"2":0,"3":0,"4":0,"5":0,"6":0,"7":0,"8":2,"9":3,"10":4},"rank":3,"date":"1998-04-03","favorite":{"wish":1,"done":2,"doing":3,"on_hold":4,"dropped":5},"series":false}
+
+{"id":2,"type":2,"name":"Cowboy Bebop: Knockin' on Heaven's Door","name_cn":"","infobox":"","platform":3,"summary":"","nsfw":false,"tags":[],"score":0,"rank":0,"date":"","favorite":{},"series":false}
+"#;
+
+const PERSONS: &str = r#"{"id":7,"name":"山寺宏一","type":1,"career":["seiyu"],"infobox":"{{Infobox Crt\r\n|性别= 男\r\n|生日= 1961年6月17日\r\n|血型= A\r\n}}","summary":"","comments":1,"collects":2}
+"#;
+
+const CHARACTERS: &str = r#"{"id":3,"role":1,"name":"スパイク","infobox":"{{Infobox Crt\r\n|性别= 男\r\n|生日= 6月26日\r\n}}","summary":"","comments":0,"collects":0}
+"#;
+
+const EPISODES: &str = r#"{"id":11,"name":"Asteroid Blues","name_cn":"","description":"","airdate":"1998-10-23","disc":0,"duration":"00:24:30","subject_id":1,"sort":1,"type":0}
+{"id":12,"name":"","name_cn":"","description":"","airdate":"","disc":0,"duration":"","subject_id":1,"sort":1,"type":5}
+"#;
+
+const RELATIONS: &str = r#"{"subject_id":1,"relation_type":6,"related_subject_id":2,"order":0}
+{"subject_id":1,"relation_type":2,"related_subject_id":999,"order":1}
+{"subject_id":2,"relation_type":12,"related_subject_id":1,"order":0}
+"#;
+
+const FILES: [(&str, &str); 5] = [
+    (SUBJECT_FILE, SUBJECTS),
+    (PERSON_FILE, PERSONS),
+    (CHARACTER_FILE, CHARACTERS),
+    (EPISODE_FILE, EPISODES),
+    (SUBJECT_RELATION_FILE, RELATIONS),
+];
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("bangumi-archive-{}-{name}", std::process::id()))
+}
+
+fn write_zip(path: &PathBuf) {
+    let mut zip = ZipWriter::new(fs::File::create(path).unwrap());
+    for (name, content) in FILES {
+        zip.start_file(format!("dump/{name}"), SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+#[test]
+fn test_parse_infobox() {
+    let infobox = parse_infobox(
+        "{{Infobox animanga/TVAnime\r\n|中文名= 星际牛仔\r\n|别名={\r\n[Space Warrior]\r\n[简体中文名|星际牛仔]\r\n}\r\n|话数= 26\r\n}}",
+    );
+    assert_eq!(infobox.len(), 3);
+    assert_eq!(infobox[0].key, "中文名");
+    assert_eq!(infobox[0].value, serde_json::json!("星际牛仔"));
+    assert_eq!(
+        infobox[1].value,
+        serde_json::json!([{"v": "Space Warrior"}, {"k": "简体中文名", "v": "星际牛仔"}])
+    );
+    assert_eq!(infobox[2].value, serde_json::json!("26"));
+    assert!(parse_infobox("").is_empty());
+
+    assert_eq!(
+        parse_birthday("1961年6月17日"),
+        (Some(1961), Some(6), Some(17))
+    );
+    assert_eq!(
+        parse_birthday("1989-02-25"),
+        (Some(1989), Some(2), Some(25))
+    );
+    assert_eq!(parse_birthday("6月26日"), (None, Some(6), Some(26)));
+    assert_eq!(parse_duration("00:24:30"), 1470);
+    assert_eq!(parse_duration("24:00"), 1440);
+    assert_eq!(parse_duration("24m"), 0);
+}
+
+#[test]
+fn test_json_lines() {
+    let records: Vec<ArchiveSubject> = JsonLines::new(Cursor::new(SUBJECTS))
+        .collect::<anyhow::Result<_>>()
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].platform, 1);
+
+    let mut broken = JsonLines::<_, ArchiveSubject>::new(Cursor::new("\n{\"id\": 1}\n"));
+    let error = broken.next().unwrap().unwrap_err();
+    assert!(error.to_string().contains("第2行"));
+    assert!(broken.next().is_none());
+}
+
+#[test]
+fn test_archive_directory() {
+    let directory = temp_path("directory");
+    fs::create_dir_all(&directory).unwrap();
+    for (name, content) in FILES {
+        fs::write(directory.join(name), content).unwrap();
+    }
+    let mut archive = Archive::open(&directory).unwrap();
+
+    let subjects: Vec<_> = archive
+        .subjects()
+        .unwrap()
+        .collect::<anyhow::Result<_>>()
+        .unwrap();
+    assert_eq!(subjects.len(), 2);
+    let bebop = &subjects[0];
+    assert_eq!(bebop.platform, "TV");
+    assert_eq!(bebop.eps, 26);
+    assert_eq!(bebop.rating.total, 10);
+    assert_eq!(bebop.rating.count.param_10, 4);
+    assert_eq!(bebop.collection.collect, Some(2));
+    assert_eq!(bebop.tags[0].name, "科幻");
+    assert_eq!(subjects[1].platform, "剧场版");
+    assert_eq!(subjects[1].date, None);
+
+    let person = archive.persons().unwrap().next().unwrap().unwrap();
+    assert_eq!(person.gender.as_deref(), Some("male"));
+    assert!(matches!(person.blood_type, Some(BloodType::A)));
+    assert_eq!(
+        (person.birth_year, person.birth_mon, person.birth_day),
+        (Some(1961), Some(6), Some(17))
+    );
+    assert_eq!(person.stat.collects, 2);
+
+    let character = archive.characters().unwrap().next().unwrap().unwrap();
+    assert_eq!(character.gender, "male");
+    assert_eq!(character.birth_year, None);
+    assert_eq!(character.birth_mon, Some(6));
+
+    let episodes: Vec<_> = archive
+        .episodes()
+        .unwrap()
+        .collect::<anyhow::Result<_>>()
+        .unwrap();
+    assert_eq!(episodes[0].duration_seconds, 1470);
+    assert_eq!(episodes[1].r#type, EpisodeType::SP);
+
+    assert_eq!(archive.subject_relations().unwrap().count(), 3);
+    assert!(archive.subject_persons().is_err());
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_relation_type() {
+    let relation = |relation_type: u32| {
+        ArchiveSubjectRelation {
+            subject_id: 1,
+            relation_type,
+            related_subject_id: 2,
+            order: 0,
+        }
+        .relation()
+    };
+    assert_eq!(relation(3), SubjectRelation::Sequel);
+    assert_eq!(relation(11).label_cn(), "衍生");
+    assert_eq!(relation(1006), SubjectRelation::Sequel);
+    assert_eq!(relation(1014).label_cn(), "联动");
+    assert_eq!(relation(3001), SubjectRelation::Soundtrack);
+    assert_eq!(relation(4003), SubjectRelation::Sequel);
+    assert_eq!(relation(4012), SubjectRelation::ParentStory);
+    assert_eq!(relation(4015).label_cn(), "扩展包");
+    assert_eq!(relation(9999), SubjectRelation::Other("9999".to_string()));
+}
+
+#[test]
+fn test_archive_zip() {
+    let path = temp_path("dump.zip");
+    write_zip(&path);
+    let mut archive = Archive::open(&path).unwrap();
+    assert_eq!(archive.subjects().unwrap().count(), 2);
+    assert_eq!(archive.episodes().unwrap().count(), 2);
+    assert!(archive.person_characters().is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "store")]
+#[test]
+fn test_load_archive() {
+    use crate::module::{archive::model::ArchiveLoadSummary, store::model::Store};
+
+    let path = temp_path("load.zip");
+    write_zip(&path);
+    let mut archive = Archive::open(&path).unwrap();
+    let store = Store::open_in_memory().unwrap();
+    let summary = store.load_archive(&mut archive).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        summary,
+        ArchiveLoadSummary {
+            subjects: 2,
+            persons: 1,
+            characters: 1,
+            episodes: 2,
+            subject_relations: 2,
+        }
+    );
+    let subject = store.get_subject(1).unwrap().unwrap().value;
+    assert_eq!(subject.name_cn, "星际牛仔");
+    assert_eq!(store.get_person(7).unwrap().unwrap().value.name, "山寺宏一");
+    assert_eq!(store.get_episode(11).unwrap().unwrap().value.subject_id, 1);
+
+    // 不在镜像中的关联条目被跳过
+    let related = store.get_subject_subjects(1).unwrap().unwrap().value;
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].id, 2);
+    assert_eq!(related[0].relation, "番外篇");
+    let parent = store.get_subject_subjects(2).unwrap().unwrap().value;
+    assert_eq!(parent[0].relation, "主线故事");
+}
+
+#[cfg(feature = "store")]
+#[test]
+fn test_load_archive_unsorted_relations() {
+    use crate::module::store::model::Store;
+
+    let directory = temp_path("unsorted");
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join(SUBJECT_FILE), SUBJECTS).unwrap();
+    fs::write(
+        directory.join(SUBJECT_RELATION_FILE),
+        r#"{"subject_id":2,"relation_type":12,"related_subject_id":1,"order":0}
+{"subject_id":1,"relation_type":6,"related_subject_id":2,"order":0}
+"#,
+    )
+    .unwrap();
+    let mut archive = Archive::open(&directory).unwrap();
+    let store = Store::open_in_memory().unwrap();
+    let error = store.load_archive(&mut archive).unwrap_err();
+    fs::remove_dir_all(&directory).unwrap();
+    assert!(error.to_string().contains("未按条目ID排序"));
+}
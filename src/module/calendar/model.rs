@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// 单集放送事件
+///
+/// 对应iCalendar中的一个全天VEVENT
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    /// 条目ID
+    pub subject_id: u32,
+    /// 条目名称（优先使用中文名）
+    pub subject_name: String,
+    /// 剧集ID
+    pub episode_id: u32,
+    /// 集数
+    pub episode: f64,
+    /// 剧集标题（优先使用中文名，可能为空）
+    pub episode_name: String,
+    /// 播出日期（YYYY-MM-DD）
+    pub airdate: String,
+}
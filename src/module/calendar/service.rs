@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use anyhow::{Result, bail};
+
+use crate::{
+    common::{
+        model::BangumiClient,
+        time::{civil_from_days, now_timestamp},
+    },
+    module::{
+        collection::model::CollectionType,
+        episode::model::{Episode, EpisodeType},
+        subject::model::{SubjectSmall, SubjectType},
+    },
+};
+
+use super::model::CalendarEvent;
+
+/// iCalendar单行内容的最大字节数（不含换行符）
+const ICS_LINE_LIMIT: usize = 75;
+
+/// 将Unix时间戳格式化为UTC日期（YYYY-MM-DD）
+fn format_date(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// 规范化播出日期为`YYYY-MM-DD`
+///
+/// 兼容月、日未补零的写法，日期为空或格式不正确时返回None
+fn normalize_date(date: &str) -> Option<String> {
+    let mut parts = date.trim().splitn(3, '-');
+    let year: u32 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    ((1..=12).contains(&month) && (1..=31).contains(&day))
+        .then(|| format!("{year:04}-{month:02}-{day:02}"))
+}
+
+/// 转义iCalendar文本值中的特殊字符
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 追加一行iCalendar内容
+///
+/// 超过75字节的行按字符边界折行，续行以空格开头
+fn push_line(ics: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > ICS_LINE_LIMIT {
+            ics.push_str("\r\n ");
+            // 续行开头的空格也计入长度
+            width = 1;
+        }
+        ics.push(c);
+        width += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+impl CalendarEvent {
+    /// 事件标题，如`葬送的芙莉莲 第5话 死者的幻影`
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} 第{}话", self.subject_name, self.episode);
+        if !self.episode_name.is_empty() {
+            summary.push(' ');
+            summary.push_str(&self.episode_name);
+        }
+        summary
+    }
+
+    /// 剧集页面链接
+    pub fn url(&self) -> String {
+        format!("https://bgm.tv/ep/{}", self.episode_id)
+    }
+
+    /// 条目页面链接
+    pub fn subject_url(&self) -> String {
+        format!("https://bgm.tv/subject/{}", self.subject_id)
+    }
+}
+
+/// 从条目的剧集列表中筛选即将播出的正片
+///
+/// # 参数
+/// - `subject`: 放送日历中的条目
+/// - `episodes`: 条目的剧集列表
+/// - `from_date`: 起始日期（YYYY-MM-DD，包含当日）
+///
+/// # 返回
+/// 返回播出日期不早于起始日期的正片事件，缺少播出日期的剧集会被跳过；
+/// 起始日期格式不正确时返回错误
+pub fn upcoming_events(
+    subject: &SubjectSmall,
+    episodes: &[Episode],
+    from_date: &str,
+) -> Result<Vec<CalendarEvent>> {
+    let Some(from_date) = normalize_date(from_date) else {
+        bail!(format!("起始日期格式不正确: {from_date}"))
+    };
+    let subject_name = if subject.name_cn.is_empty() {
+        &subject.name
+    } else {
+        &subject.name_cn
+    };
+    let events = episodes
+        .iter()
+        .filter(|episode| episode.r#type == EpisodeType::Normal)
+        .filter_map(|episode| {
+            let airdate = normalize_date(&episode.airdate)?;
+            (airdate >= from_date).then(|| CalendarEvent {
+                subject_id: subject.id,
+                subject_name: subject_name.clone(),
+                episode_id: episode.id,
+                episode: if episode.ep > 0.0 {
+                    episode.ep
+                } else {
+                    episode.sort
+                },
+                episode_name: if episode.name_cn.is_empty() {
+                    episode.name.clone()
+                } else {
+                    episode.name_cn.clone()
+                },
+                airdate,
+            })
+        })
+        .collect();
+    Ok(events)
+}
+
+/// 将放送事件生成为iCalendar（.ics）文本
+///
+/// 每个事件对应一个全天VEVENT，UID由剧集ID生成，重复导入时日历应用会更新而不是新增事件
+///
+/// # 参数
+/// - `events`: 放送事件
+/// - `generated_at`: 生成时间的Unix时间戳（秒），用于DTSTAMP
+///
+/// # 返回
+/// 返回以CRLF换行的iCalendar文本
+pub fn to_ics(events: &[CalendarEvent], generated_at: i64) -> String {
+    let (year, month, day) = civil_from_days(generated_at.div_euclid(86400));
+    let seconds = generated_at.rem_euclid(86400);
+    let dtstamp = format!(
+        "{year:04}{month:02}{day:02}T{:02}{:02}{:02}Z",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    );
+
+    let mut ics = String::new();
+    push_line(&mut ics, "BEGIN:VCALENDAR");
+    push_line(&mut ics, "VERSION:2.0");
+    push_line(&mut ics, "PRODID:-//bangumi-rs//calendar//ZH");
+    push_line(&mut ics, "CALSCALE:GREGORIAN");
+    push_line(&mut ics, "X-WR-CALNAME:Bangumi 放送日历");
+    for event in events {
+        push_line(&mut ics, "BEGIN:VEVENT");
+        push_line(&mut ics, &format!("UID:bgm-ep-{}@bgm.tv", event.episode_id));
+        push_line(&mut ics, &format!("DTSTAMP:{dtstamp}"));
+        push_line(
+            &mut ics,
+            &format!("DTSTART;VALUE=DATE:{}", event.airdate.replace('-', "")),
+        );
+        push_line(&mut ics, "DURATION:P1D");
+        push_line(
+            &mut ics,
+            &format!("SUMMARY:{}", escape_text(&event.summary())),
+        );
+        push_line(
+            &mut ics,
+            &format!("DESCRIPTION:{}", escape_text(&event.subject_url())),
+        );
+        push_line(&mut ics, &format!("URL:{}", event.url()));
+        push_line(&mut ics, "TRANSP:TRANSPARENT");
+        push_line(&mut ics, "END:VEVENT");
+    }
+    push_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+impl BangumiClient {
+    /// 获取放送日历中即将播出的剧集
+    ///
+    /// 遍历每日放送中的条目并拉取其正片列表，按播出日期筛选。
+    /// 指定用户名时仅保留该用户"在看"的动画
+    ///
+    /// # 参数
+    /// - `from_date`: 可选，起始日期（YYYY-MM-DD），默认为当天（UTC）
+    /// - `username`: 可选，仅保留该用户在看的条目
+    ///
+    /// # 返回
+    /// 返回按播出日期、条目ID、集数排序的放送事件，起始日期格式不正确时返回错误
+    pub async fn get_calendar_events(
+        &self,
+        from_date: Option<&str>,
+        username: Option<&str>,
+    ) -> Result<Vec<CalendarEvent>> {
+        let from_date = match from_date {
+            Some(date) => match normalize_date(date) {
+                Some(date) => date,
+                None => bail!(format!("起始日期格式不正确: {date}")),
+            },
+            None => format_date(now_timestamp()),
+        };
+
+        let mut doing = None;
+        if let Some(username) = username {
+            let collections = BangumiClient::fetch_all(|limit, offset| {
+                self.get_collection_subjects(
+                    username,
+                    Some(SubjectType::Anime),
+                    Some(CollectionType::Doing),
+                    Some(limit),
+                    Some(offset),
+                )
+            })
+            .await?;
+            doing = Some(
+                collections
+                    .iter()
+                    .map(|item| item.subject_id)
+                    .collect::<HashSet<u32>>(),
+            );
+        }
+
+        let mut events = Vec::new();
+        let mut visited = HashSet::new();
+        for day in self.get_calendar().await? {
+            for subject in day.items {
+                if !visited.insert(subject.id)
+                    || doing.as_ref().is_some_and(|ids| !ids.contains(&subject.id))
+                {
+                    continue;
+                }
+
+                let episodes = BangumiClient::fetch_all(|limit, offset| {
+                    self.get_episodes(
+                        subject.id,
+                        Some(EpisodeType::Normal),
+                        Some(limit),
+                        Some(offset),
+                    )
+                })
+                .await?;
+                events.extend(upcoming_events(&subject, &episodes, &from_date)?);
+            }
+        }
+
+        events.sort_by(|a, b| {
+            a.airdate
+                .cmp(&b.airdate)
+                .then(a.subject_id.cmp(&b.subject_id))
+                .then(a.episode.total_cmp(&b.episode))
+        });
+        Ok(events)
+    }
+
+    /// 导出放送日历为iCalendar（.ics）文本
+    ///
+    /// 可直接订阅或导入到常见日历应用，参数含义同[`BangumiClient::get_calendar_events`]
+    pub async fn export_calendar_ics(
+        &self,
+        from_date: Option<&str>,
+        username: Option<&str>,
+    ) -> Result<String> {
+        let events = self.get_calendar_events(from_date, username).await?;
+        Ok(to_ics(&events, now_timestamp()))
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    common::{
+        fixture::{self, Fixture, parse_all},
+        mock::{MockRoute, MockServer},
+    },
+    module::{
+        calendar::{
+            model::CalendarEvent,
+            service::{to_ics, upcoming_events},
+        },
+        episode::model::Episode,
+        subject::model::SubjectSmall,
+    },
+};
+
+fn episodes() -> Vec<Episode> {
+    parse_all([
+        fixture::episode(10, 1.0)
+            .with("name_cn", "旅程的终点")
+            .with("airdate", "2024-01-01"),
+        fixture::episode(11, 2.0).with("airdate", "2024-1-8"),
+        fixture::episode(12, 3.0).with("airdate", ""),
+        fixture::episode(13, 1.0)
+            .with("type", 1)
+            .with("name_cn", "特别篇")
+            .with("airdate", "2024-01-09"),
+    ])
+}
+
+fn frieren() -> Fixture {
+    fixture::subject_small(1)
+        .with("name", "Sousou no Frieren")
+        .with("name_cn", "葬送的芙莉莲")
+}
+
+#[test]
+fn test_upcoming_events() {
+    let subject: SubjectSmall = frieren().parse();
+    let events = upcoming_events(&subject, &episodes(), "2024-01-02").unwrap();
+
+    // 已播出、缺少日期以及非正片的剧集均被跳过，日期会被规范化
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].episode_id, 11);
+    assert_eq!(events[0].subject_name, "葬送的芙莉莲");
+    assert_eq!(events[0].airdate, "2024-01-08");
+    assert_eq!(events[0].summary(), "葬送的芙莉莲 第2话");
+
+    let events = upcoming_events(&subject, &episodes(), "2024-01-01").unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].summary(), "葬送的芙莉莲 第1话 旅程的终点");
+
+    // 起始日期格式不正确时返回错误，而不是返回全部剧集
+    assert!(upcoming_events(&subject, &episodes(), "2024/01/01").is_err());
+}
+
+#[test]
+fn test_to_ics() {
+    let events = vec![CalendarEvent {
+        subject_id: 400602,
+        subject_name: "Title, with; special\\chars".to_string(),
+        episode_id: 1227087,
+        episode: 5.0,
+        episode_name: String::new(),
+        airdate: "2024-01-08".to_string(),
+    }];
+    // 2024-01-02T03:04:05Z
+    let ics = to_ics(&events, 1704164645);
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.contains("UID:bgm-ep-1227087@bgm.tv\r\n"));
+    assert!(ics.contains("DTSTAMP:20240102T030405Z\r\n"));
+    assert!(ics.contains("DTSTART;VALUE=DATE:20240108\r\n"));
+    assert!(ics.contains("SUMMARY:Title\\, with\\; special\\\\chars 第5话\r\n"));
+    assert!(ics.contains("URL:https://bgm.tv/ep/1227087\r\n"));
+    assert!(ics.contains("DESCRIPTION:https://bgm.tv/subject/400602\r\n"));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+}
+
+#[test]
+fn test_to_ics_folds_long_lines() {
+    let events = vec![CalendarEvent {
+        subject_id: 1,
+        subject_name: "葬送的芙莉莲".repeat(10),
+        episode_id: 1,
+        episode: 1.0,
+        episode_name: String::new(),
+        airdate: "2024-01-08".to_string(),
+    }];
+    let ics = to_ics(&events, 0);
+
+    for line in ics.split("\r\n") {
+        assert!(line.len() <= 75, "行过长: {line}");
+    }
+    // 去除折行后可以还原完整内容
+    let unfolded = ics.replace("\r\n ", "");
+    assert!(unfolded.contains(&format!("SUMMARY:{} 第1话", "葬送的芙莉莲".repeat(10))));
+}
+
+#[tokio::test]
+async fn test_get_calendar_events_doing_only() {
+    let server = MockServer::start(vec![
+        MockRoute::json(
+            "GET",
+            "/calendar",
+            200,
+            serde_json::json!([
+                {
+                    "weekday": {"en": "Mon", "cn": "星期一", "ja": "月耀日", "id": 1},
+                    "items": [frieren(), fixture::subject_small(2)]
+                }
+            ]),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/users/sai/collections",
+            200,
+            serde_json::json!({"total": 1, "limit": 100, "offset": 0, "data": [
+                fixture::collection(1).with("type", 3).with("subject", None::<()>)
+            ]}),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/episodes",
+            200,
+            serde_json::json!({
+                "total": 2,
+                "limit": 100,
+                "offset": 0,
+                "data": [
+                    fixture::episode(11, 2.0).with("airdate", "2024-01-08"),
+                    fixture::episode(10, 1.0).with("airdate", "2024-01-01")
+                ]
+            }),
+        ),
+    ]);
+    let client = server.client();
+
+    let events = client
+        .get_calendar_events(Some("2024-01-01"), Some("sai"))
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| event.subject_id == 1));
+    assert_eq!(events[0].episode_id, 10);
+
+    // 仅请求了在看条目的剧集
+    let requests = server.requests();
+    let episode_requests: Vec<_> = requests
+        .iter()
+        .filter(|request| request.path == "/v0/episodes")
+        .collect();
+    assert_eq!(episode_requests.len(), 1);
+    assert!(episode_requests[0].query.contains("subject_id=1"));
+
+    let ics = client
+        .export_calendar_ics(Some("2024-01-02"), Some("sai"))
+        .await
+        .unwrap();
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+
+    // 起始日期格式不正确时在发出请求前返回错误
+    let sent = server.requests().len();
+    assert!(
+        client
+            .export_calendar_ics(Some("next week"), None)
+            .await
+            .is_err()
+    );
+    assert_eq!(server.requests().len(), sent);
+}
@@ -2,5 +2,5 @@
 pub mod model;
 pub mod service;
 
-#[cfg(test)]
+#[cfg(all(test, any(feature = "reqwest", feature = "ureq")))]
 pub mod test;
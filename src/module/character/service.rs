@@ -1,6 +1,6 @@
 use anyhow::Result;
 use bytes::Bytes;
-use reqwest::Method;
+use http::Method;
 
 use crate::{
     common::model::BangumiClient,
@@ -42,7 +42,7 @@ impl BangumiClient {
         request_builder = request_builder.json(&payload);
 
         // 发送请求并解析响应
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -62,7 +62,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析响应
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -89,7 +89,7 @@ impl BangumiClient {
         request_builder = request_builder.query(&[("type", &r#type)]);
 
         // 发送请求并获取图片字节数据
-        let res = self.request_send(request_builder).await?.bytes().await?;
+        let res = self.request_send(request_builder).await?.bytes();
 
         Ok(res)
     }
@@ -109,7 +109,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析响应
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -129,7 +129,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析响应
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -29,7 +29,7 @@ pub struct CollectionStats {
 /// 收藏类型枚举
 ///
 /// 表示用户对条目（如动画、书籍等）的收藏状态分类，使用u8作为底层存储类型
-#[derive(Debug, Clone, Serialize_repr, Deserialize_repr)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
 pub enum CollectionType {
     /// 想看（计划观看/阅读）
@@ -47,7 +47,7 @@ pub enum CollectionType {
 /// 单集收藏状态枚举
 ///
 /// 表示用户对条目中单个剧集的收藏/观看状态，使用u8作为底层存储类型
-#[derive(Debug, Clone, Serialize_repr, Deserialize_repr)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
 pub enum CollectionEpisodeType {
     /// 未处理（未标记状态）
@@ -63,7 +63,7 @@ pub enum CollectionEpisodeType {
 /// 更新条目收藏状态的请求参数
 ///
 /// 用于向API提交更新用户对某个条目的收藏信息，包含状态、评分、进度等
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct CollectionSubjectUpdate {
     /// 收藏类型（可选，更新为指定状态）
     pub r#type: Option<CollectionType>,
@@ -195,7 +195,7 @@ pub struct UserSubject {
 /// 批量更新单集收藏状态的请求参数
 ///
 /// 用于一次性更新多个剧集的收藏状态
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CollectionEpisodesUpdate {
     /// 剧集ID列表（需更新状态的剧集）
     pub episode_id: Vec<u32>,
@@ -211,3 +211,18 @@ pub struct CollectionEpisodeUpdate {
     /// 目标收藏状态（更新后的状态）
     pub r#type: CollectionEpisodeType,
 }
+
+/// 观看进度更新结果
+///
+/// 记录"标记看到第N集"操作实际标记的剧集及收藏状态变化
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressUpdate {
+    /// 条目ID
+    pub subject_id: u32,
+    /// 被标记为已看的剧集ID列表
+    pub episode_ids: Vec<u32>,
+    /// 更新后的剧集进度（已看正片数量）
+    pub ep_status: u32,
+    /// 是否已看完全部正片（此时收藏状态会被设为"看过"）
+    pub completed: bool,
+}
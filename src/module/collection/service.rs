@@ -1,16 +1,94 @@
 use anyhow::Result;
-use reqwest::Method;
+use http::Method;
+use serde::de::IgnoredAny;
 
 use crate::{
-    common::model::BangumiClient,
-    module::{episode::model::EpisodeType, model::Paged, subject::model::SubjectType},
+    common::model::{ApiResponse, BangumiClient},
+    module::{
+        episode::model::{Episode, EpisodeType},
+        model::Paged,
+        subject::model::SubjectType,
+    },
 };
 
 use super::model::{
-    CollectionCharacter, CollectionEpisode, CollectionEpisodeUpdate, CollectionEpisodesUpdate,
-    CollectionPerson, CollectionSubject, CollectionSubjectUpdate, CollectionType,
+    CollectionCharacter, CollectionEpisode, CollectionEpisodeType, CollectionEpisodeUpdate,
+    CollectionEpisodesUpdate, CollectionPerson, CollectionSubject, CollectionSubjectUpdate,
+    CollectionType, ProgressUpdate,
 };
 
+/// 比较集数时允许的误差
+const EP_EPSILON: f64 = 1e-6;
+
+/// 挑选"看到第N集"需要标记的正片
+///
+/// 仅考虑正片（跳过SP、OP、ED），集数优先使用`ep`，为0时使用`sort`，支持小数集数。
+/// 指定光盘编号时，之前光盘的全部正片以及该光盘中集数不超过`up_to_ep`的正片都会被选中
+///
+/// # 参数
+/// - `episodes`: 条目的剧集列表
+/// - `up_to_ep`: 看到的集数（包含该集）
+/// - `disc`: 可选，光盘编号（用于每张光盘单独编号的条目）
+///
+/// # 返回
+/// 返回按顺序排列的剧集ID，以及是否已看完全部正片
+pub fn select_progress_episodes(
+    episodes: &[Episode],
+    up_to_ep: f64,
+    disc: Option<u32>,
+) -> (Vec<u32>, bool) {
+    let mut normal: Vec<&Episode> = episodes
+        .iter()
+        .filter(|episode| episode.r#type == EpisodeType::Normal)
+        .collect();
+    normal.sort_by(|a, b| a.disc.cmp(&b.disc).then(a.sort.total_cmp(&b.sort)));
+
+    let watched = |episode: &Episode| {
+        let number = if episode.ep > 0.0 {
+            episode.ep
+        } else {
+            episode.sort
+        };
+        let within = number <= up_to_ep + EP_EPSILON;
+        match disc {
+            Some(disc) => episode.disc < disc || (episode.disc == disc && within),
+            None => within,
+        }
+    };
+
+    let selected: Vec<u32> = normal
+        .iter()
+        .filter(|episode| watched(episode))
+        .map(|episode| episode.id)
+        .collect();
+    let completed = !normal.is_empty() && selected.len() == normal.len();
+
+    (selected, completed)
+}
+
+/// 决定"看到第N集"后写入的收藏状态
+///
+/// 看完全部正片时设为"看过"；未看完时仅把未收藏、想看、搁置、抛弃升级为"在看"，
+/// 已是"在看"或"看过"的收藏保持不变，避免重看时把"看过"降级
+///
+/// # 参数
+/// - `current`: 当前收藏状态，未收藏时为None
+/// - `completed`: 是否已看完全部正片
+///
+/// # 返回
+/// 返回需要写入的收藏状态，无需修改时返回None
+pub fn progress_collection_type(
+    current: Option<&CollectionType>,
+    completed: bool,
+) -> Option<CollectionType> {
+    match (current, completed) {
+        (Some(CollectionType::Done), _) => None,
+        (_, true) => Some(CollectionType::Done),
+        (Some(CollectionType::Doing), false) => None,
+        (_, false) => Some(CollectionType::Doing),
+    }
+}
+
 impl BangumiClient {
     /// 获取用户的收藏条目列表
     ///
@@ -56,7 +134,7 @@ impl BangumiClient {
         }
 
         // 发送请求并解析分页结果
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -85,7 +163,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析收藏详情
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -118,6 +196,30 @@ impl BangumiClient {
         Ok(())
     }
 
+    /// 创建或更新当前用户对条目的收藏，并返回响应元信息
+    ///
+    /// 与 [`BangumiClient::post_collection_subject`] 相同，额外保留状态码、响应头、耗时与请求ID
+    pub async fn post_collection_subject_with_meta(
+        &self,
+        subject_id: u32,
+        payload: Option<CollectionSubjectUpdate>,
+    ) -> Result<ApiResponse<()>> {
+        // 构建请求URL：当前用户条目收藏操作接口（"-"表示当前认证用户）
+        let url = format!("{}/v0/users/-/collections/{subject_id}", self.base_path);
+
+        let mut request_builder = self.request_builder(Method::POST, &url);
+        // 添加请求体参数
+        request_builder = request_builder.json(&payload);
+
+        // 发送请求并保留响应元信息，忽略响应内容
+        let res = self
+            .send_with_meta::<IgnoredAny>(request_builder)
+            .await?
+            .map(|_| ());
+
+        Ok(res)
+    }
+
     /// 部分更新当前用户对条目的收藏
     ///
     /// 用于增量修改收藏信息（如仅更新评分或进度，需认证）
@@ -146,6 +248,30 @@ impl BangumiClient {
         Ok(())
     }
 
+    /// 部分更新当前用户对条目的收藏，并返回响应元信息
+    ///
+    /// 与 [`BangumiClient::patch_collection_subject`] 相同，额外保留状态码、响应头、耗时与请求ID
+    pub async fn patch_collection_subject_with_meta(
+        &self,
+        subject_id: u32,
+        payload: Option<CollectionSubjectUpdate>,
+    ) -> Result<ApiResponse<()>> {
+        // 构建请求URL：当前用户条目收藏部分更新接口
+        let url = format!("{}/v0/users/-/collections/{subject_id}", self.base_path);
+
+        let mut request_builder = self.request_builder(Method::PATCH, &url);
+        // 添加请求体参数
+        request_builder = request_builder.json(&payload);
+
+        // 发送请求并保留响应元信息，忽略响应内容
+        let res = self
+            .send_with_meta::<IgnoredAny>(request_builder)
+            .await?
+            .map(|_| ());
+
+        Ok(res)
+    }
+
     /// 获取当前用户条目的剧集收藏状态
     ///
     /// 查询指定条目下各剧集的收藏/观看状态（需认证）
@@ -186,7 +312,7 @@ impl BangumiClient {
         }
 
         // 发送请求并解析分页结果
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -222,6 +348,33 @@ impl BangumiClient {
         Ok(())
     }
 
+    /// 批量更新当前用户剧集收藏状态，并返回响应元信息
+    ///
+    /// 与 [`BangumiClient::patch_collection_episodes`] 相同，额外保留状态码、响应头、耗时与请求ID
+    pub async fn patch_collection_episodes_with_meta(
+        &self,
+        subject_id: u32,
+        payload: Option<CollectionEpisodesUpdate>,
+    ) -> Result<ApiResponse<()>> {
+        // 构建请求URL：当前用户剧集批量收藏更新接口
+        let url = format!(
+            "{}/v0/users/-/collections/{subject_id}/episodes",
+            self.base_path
+        );
+
+        let mut request_builder = self.request_builder(Method::PATCH, &url);
+        // 添加请求体参数
+        request_builder = request_builder.json(&payload);
+
+        // 发送请求并保留响应元信息，忽略响应内容
+        let res = self
+            .send_with_meta::<IgnoredAny>(request_builder)
+            .await?
+            .map(|_| ());
+
+        Ok(res)
+    }
+
     /// 获取当前用户单个剧集的收藏状态
     ///
     /// 查询指定剧集的收藏/观看状态（需认证）
@@ -241,7 +394,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析结果
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -277,6 +430,33 @@ impl BangumiClient {
         Ok(())
     }
 
+    /// 更新当前用户单个剧集的收藏状态，并返回响应元信息
+    ///
+    /// 与 [`BangumiClient::put_collection_episode`] 相同，额外保留状态码、响应头、耗时与请求ID
+    pub async fn put_collection_episode_with_meta(
+        &self,
+        episode_id: u32,
+        payload: Option<CollectionEpisodeUpdate>,
+    ) -> Result<ApiResponse<()>> {
+        // 构建请求URL：当前用户单个剧集收藏更新接口
+        let url = format!(
+            "{}/v0/users/-/collections/-/episodes/{episode_id}",
+            self.base_path
+        );
+
+        let mut request_builder = self.request_builder(Method::PUT, &url);
+        // 添加请求体参数
+        request_builder = request_builder.json(&payload);
+
+        // 发送请求并保留响应元信息，忽略响应内容
+        let res = self
+            .send_with_meta::<IgnoredAny>(request_builder)
+            .await?
+            .map(|_| ());
+
+        Ok(res)
+    }
+
     /// 获取用户收藏的角色列表
     ///
     /// 查询指定用户收藏的所有角色（支持分页）
@@ -299,7 +479,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析分页结果
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -328,7 +508,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析结果
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -352,7 +532,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析分页结果
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -381,7 +561,85 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析结果
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
+
+        Ok(res)
+    }
+
+    /// 标记看到第N集（需认证）
+    ///
+    /// 获取条目的全部正片，将不超过指定集数的正片批量标记为已看，
+    /// 并同步更新条目的剧集进度。看完全部正片时收藏状态设为"看过"，
+    /// 否则仅将未收藏、想看、搁置、抛弃的条目设为"在看"，不会降级已有的"看过"
+    ///
+    /// # 参数
+    /// - `subject_id`: 条目ID
+    /// - `up_to_ep`: 看到的集数（包含该集，支持小数）
+    /// - `disc`: 可选，光盘编号（用于每张光盘单独编号的条目）
+    ///
+    /// # 返回
+    /// 返回实际标记的剧集及收藏状态变化
+    pub async fn mark_progress(
+        &self,
+        subject_id: u32,
+        up_to_ep: f64,
+        disc: Option<u32>,
+    ) -> Result<ProgressUpdate> {
+        // 分页获取全部正片
+        let episodes = BangumiClient::fetch_all(|limit, offset| {
+            self.get_episodes(
+                subject_id,
+                Some(EpisodeType::Normal),
+                Some(limit),
+                Some(offset),
+            )
+        })
+        .await?;
+
+        let (episode_ids, completed) = select_progress_episodes(&episodes, up_to_ep, disc);
+        let ep_status = episode_ids.len() as u32;
+
+        // 先写入收藏状态，确保未收藏的条目也能记录剧集进度
+        let current = self.current_collection_type(subject_id).await?;
+        let payload = CollectionSubjectUpdate {
+            r#type: progress_collection_type(current.as_ref(), completed),
+            ep_status: Some(ep_status),
+            ..Default::default()
+        };
+        self.post_collection_subject(subject_id, Some(payload))
+            .await?;
+
+        if !episode_ids.is_empty() {
+            let payload = CollectionEpisodesUpdate {
+                episode_id: episode_ids.clone(),
+                r#type: CollectionEpisodeType::Done,
+            };
+            self.patch_collection_episodes(subject_id, Some(payload))
+                .await?;
+        }
+
+        Ok(ProgressUpdate {
+            subject_id,
+            episode_ids,
+            ep_status,
+            completed,
+        })
+    }
+
+    /// 获取当前用户对条目的收藏状态（需认证）
+    ///
+    /// 未收藏时接口返回404，此时返回None而不是错误
+    async fn current_collection_type(&self, subject_id: u32) -> Result<Option<CollectionType>> {
+        // 构建请求URL：当前用户条目收藏详情接口（"-"表示当前认证用户）
+        let url = format!("{}/v0/users/-/collections/{subject_id}", self.base_path);
+
+        let request_builder = self.request_builder(Method::GET, &url);
+
+        // 发送请求，未收藏时返回None
+        let res = match self.request_send_optional(request_builder).await? {
+            Some(response) => Some(response.json::<CollectionSubject>()?.r#type),
+            None => None,
+        };
 
         Ok(res)
     }
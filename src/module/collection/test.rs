@@ -1,15 +1,33 @@
 use crate::{
-    common::model::BangumiClient,
+    common::{
+        fixture::{self, Fixture, parse_all},
+        mock::{MockRoute, MockServer},
+        model::BangumiClient,
+    },
     module::{
-        collection::model::{
-            CollectionEpisodeType, CollectionEpisodeUpdate, CollectionEpisodesUpdate,
-            CollectionSubjectUpdate, CollectionType,
+        collection::{
+            model::{
+                CollectionEpisodeType, CollectionEpisodeUpdate, CollectionEpisodesUpdate,
+                CollectionSubjectUpdate, CollectionType,
+            },
+            service::{progress_collection_type, select_progress_episodes},
         },
-        episode::model::EpisodeType,
+        episode::model::{Episode, EpisodeType},
         subject::model::SubjectType,
     },
 };
 
+fn episode(id: u32, r#type: u8, sort: f64, ep: f64, disc: u32) -> Fixture {
+    fixture::episode(id, sort)
+        .with("type", r#type)
+        .with("ep", ep)
+        .with("disc", disc)
+}
+
+fn episodes(values: Vec<Fixture>) -> Vec<Episode> {
+    parse_all(values)
+}
+
 #[tokio::test]
 async fn test_get_collection_subjects() {
     let client = BangumiClient::default();
@@ -136,3 +154,147 @@ async fn test_get_collection_person() {
     let result = client.get_collection_person("sai", 1).await;
     assert!(result.is_ok(), "错误: {:?}", result.err());
 }
+
+#[test]
+fn test_select_progress_episodes() {
+    let list = episodes(vec![
+        episode(1, 0, 1.0, 1.0, 0),
+        episode(2, 0, 2.0, 2.0, 0),
+        episode(3, 0, 2.5, 2.5, 0),
+        episode(4, 1, 1.0, 1.0, 0),
+        episode(5, 2, 1.0, 1.0, 0),
+        episode(6, 0, 3.0, 0.0, 0),
+    ]);
+
+    assert_eq!(
+        select_progress_episodes(&list, 2.0, None),
+        (vec![1, 2], false)
+    );
+    assert_eq!(
+        select_progress_episodes(&list, 2.5, None),
+        (vec![1, 2, 3], false)
+    );
+    assert_eq!(
+        select_progress_episodes(&list, 3.0, None),
+        (vec![1, 2, 3, 6], true)
+    );
+    assert_eq!(select_progress_episodes(&list, 0.0, None), (vec![], false));
+}
+
+#[test]
+fn test_select_progress_episodes_multi_disc() {
+    let list = episodes(vec![
+        episode(21, 0, 3.0, 1.0, 2),
+        episode(11, 0, 1.0, 1.0, 1),
+        episode(12, 0, 2.0, 2.0, 1),
+        episode(22, 0, 4.0, 2.0, 2),
+    ]);
+
+    assert_eq!(
+        select_progress_episodes(&list, 1.0, Some(2)),
+        (vec![11, 12, 21], false)
+    );
+    assert_eq!(
+        select_progress_episodes(&list, 2.0, Some(2)),
+        (vec![11, 12, 21, 22], true)
+    );
+    assert_eq!(
+        select_progress_episodes(&list, 1.0, Some(1)),
+        (vec![11], false)
+    );
+}
+
+#[test]
+fn test_progress_collection_type() {
+    use CollectionType::*;
+
+    assert_eq!(progress_collection_type(None, false), Some(Doing));
+    assert_eq!(progress_collection_type(Some(&Wish), false), Some(Doing));
+    assert_eq!(progress_collection_type(Some(&Dropped), false), Some(Doing));
+    assert_eq!(progress_collection_type(Some(&Doing), false), None);
+    assert_eq!(progress_collection_type(Some(&OnHold), true), Some(Done));
+    // 重看已看过的条目不会降级为在看
+    assert_eq!(progress_collection_type(Some(&Done), false), None);
+    assert_eq!(progress_collection_type(Some(&Done), true), None);
+}
+
+fn progress_routes() -> Vec<MockRoute> {
+    vec![
+        MockRoute::json(
+            "GET",
+            "/v0/episodes",
+            200,
+            serde_json::json!({
+                "total": 2,
+                "limit": 100,
+                "offset": 0,
+                "data": [episode(1, 0, 1.0, 1.0, 0), episode(2, 0, 2.0, 2.0, 0)]
+            }),
+        ),
+        MockRoute::json(
+            "POST",
+            "/v0/users/-/collections/1",
+            202,
+            serde_json::Value::Null,
+        ),
+        MockRoute::json(
+            "PATCH",
+            "/v0/users/-/collections/1/episodes",
+            204,
+            serde_json::Value::Null,
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn test_mark_progress_with_mock_server() {
+    // 未收藏的条目（收藏接口返回404）
+    let server = MockServer::start(progress_routes());
+    let client = server.client();
+
+    let result = client.mark_progress(1, 2.0, None).await.unwrap();
+    assert_eq!(result.episode_ids, vec![1, 2]);
+    assert!(result.completed);
+
+    let requests = server.requests();
+    let post = requests.iter().find(|r| r.method == "POST").unwrap();
+    assert_eq!(
+        serde_json::from_str::<CollectionSubjectUpdate>(&post.body).unwrap(),
+        CollectionSubjectUpdate {
+            r#type: Some(CollectionType::Done),
+            ep_status: Some(2),
+            ..Default::default()
+        }
+    );
+    let patch = requests.iter().find(|r| r.method == "PATCH").unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&patch.body).unwrap(),
+        serde_json::json!({"episode_id": [1, 2], "type": 2})
+    );
+}
+
+#[tokio::test]
+async fn test_mark_progress_keeps_done() {
+    let mut routes = progress_routes();
+    routes.push(MockRoute::json(
+        "GET",
+        "/v0/users/-/collections/1",
+        200,
+        fixture::collection(1).with("ep_status", 2).json(),
+    ));
+    let server = MockServer::start(routes);
+    let client = server.client();
+
+    let result = client.mark_progress(1, 1.0, None).await.unwrap();
+    assert!(!result.completed);
+
+    let requests = server.requests();
+    let post = requests.iter().find(|r| r.method == "POST").unwrap();
+    assert_eq!(
+        serde_json::from_str::<CollectionSubjectUpdate>(&post.body).unwrap(),
+        CollectionSubjectUpdate {
+            ep_status: Some(1),
+            ..Default::default()
+        }
+    );
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use reqwest::Method;
+use http::Method;
 
 use crate::{common::model::BangumiClient, module::model::Paged};
 
@@ -47,7 +47,7 @@ impl BangumiClient {
         }
 
         // 发送请求并解析响应为分页的剧集列表
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -69,7 +69,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析响应为剧集详情结构体
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -0,0 +1,6 @@
+/// 收藏导出
+pub mod model;
+pub mod service;
+
+#[cfg(all(test, any(feature = "reqwest", feature = "ureq")))]
+pub mod test;
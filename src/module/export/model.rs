@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::module::collection::model::{
+    CollectionCharacter, CollectionEpisode, CollectionPerson, CollectionSubject,
+};
+
+/// 当前导出存档的格式版本
+///
+/// 存档结构发生不兼容变化时递增，读取时据此拒绝无法识别的存档
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// 用户收藏的完整导出存档
+///
+/// 包含条目、角色、人物收藏以及剧集观看进度，可序列化为带版本号的JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionArchive {
+    /// 存档格式版本
+    pub version: u32,
+    /// 导出的用户名
+    pub username: String,
+    /// 导出时间（Unix时间戳，秒）
+    pub exported_at: u64,
+    /// 条目收藏列表
+    pub subjects: Vec<CollectionSubject>,
+    /// 角色收藏列表
+    pub characters: Vec<CollectionCharacter>,
+    /// 人物收藏列表
+    pub persons: Vec<CollectionPerson>,
+    /// 剧集观看进度（按条目分组）
+    pub episodes: Vec<EpisodeProgress>,
+}
+
+/// 单个条目的剧集观看进度
+///
+/// 记录条目下每一集的收藏状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpisodeProgress {
+    /// 条目ID
+    pub subject_id: u32,
+    /// 剧集收藏状态列表
+    pub episodes: Vec<CollectionEpisode>,
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+
+use crate::{
+    common::{model::BangumiClient, time::now_timestamp},
+    module::{
+        collection::model::{CollectionSubject, CollectionType},
+        subject::model::SubjectType,
+    },
+};
+
+use super::model::{ARCHIVE_VERSION, CollectionArchive, EpisodeProgress};
+
+/// 转义CSV字段
+///
+/// 字段包含逗号、引号或换行时使用双引号包裹，并将内部引号加倍
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 将文本包装为XML的CDATA段
+fn cdata(value: &str) -> String {
+    format!("<![CDATA[{}]]>", value.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// 收藏类型对应的英文名称（用于CSV导出）
+fn collection_type_name(r#type: &CollectionType) -> &'static str {
+    match r#type {
+        CollectionType::Wish => "wish",
+        CollectionType::Done => "done",
+        CollectionType::Doing => "doing",
+        CollectionType::OnHold => "on_hold",
+        CollectionType::Dropped => "dropped",
+    }
+}
+
+/// 收藏类型对应的MyAnimeList状态
+fn mal_status(r#type: &CollectionType) -> &'static str {
+    match r#type {
+        CollectionType::Wish => "Plan to Watch",
+        CollectionType::Done => "Completed",
+        CollectionType::Doing => "Watching",
+        CollectionType::OnHold => "On-Hold",
+        CollectionType::Dropped => "Dropped",
+    }
+}
+
+impl CollectionArchive {
+    /// 序列化为带版本号的JSON存档
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 从JSON存档反序列化
+    ///
+    /// 存档版本高于当前支持的版本时返回错误
+    pub fn from_json(json: &str) -> Result<Self> {
+        let archive: CollectionArchive = serde_json::from_str(json)?;
+        if archive.version > ARCHIVE_VERSION {
+            bail!(format!(
+                "不支持的存档版本: {}（当前支持 {}）",
+                archive.version, ARCHIVE_VERSION
+            ));
+        }
+        Ok(archive)
+    }
+
+    /// 将条目收藏导出为CSV
+    ///
+    /// 每行一个条目收藏，标签以空格分隔
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "subject_id,subject_type,name,name_cn,type,rate,ep_status,vol_status,tags,comment,private,updated_at\n",
+        );
+        for item in &self.subjects {
+            let (name, name_cn) = item
+                .subject
+                .as_ref()
+                .map(|subject| (subject.name.as_str(), subject.name_cn.as_str()))
+                .unwrap_or_default();
+            let row = [
+                item.subject_id.to_string(),
+                (item.subject_type.clone() as u8).to_string(),
+                csv_field(name),
+                csv_field(name_cn),
+                collection_type_name(&item.r#type).to_string(),
+                item.rate.to_string(),
+                item.ep_status.to_string(),
+                item.vol_status.to_string(),
+                csv_field(&item.tags.join(" ")),
+                csv_field(item.comment.as_deref().unwrap_or_default()),
+                item.private.to_string(),
+                csv_field(&item.updated_at),
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// 将动画收藏导出为MyAnimeList的XML导出格式
+    ///
+    /// # 参数
+    /// - `mal_ids`: Bangumi条目ID到MyAnimeList动画ID的映射，缺失的条目以0填充
+    ///
+    /// # 返回
+    /// 返回可被MyAnimeList及兼容站点导入的XML文本
+    pub fn to_mal_xml(&self, mal_ids: &HashMap<u32, u32>) -> String {
+        let anime: Vec<&CollectionSubject> = self
+            .subjects
+            .iter()
+            .filter(|item| item.subject_type == SubjectType::Anime)
+            .collect();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n<myanimelist>\n");
+        xml.push_str("  <myinfo>\n");
+        xml.push_str(&format!(
+            "    <user_name>{}</user_name>\n",
+            cdata(&self.username)
+        ));
+        xml.push_str("    <user_export_type>1</user_export_type>\n");
+        xml.push_str(&format!(
+            "    <user_total_anime>{}</user_total_anime>\n",
+            anime.len()
+        ));
+        xml.push_str("  </myinfo>\n");
+
+        for item in anime {
+            let (title, episodes) = item
+                .subject
+                .as_ref()
+                .map(|subject| (subject.name.as_str(), subject.eps))
+                .unwrap_or_default();
+            xml.push_str("  <anime>\n");
+            xml.push_str(&format!(
+                "    <series_animedb_id>{}</series_animedb_id>\n",
+                mal_ids.get(&item.subject_id).copied().unwrap_or_default()
+            ));
+            xml.push_str(&format!(
+                "    <series_title>{}</series_title>\n",
+                cdata(title)
+            ));
+            xml.push_str(&format!(
+                "    <series_episodes>{episodes}</series_episodes>\n"
+            ));
+            xml.push_str(&format!(
+                "    <my_watched_episodes>{}</my_watched_episodes>\n",
+                item.ep_status
+            ));
+            xml.push_str("    <my_start_date>0000-00-00</my_start_date>\n");
+            xml.push_str("    <my_finish_date>0000-00-00</my_finish_date>\n");
+            xml.push_str(&format!("    <my_score>{}</my_score>\n", item.rate));
+            xml.push_str(&format!(
+                "    <my_status>{}</my_status>\n",
+                mal_status(&item.r#type)
+            ));
+            xml.push_str(&format!(
+                "    <my_comments>{}</my_comments>\n",
+                cdata(item.comment.as_deref().unwrap_or_default())
+            ));
+            xml.push_str(&format!(
+                "    <my_tags>{}</my_tags>\n",
+                cdata(&item.tags.join(", "))
+            ));
+            xml.push_str("    <update_on_import>1</update_on_import>\n");
+            xml.push_str("  </anime>\n");
+        }
+
+        xml.push_str("</myanimelist>\n");
+        xml
+    }
+}
+
+impl BangumiClient {
+    /// 导出用户的全部收藏
+    ///
+    /// 分页拉取用户所有类型、所有状态的条目收藏，以及角色、人物收藏。
+    /// 剧集观看进度接口仅对当前认证用户可用，因此需显式开启
+    ///
+    /// # 参数
+    /// - `username`: 用户名
+    /// - `include_episodes`: 是否导出剧集观看进度（需认证，且`username`应为当前用户）
+    ///
+    /// # 返回
+    /// 返回包含完整收藏信息的导出存档
+    pub async fn export_collections(
+        &self,
+        username: &str,
+        include_episodes: bool,
+    ) -> Result<CollectionArchive> {
+        // 不指定条目类型与收藏状态时接口会返回全部收藏
+        let subjects = BangumiClient::fetch_all(|limit, offset| {
+            self.get_collection_subjects(username, None, None, Some(limit), Some(offset))
+        })
+        .await?;
+
+        let characters = self
+            .get_collection_characters(username)
+            .await?
+            .data
+            .unwrap_or_default();
+        let persons = self
+            .get_collection_persons(username)
+            .await?
+            .data
+            .unwrap_or_default();
+
+        let mut episodes = Vec::new();
+        if include_episodes {
+            for item in subjects
+                .iter()
+                .filter(|item| matches!(item.subject_type, SubjectType::Anime | SubjectType::Real))
+            {
+                let progress = BangumiClient::fetch_all(|limit, offset| {
+                    self.get_collection_episodes(item.subject_id, Some(offset), Some(limit), None)
+                })
+                .await?;
+                episodes.push(EpisodeProgress {
+                    subject_id: item.subject_id,
+                    episodes: progress,
+                });
+            }
+        }
+
+        let exported_at = now_timestamp().max(0) as u64;
+
+        Ok(CollectionArchive {
+            version: ARCHIVE_VERSION,
+            username: username.to_string(),
+            exported_at,
+            subjects,
+            characters,
+            persons,
+            episodes,
+        })
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::model::BangumiClient,
+    module::export::model::{ARCHIVE_VERSION, CollectionArchive},
+};
+
+fn archive() -> CollectionArchive {
+    let image = serde_json::json!({
+        "large": "", "common": "", "medium": "", "small": "", "grid": ""
+    });
+    serde_json::from_value(serde_json::json!({
+        "version": ARCHIVE_VERSION,
+        "username": "sai",
+        "exported_at": 0,
+        "subjects": [
+            {
+                "subject_id": 1,
+                "subject_type": 2,
+                "rate": 9,
+                "type": 2,
+                "comment": "好看, \"推荐\"",
+                "tags": ["TV", "原创"],
+                "ep_status": 12,
+                "vol_status": 0,
+                "updated_at": "2024-01-01T00:00:00+08:00",
+                "private": false,
+                "subject": {
+                    "id": 1,
+                    "type": 2,
+                    "name": "Title ]]> One",
+                    "name_cn": "标题",
+                    "short_summary": "",
+                    "date": "2020-01-01",
+                    "images": image,
+                    "volumes": 0,
+                    "eps": 12,
+                    "collection_total": 100,
+                    "score": 7.5,
+                    "rank": 10,
+                    "tags": []
+                }
+            },
+            {
+                "subject_id": 2,
+                "subject_type": 1,
+                "rate": 0,
+                "type": 1,
+                "comment": null,
+                "tags": [],
+                "ep_status": 0,
+                "vol_status": 3,
+                "updated_at": "2024-01-02T00:00:00+08:00",
+                "private": true,
+                "subject": null
+            }
+        ],
+        "characters": [],
+        "persons": [],
+        "episodes": []
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_archive_json_roundtrip() {
+    let json = archive().to_json().unwrap();
+    let parsed = CollectionArchive::from_json(&json).unwrap();
+    assert_eq!(parsed.version, ARCHIVE_VERSION);
+    assert_eq!(parsed.subjects.len(), 2);
+
+    let newer = json.replacen(
+        &format!("\"version\": {ARCHIVE_VERSION}"),
+        &format!("\"version\": {}", ARCHIVE_VERSION + 1),
+        1,
+    );
+    assert!(CollectionArchive::from_json(&newer).is_err());
+}
+
+#[test]
+fn test_archive_to_csv() {
+    let csv = archive().to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[1],
+        "1,2,Title ]]> One,标题,done,9,12,0,TV 原创,\"好看, \"\"推荐\"\"\",false,2024-01-01T00:00:00+08:00"
+    );
+    assert_eq!(
+        lines[2],
+        "2,1,,,wish,0,0,3,,,true,2024-01-02T00:00:00+08:00"
+    );
+}
+
+#[test]
+fn test_archive_to_mal_xml() {
+    let xml = archive().to_mal_xml(&HashMap::from([(1, 5114)]));
+    assert!(xml.contains("<user_total_anime>1</user_total_anime>"));
+    assert!(xml.contains("<series_animedb_id>5114</series_animedb_id>"));
+    assert!(xml.contains("<series_title><![CDATA[Title ]]]]><![CDATA[> One]]></series_title>"));
+    assert!(xml.contains("<my_status>Completed</my_status>"));
+    assert!(xml.contains("<my_score>9</my_score>"));
+    assert_eq!(xml.matches("<anime>").count(), 1);
+}
+
+#[tokio::test]
+async fn test_export_collections() {
+    let client = BangumiClient::default();
+    let result = client.export_collections("sai", false).await;
+    assert!(result.is_ok(), "导出收藏失败: {:?}", result.err());
+}
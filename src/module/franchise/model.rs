@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::module::subject::model::SubjectType;
+
+/// 系列作品中条目的定位
+///
+/// 用于区分主线剧情与番外、总集篇等衍生内容
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FranchiseRole {
+    /// 主线剧情（正篇、前传、续集）
+    Main,
+    /// 衍生内容（番外篇、总集篇）
+    Side,
+}
+
+/// 系列作品中的单个条目
+///
+/// 记录条目的基本信息及其在系列中的定位
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FranchiseEntry {
+    /// 条目ID
+    pub id: u32,
+    /// 条目类型
+    pub r#type: SubjectType,
+    /// 条目原名
+    pub name: String,
+    /// 条目中文名
+    pub name_cn: String,
+    /// 条目日期（格式通常为YYYY-MM-DD，可能为空）
+    pub date: Option<String>,
+    /// 条目在系列中的定位
+    pub role: FranchiseRole,
+}
+
+/// 系列作品中两个条目之间的关联
+///
+/// 对应 `get_subject_subjects` 返回的一条关系记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FranchiseLink {
+    /// 关系的发起条目ID
+    pub from: u32,
+    /// 关系的目标条目ID
+    pub to: u32,
+    /// 关系描述（如"续集"、"番外篇"）
+    pub relation: String,
+}
+
+/// 按观看顺序整理后的系列作品
+///
+/// 主线与衍生内容分别按日期排序
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Franchise {
+    /// 解析起点的条目ID
+    pub root_id: u32,
+    /// 主线条目（按日期排序）
+    pub main: Vec<FranchiseEntry>,
+    /// 衍生条目（按日期排序）
+    pub side: Vec<FranchiseEntry>,
+}
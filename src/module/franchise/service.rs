@@ -0,0 +1,211 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::Result;
+
+use crate::{
+    common::model::BangumiClient,
+    module::subject::model::{Subject, SubjectRelation},
+};
+
+use super::model::{Franchise, FranchiseEntry, FranchiseLink, FranchiseRole};
+
+/// 判断条目关系是否属于系列关系
+///
+/// 仅跟随前传、续集、总集篇、番外篇、主线故事
+fn is_franchise_relation(relation: &SubjectRelation) -> bool {
+    matches!(
+        relation,
+        SubjectRelation::Prequel
+            | SubjectRelation::Sequel
+            | SubjectRelation::Summary
+            | SubjectRelation::SideStory
+            | SubjectRelation::ParentStory
+    )
+}
+
+/// 由条目详情构建系列条目（定位在解析时确定）
+fn franchise_entry(subject: &Subject) -> FranchiseEntry {
+    FranchiseEntry {
+        id: subject.id,
+        r#type: subject.r#type.clone(),
+        name: subject.name.clone(),
+        name_cn: subject.name_cn.clone(),
+        date: subject.date.clone(),
+        role: FranchiseRole::Main,
+    }
+}
+
+/// 整理系列作品的观看顺序
+///
+/// 根据条目之间的关系判定主线与衍生内容，并分别按日期排序。
+/// 关系图中允许出现环路以及相互矛盾的关系描述：
+/// - 被标记为"番外篇"/"总集篇"，或自身指向"主线故事"的条目记为衍生的票数
+/// - 被标记为"主线故事"，或自身拥有"番外篇"/"总集篇"的条目记为主线的票数
+/// - 票数持平的条目沿前传/续集关系继承相邻条目的定位，主线优先
+///
+/// # 参数
+/// - `root_id`: 解析起点的条目ID
+/// - `entries`: 系列中的全部条目（`role` 字段会被重新计算）
+/// - `links`: 条目之间的关系记录
+///
+/// # 返回
+/// 返回按日期排序、区分主线与衍生内容的系列作品
+pub fn resolve_franchise(
+    root_id: u32,
+    entries: Vec<FranchiseEntry>,
+    links: &[FranchiseLink],
+) -> Franchise {
+    let ids: HashSet<u32> = entries.iter().map(|entry| entry.id).collect();
+
+    // 统计每个条目的主线/衍生票数
+    let mut votes: HashMap<u32, i32> = HashMap::new();
+    // 前传/续集关系构成的无向邻接表
+    let mut neighbours: HashMap<u32, Vec<u32>> = HashMap::new();
+
+    for link in links {
+        if !ids.contains(&link.from) || !ids.contains(&link.to) || link.from == link.to {
+            continue;
+        }
+        match SubjectRelation::from_label(&link.relation) {
+            SubjectRelation::SideStory | SubjectRelation::Summary => {
+                *votes.entry(link.to).or_default() -= 1;
+                *votes.entry(link.from).or_default() += 1;
+            }
+            SubjectRelation::ParentStory => {
+                *votes.entry(link.to).or_default() += 1;
+                *votes.entry(link.from).or_default() -= 1;
+            }
+            SubjectRelation::Prequel | SubjectRelation::Sequel => {
+                neighbours.entry(link.from).or_default().push(link.to);
+                neighbours.entry(link.to).or_default().push(link.from);
+            }
+            _ => {}
+        }
+    }
+
+    let mut roles: HashMap<u32, FranchiseRole> = HashMap::new();
+    for (&id, &vote) in &votes {
+        match vote.cmp(&0) {
+            std::cmp::Ordering::Greater => {
+                roles.insert(id, FranchiseRole::Main);
+            }
+            std::cmp::Ordering::Less => {
+                roles.insert(id, FranchiseRole::Side);
+            }
+            std::cmp::Ordering::Equal => {}
+        }
+    }
+
+    // 未定位的条目沿前传/续集关系继承定位，直到不再变化
+    loop {
+        let mut changed = false;
+        for &id in &ids {
+            if roles.contains_key(&id) {
+                continue;
+            }
+            let adjacent: Vec<&FranchiseRole> = neighbours
+                .get(&id)
+                .map(|list| list.iter().filter_map(|n| roles.get(n)).collect())
+                .unwrap_or_default();
+            let role = if adjacent.contains(&&FranchiseRole::Main) {
+                Some(FranchiseRole::Main)
+            } else if adjacent.contains(&&FranchiseRole::Side) {
+                Some(FranchiseRole::Side)
+            } else {
+                None
+            };
+            if let Some(role) = role {
+                roles.insert(id, role);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut main = Vec::new();
+    let mut side = Vec::new();
+    for mut entry in entries {
+        entry.role = roles.remove(&entry.id).unwrap_or(FranchiseRole::Main);
+        match entry.role {
+            FranchiseRole::Main => main.push(entry),
+            FranchiseRole::Side => side.push(entry),
+        }
+    }
+
+    // 按日期排序，缺失日期的条目排在最后，日期相同时按ID排序
+    let order = |a: &FranchiseEntry, b: &FranchiseEntry| {
+        let date_a = a.date.as_deref().filter(|date| !date.is_empty());
+        let date_b = b.date.as_deref().filter(|date| !date.is_empty());
+        match (date_a, date_b) {
+            (Some(x), Some(y)) => x.cmp(y),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+        .then(a.id.cmp(&b.id))
+    };
+    main.sort_by(order);
+    side.sort_by(order);
+
+    Franchise {
+        root_id,
+        main,
+        side,
+    }
+}
+
+impl BangumiClient {
+    /// 解析条目所属的系列作品
+    ///
+    /// 从指定条目出发，沿前传、续集、总集篇、番外篇、主线故事关系遍历同类型的关联条目，
+    /// 并整理为按日期排序的观看顺序。遍历过程会跳过已访问的条目，因此可以处理环状关系
+    ///
+    /// # 参数
+    /// - `subject_id`: 起点条目ID
+    /// - `max_entries`: 可选，系列条目数量上限（用于限制超大系列的请求次数）
+    ///
+    /// # 返回
+    /// 返回区分主线与衍生内容的系列作品
+    pub async fn get_franchise(
+        &self,
+        subject_id: u32,
+        max_entries: Option<usize>,
+    ) -> Result<Franchise> {
+        let root = self.get_subject(subject_id).await?;
+
+        let mut entries = vec![franchise_entry(&root)];
+        let mut links = Vec::new();
+        let mut visited = HashSet::from([subject_id]);
+        let mut queue = VecDeque::from([subject_id]);
+
+        while let Some(current_id) = queue.pop_front() {
+            for related in self.get_subject_subjects(current_id).await? {
+                // 仅跟随同类型条目的系列关系
+                if related.r#type != root.r#type || !is_franchise_relation(&related.relation_type())
+                {
+                    continue;
+                }
+                links.push(FranchiseLink {
+                    from: current_id,
+                    to: related.id,
+                    relation: related.relation,
+                });
+
+                if visited.contains(&related.id)
+                    || max_entries.is_some_and(|max| entries.len() >= max)
+                {
+                    continue;
+                }
+                visited.insert(related.id);
+
+                let subject = self.get_subject(related.id).await?;
+                entries.push(franchise_entry(&subject));
+                queue.push_back(related.id);
+            }
+        }
+
+        Ok(resolve_franchise(subject_id, entries, &links))
+    }
+}
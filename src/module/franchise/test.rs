@@ -0,0 +1,103 @@
+use crate::{
+    common::model::BangumiClient,
+    module::{
+        franchise::{
+            model::{FranchiseEntry, FranchiseLink, FranchiseRole},
+            service::resolve_franchise,
+        },
+        subject::model::SubjectType,
+    },
+};
+
+fn entry(id: u32, date: Option<&str>) -> FranchiseEntry {
+    FranchiseEntry {
+        id,
+        r#type: SubjectType::Anime,
+        name: format!("subject {id}"),
+        name_cn: String::new(),
+        date: date.map(|date| date.to_string()),
+        role: FranchiseRole::Main,
+    }
+}
+
+fn link(from: u32, to: u32, relation: &str) -> FranchiseLink {
+    FranchiseLink {
+        from,
+        to,
+        relation: relation.to_string(),
+    }
+}
+
+#[test]
+fn test_resolve_franchise_orders_by_date() {
+    let entries = vec![
+        entry(3, Some("2012-01-01")),
+        entry(1, Some("2008-04-01")),
+        entry(2, Some("2010-07-01")),
+        entry(4, Some("2011-05-01")),
+    ];
+    let links = vec![
+        link(1, 2, "续集"),
+        link(2, 1, "前传"),
+        link(2, 3, "续集"),
+        link(3, 2, "前传"),
+        link(2, 4, "番外篇"),
+        link(4, 2, "主线故事"),
+    ];
+    let franchise = resolve_franchise(2, entries, &links);
+
+    let main: Vec<u32> = franchise.main.iter().map(|e| e.id).collect();
+    let side: Vec<u32> = franchise.side.iter().map(|e| e.id).collect();
+    assert_eq!(main, vec![1, 2, 3]);
+    assert_eq!(side, vec![4]);
+    assert_eq!(franchise.side[0].role, FranchiseRole::Side);
+}
+
+#[test]
+fn test_resolve_franchise_handles_cycles_and_missing_dates() {
+    let entries = vec![
+        entry(1, None),
+        entry(2, Some("")),
+        entry(3, Some("2001-01-01")),
+    ];
+    let links = vec![link(1, 2, "续集"), link(2, 3, "续集"), link(3, 1, "续集")];
+    let franchise = resolve_franchise(1, entries, &links);
+
+    let main: Vec<u32> = franchise.main.iter().map(|e| e.id).collect();
+    assert_eq!(main, vec![3, 1, 2]);
+    assert!(franchise.side.is_empty());
+}
+
+#[test]
+fn test_resolve_franchise_inconsistent_labels() {
+    // 2 同时被标记为番外篇与主线故事，票数持平后沿续集关系继承主线定位
+    // 5 是番外篇 4 的续集，继承衍生定位
+    let entries = vec![
+        entry(1, Some("2000-01-01")),
+        entry(2, Some("2001-01-01")),
+        entry(3, Some("2002-01-01")),
+        entry(4, Some("2003-01-01")),
+        entry(5, Some("2004-01-01")),
+    ];
+    let links = vec![
+        link(1, 2, " 续作 "),
+        link(1, 2, "番外篇"),
+        link(3, 2, "主线故事"),
+        link(1, 4, "外传"),
+        link(4, 5, "续集"),
+        link(1, 5, "角色出演"),
+    ];
+    let franchise = resolve_franchise(1, entries, &links);
+
+    let main: Vec<u32> = franchise.main.iter().map(|e| e.id).collect();
+    let side: Vec<u32> = franchise.side.iter().map(|e| e.id).collect();
+    assert_eq!(main, vec![1, 2]);
+    assert_eq!(side, vec![3, 4, 5]);
+}
+
+#[tokio::test]
+async fn test_get_franchise() {
+    let client = BangumiClient::default();
+    let result = client.get_franchise(1024, Some(10)).await;
+    assert!(result.is_ok(), "解析系列作品失败: {:?}", result.err());
+}
@@ -0,0 +1,6 @@
+/// GraphQL接口（需启用`graphql`特性）
+pub mod model;
+pub mod service;
+
+#[cfg(all(test, any(feature = "reqwest", feature = "ureq")))]
+pub mod test;
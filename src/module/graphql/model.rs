@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+
+#[cfg(feature = "store")]
+use crate::module::store::model::CachedClient;
+use crate::{
+    common::model::BangumiClient,
+    module::{
+        character::model::{Character, CharacterPerson},
+        collection::model::CollectionSubject,
+        episode::model::Episode,
+        person::model::{PersonDetail, PersonSubject},
+        subject::model::{Subject, SubjectCharacter, SubjectPerson, SubjectSubject},
+        user::model::UserPublic,
+    },
+};
+
+/// GraphQL模式
+pub type BangumiSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+/// 数据加载器
+///
+/// 为各类数据实现`async_graphql::dataloader::Loader`，同一轮解析中请求的ID
+/// 会被合并去重，每个ID仍单独请求一次API（Bangumi没有批量查询接口），
+/// 避免嵌套查询时重复请求同一实体
+pub struct BangumiLoader {
+    /// API客户端
+    pub client: Arc<BangumiClient>,
+    /// 可选的本地镜像，存在时条目、人物、角色、剧集及条目关联列表优先从镜像读取
+    #[cfg(feature = "store")]
+    pub cache: Option<Arc<CachedClient>>,
+}
+
+/// 条目详情的加载键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubjectKey(pub u32);
+
+/// 条目关联人物的加载键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubjectPersonsKey(pub u32);
+
+/// 条目关联角色的加载键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubjectCharactersKey(pub u32);
+
+/// 条目关联条目的加载键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubjectRelationsKey(pub u32);
+
+/// 条目全部剧集的加载键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EpisodesKey(pub u32);
+
+/// 角色详情的加载键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CharacterKey(pub u32);
+
+/// 角色关联人物的加载键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CharacterPersonsKey(pub u32);
+
+/// 人物详情的加载键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PersonKey(pub u32);
+
+/// 人物关联条目的加载键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PersonSubjectsKey(pub u32);
+
+/// 查询根
+pub struct Query;
+
+/// 条目节点
+pub struct SubjectNode(pub Subject);
+
+/// 条目标签节点
+pub struct TagNode {
+    /// 标签名称
+    pub name: String,
+    /// 标记人数
+    pub count: u32,
+}
+
+/// 条目关联人物节点
+pub struct SubjectPersonNode(pub SubjectPerson);
+
+/// 条目关联角色节点
+pub struct SubjectCharacterNode(pub SubjectCharacter);
+
+/// 关联条目节点
+pub struct RelatedSubjectNode(pub SubjectSubject);
+
+/// 剧集节点
+pub struct EpisodeNode(pub Episode);
+
+/// 角色节点
+pub struct CharacterNode(pub Character);
+
+/// 角色关联人物节点
+pub struct CharacterPersonNode(pub CharacterPerson);
+
+/// 人物节点
+pub struct PersonNode(pub PersonDetail);
+
+/// 人物关联条目节点
+pub struct PersonSubjectNode(pub PersonSubject);
+
+/// 用户节点
+pub struct UserNode(pub UserPublic);
+
+/// 用户收藏节点
+pub struct CollectionNode(pub CollectionSubject);
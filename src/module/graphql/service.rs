@@ -0,0 +1,775 @@
+use std::{collections::HashMap, convert::Infallible, future::Future, hash::Hash, sync::Arc};
+
+use anyhow::Result;
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Object, Schema,
+    dataloader::{DataLoader, Loader, NoCache},
+    futures_util::future::join_all,
+};
+use serde::Serialize;
+
+#[cfg(feature = "store")]
+use crate::module::store::model::CachedClient;
+use crate::{
+    common::model::{BangumiClient, PAGE_LIMIT},
+    module::{
+        character::model::{Character, CharacterPerson},
+        collection::model::CollectionType,
+        episode::model::Episode,
+        person::model::{PersonDetail, PersonSubject},
+        subject::model::{Subject, SubjectCharacter, SubjectPerson, SubjectSubject, SubjectType},
+        user::model::UserPublic,
+    },
+};
+
+use super::model::{
+    BangumiLoader, BangumiSchema, CharacterKey, CharacterNode, CharacterPersonNode,
+    CharacterPersonsKey, CollectionNode, EpisodeNode, EpisodesKey, PersonKey, PersonNode,
+    PersonSubjectNode, PersonSubjectsKey, Query, RelatedSubjectNode, SubjectCharacterNode,
+    SubjectCharactersKey, SubjectKey, SubjectNode, SubjectPersonNode, SubjectPersonsKey,
+    SubjectRelationsKey, TagNode, UserNode,
+};
+
+/// 单次`subjects`查询允许的最大ID数量
+const MAX_IDS: usize = 100;
+
+/// 加载器使用的数据加载器类型
+///
+/// 不跨请求缓存，只在同一轮解析中合并去重
+type BangumiDataLoader = DataLoader<BangumiLoader, NoCache>;
+
+/// 创建GraphQL模式
+///
+/// 模式中的所有嵌套字段都通过 [`BangumiLoader`] 加载：同一轮解析中的相同ID只请求一次，
+/// 不同ID仍逐个请求API。客户端会启用 [`BangumiClient::with_single_flight`]，
+/// 并发查询之间相同的请求也只发出一次
+pub fn schema(client: BangumiClient) -> BangumiSchema {
+    build_schema(BangumiLoader {
+        client: Arc::new(client.with_single_flight()),
+        #[cfg(feature = "store")]
+        cache: None,
+    })
+}
+
+/// 创建优先读取本地镜像的GraphQL模式（需启用`store`特性）
+///
+/// 条目、人物、角色、剧集及条目关联列表经 [`CachedClient`] 读取，
+/// 镜像中已有的数据不再请求API；其余字段与 [`schema`] 相同
+#[cfg(feature = "store")]
+pub fn cached_schema(client: CachedClient) -> BangumiSchema {
+    let api = BangumiClient {
+        base_path: client.client.base_path.clone(),
+        user_agent: client.client.user_agent.clone(),
+        transport: client.client.transport.clone(),
+        access_token: client.client.access_token.clone(),
+    };
+    build_schema(BangumiLoader {
+        client: Arc::new(api),
+        cache: Some(Arc::new(client)),
+    })
+}
+
+fn build_schema(loader: BangumiLoader) -> BangumiSchema {
+    let loader = DataLoader::with_cache(loader, tokio::spawn, NoCache);
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(loader)
+        .finish()
+}
+
+/// 并发请求每个键对应的数据
+///
+/// 单个键请求失败时只影响该键，错误信息随结果一同返回
+async fn load_each<K, V, F, Fut>(keys: &[K], fetch: F) -> HashMap<K, Result<V, String>>
+where
+    K: Copy + Eq + Hash,
+    F: Fn(K) -> Fut,
+    Fut: Future<Output = Result<V>>,
+{
+    let fetch = &fetch;
+    join_all(keys.iter().map(|&key| async move {
+        let value = fetch(key).await.map_err(|error| format!("{error:#}"));
+        (key, value)
+    }))
+    .await
+    .into_iter()
+    .collect()
+}
+
+macro_rules! impl_loader {
+    ($key:ident, $value:ty, |$client:ident, $id:ident| $fetch:expr) => {
+        impl Loader<$key> for BangumiLoader {
+            type Value = Result<$value, String>;
+            type Error = Infallible;
+
+            async fn load(&self, keys: &[$key]) -> Result<HashMap<$key, Self::Value>, Self::Error> {
+                let $client = &self.client;
+                Ok(load_each(keys, |$key($id)| $fetch).await)
+            }
+        }
+    };
+    // 启用`store`特性且配置了本地镜像时，经镜像读取
+    ($key:ident, $value:ty, |$client:ident, $id:ident| $fetch:expr, |$cache:ident| $cached:expr) => {
+        impl Loader<$key> for BangumiLoader {
+            type Value = Result<$value, String>;
+            type Error = Infallible;
+
+            async fn load(&self, keys: &[$key]) -> Result<HashMap<$key, Self::Value>, Self::Error> {
+                #[cfg(feature = "store")]
+                if let Some($cache) = &self.cache {
+                    return Ok(load_each(keys, |$key($id)| $cached).await);
+                }
+                let $client = &self.client;
+                Ok(load_each(keys, |$key($id)| $fetch).await)
+            }
+        }
+    };
+}
+
+impl_loader! { SubjectKey, Subject, |client, id| client.get_subject(id), |cache| cache.get_subject(id) }
+impl_loader! { SubjectPersonsKey, Vec<SubjectPerson>, |client, id| client.get_subject_persons(id), |cache| cache.get_subject_persons(id) }
+impl_loader! { SubjectCharactersKey, Vec<SubjectCharacter>, |client, id| client.get_subject_characters(id), |cache| cache.get_subject_characters(id) }
+impl_loader! { SubjectRelationsKey, Vec<SubjectSubject>, |client, id| client.get_subject_subjects(id), |cache| cache.get_subject_subjects(id) }
+impl_loader! { EpisodesKey, Vec<Episode>, |client, id| all_episodes(client, id), |cache| cache.get_subject_episodes(id) }
+impl_loader! { CharacterKey, Character, |client, id| client.get_character(id), |cache| cache.get_character(id) }
+impl_loader! { CharacterPersonsKey, Vec<CharacterPerson>, |client, id| client.get_character_persons(id) }
+impl_loader! { PersonKey, PersonDetail, |client, id| client.get_person(id), |cache| cache.get_person(id) }
+impl_loader! { PersonSubjectsKey, Vec<PersonSubject>, |client, id| client.get_person_subjects(id) }
+
+/// 分页获取条目的全部剧集
+async fn all_episodes(client: &BangumiClient, subject_id: u32) -> Result<Vec<Episode>> {
+    BangumiClient::fetch_all(|limit, offset| {
+        client.get_episodes(subject_id, None, Some(limit), Some(offset))
+    })
+    .await
+}
+
+/// 通过数据加载器获取单个键的数据
+async fn load<K, V>(ctx: &Context<'_>, key: K) -> async_graphql::Result<V>
+where
+    K: Copy + Eq + Hash + Send + Sync + 'static,
+    V: Send + Sync + Clone + 'static,
+    BangumiLoader: Loader<K, Value = Result<V, String>, Error = Infallible>,
+{
+    let loader = ctx.data_unchecked::<BangumiDataLoader>();
+    match loader.load_one(key).await {
+        Ok(Some(Ok(value))) => Ok(value),
+        Ok(Some(Err(error))) => Err(error.into()),
+        Ok(None) => Err("数据加载失败".into()),
+        Err(error) => match error {},
+    }
+}
+
+/// 批量获取多个键的数据，结果顺序与键的顺序一致
+async fn load_many<K, V>(ctx: &Context<'_>, keys: Vec<K>) -> async_graphql::Result<Vec<V>>
+where
+    K: Copy + Eq + Hash + Send + Sync + 'static,
+    V: Send + Sync + Clone + 'static,
+    BangumiLoader: Loader<K, Value = Result<V, String>, Error = Infallible>,
+{
+    let loader = ctx.data_unchecked::<BangumiDataLoader>();
+    let values = match loader.load_many(keys.iter().copied()).await {
+        Ok(values) => values,
+        Err(error) => match error {},
+    };
+    let mut result = Vec::with_capacity(keys.len());
+    for key in keys {
+        match values.get(&key) {
+            Some(Ok(value)) => result.push(value.clone()),
+            Some(Err(error)) => return Err(error.clone().into()),
+            None => return Err("数据加载失败".into()),
+        }
+    }
+    Ok(result)
+}
+
+/// 将枚举转换为API中使用的数值或字符串
+fn code<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+/// 将API中使用的数值转换为枚举
+fn from_code<T: serde::de::DeserializeOwned>(
+    name: &str,
+    value: Option<u8>,
+) -> async_graphql::Result<Option<T>> {
+    match value {
+        Some(value) => match serde_json::from_value(value.into()) {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(format!("无效的{name}: {value}").into()),
+        },
+        None => Ok(None),
+    }
+}
+
+/// 将枚举转换为数值
+fn int<T: Serialize>(value: &T) -> u8 {
+    code(value).as_u64().unwrap_or_default() as u8
+}
+
+/// 将职业列表转换为字符串
+fn careers<T: Serialize>(values: &[T]) -> Vec<String> {
+    values
+        .iter()
+        .filter_map(|value| code(value).as_str().map(str::to_string))
+        .collect()
+}
+
+#[Object]
+impl Query {
+    /// 获取条目
+    async fn subject(&self, ctx: &Context<'_>, id: u32) -> async_graphql::Result<SubjectNode> {
+        load(ctx, SubjectKey(id)).await.map(SubjectNode)
+    }
+
+    /// 批量获取条目，结果顺序与ID顺序一致
+    async fn subjects(
+        &self,
+        ctx: &Context<'_>,
+        ids: Vec<u32>,
+    ) -> async_graphql::Result<Vec<SubjectNode>> {
+        if ids.len() > MAX_IDS {
+            return Err(format!("单次最多查询{MAX_IDS}个条目").into());
+        }
+        let keys = ids.into_iter().map(SubjectKey).collect();
+        let subjects = load_many(ctx, keys).await?;
+        Ok(subjects.into_iter().map(SubjectNode).collect())
+    }
+
+    /// 获取角色
+    async fn character(&self, ctx: &Context<'_>, id: u32) -> async_graphql::Result<CharacterNode> {
+        load(ctx, CharacterKey(id)).await.map(CharacterNode)
+    }
+
+    /// 获取人物
+    async fn person(&self, ctx: &Context<'_>, id: u32) -> async_graphql::Result<PersonNode> {
+        load(ctx, PersonKey(id)).await.map(PersonNode)
+    }
+
+    /// 获取用户
+    async fn user(&self, ctx: &Context<'_>, username: String) -> async_graphql::Result<UserNode> {
+        let loader = ctx.data_unchecked::<BangumiDataLoader>().loader();
+        let user = loader.client.get_user(&username).await?;
+        Ok(UserNode(user))
+    }
+
+    /// 获取当前登录的用户，需要客户端携带访问令牌
+    async fn me(&self, ctx: &Context<'_>) -> async_graphql::Result<UserNode> {
+        let loader = ctx.data_unchecked::<BangumiDataLoader>().loader();
+        let me = loader.client.get_me().await?;
+        Ok(UserNode(UserPublic {
+            id: me.id,
+            url: me.url,
+            username: me.username,
+            nickname: me.nickname,
+            user_group: me.user_group,
+            avatar: me.avatar,
+            sign: me.sign,
+        }))
+    }
+}
+
+/// 条目
+#[Object(name = "Subject")]
+impl SubjectNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    /// 条目类型（1书籍、2动画、3音乐、4游戏、6三次元）
+    async fn r#type(&self) -> u8 {
+        int(&self.0.r#type)
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn name_cn(&self) -> &str {
+        &self.0.name_cn
+    }
+
+    async fn summary(&self) -> &str {
+        &self.0.summary
+    }
+
+    async fn nsfw(&self) -> bool {
+        self.0.nsfw
+    }
+
+    async fn date(&self) -> Option<&str> {
+        self.0.date.as_deref()
+    }
+
+    async fn platform(&self) -> &str {
+        &self.0.platform
+    }
+
+    async fn eps(&self) -> u32 {
+        self.0.eps
+    }
+
+    async fn volumes(&self) -> u32 {
+        self.0.volumes
+    }
+
+    async fn score(&self) -> f64 {
+        self.0.rating.score
+    }
+
+    async fn rank(&self) -> u32 {
+        self.0.rating.rank
+    }
+
+    /// 评分人数
+    async fn rating_total(&self) -> u32 {
+        self.0.rating.total
+    }
+
+    /// 大尺寸封面地址
+    async fn image(&self) -> &str {
+        &self.0.images.large
+    }
+
+    async fn tags(&self) -> Vec<TagNode> {
+        self.0
+            .tags
+            .iter()
+            .map(|tag| TagNode {
+                name: tag.name.clone(),
+                count: tag.count,
+            })
+            .collect()
+    }
+
+    /// 关联人物（制作人员）
+    async fn persons(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<SubjectPersonNode>> {
+        let persons = load(ctx, SubjectPersonsKey(self.0.id)).await?;
+        Ok(persons.into_iter().map(SubjectPersonNode).collect())
+    }
+
+    /// 关联角色
+    async fn characters(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<SubjectCharacterNode>> {
+        let characters = load(ctx, SubjectCharactersKey(self.0.id)).await?;
+        Ok(characters.into_iter().map(SubjectCharacterNode).collect())
+    }
+
+    /// 关联条目（前传、续集等）
+    async fn related_subjects(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<RelatedSubjectNode>> {
+        let subjects = load(ctx, SubjectRelationsKey(self.0.id)).await?;
+        Ok(subjects.into_iter().map(RelatedSubjectNode).collect())
+    }
+
+    /// 剧集，可按类型（0正片、1SP、2OP、3ED）筛选
+    async fn episodes(
+        &self,
+        ctx: &Context<'_>,
+        r#type: Option<u8>,
+    ) -> async_graphql::Result<Vec<EpisodeNode>> {
+        let episodes = load(ctx, EpisodesKey(self.0.id)).await?;
+        Ok(episodes
+            .into_iter()
+            .filter(|episode| r#type.is_none_or(|kind| int(&episode.r#type) == kind))
+            .map(EpisodeNode)
+            .collect())
+    }
+}
+
+/// 条目标签
+#[Object(name = "Tag")]
+impl TagNode {
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn count(&self) -> u32 {
+        self.count
+    }
+}
+
+/// 条目关联人物
+#[Object(name = "SubjectPerson")]
+impl SubjectPersonNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// 在条目中担任的职位
+    async fn relation(&self) -> &str {
+        &self.0.relation
+    }
+
+    /// 参与的章节
+    async fn eps(&self) -> &str {
+        &self.0.eps
+    }
+
+    async fn careers(&self) -> Vec<String> {
+        careers(&self.0.career)
+    }
+
+    /// 人物详情
+    async fn person(&self, ctx: &Context<'_>) -> async_graphql::Result<PersonNode> {
+        load(ctx, PersonKey(self.0.id)).await.map(PersonNode)
+    }
+}
+
+/// 条目关联角色
+#[Object(name = "SubjectCharacter")]
+impl SubjectCharacterNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// 角色定位（主角、配角等）
+    async fn relation(&self) -> &str {
+        &self.0.relation
+    }
+
+    /// 角色详情
+    async fn character(&self, ctx: &Context<'_>) -> async_graphql::Result<CharacterNode> {
+        load(ctx, CharacterKey(self.0.id)).await.map(CharacterNode)
+    }
+
+    /// 声优/演员详情
+    async fn actors(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<PersonNode>> {
+        let keys = self
+            .0
+            .actors
+            .iter()
+            .map(|actor| PersonKey(actor.id))
+            .collect();
+        let persons = load_many(ctx, keys).await?;
+        Ok(persons.into_iter().map(PersonNode).collect())
+    }
+}
+
+/// 关联条目
+#[Object(name = "RelatedSubject")]
+impl RelatedSubjectNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    async fn r#type(&self) -> u8 {
+        int(&self.0.r#type)
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn name_cn(&self) -> &str {
+        &self.0.name_cn
+    }
+
+    /// 关联关系（前传、续集等）
+    async fn relation(&self) -> &str {
+        &self.0.relation
+    }
+
+    /// 条目详情
+    async fn subject(&self, ctx: &Context<'_>) -> async_graphql::Result<SubjectNode> {
+        load(ctx, SubjectKey(self.0.id)).await.map(SubjectNode)
+    }
+}
+
+/// 剧集
+#[Object(name = "Episode")]
+impl EpisodeNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    /// 剧集类型（0正片、1SP、2OP、3ED）
+    async fn r#type(&self) -> u8 {
+        int(&self.0.r#type)
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn name_cn(&self) -> &str {
+        &self.0.name_cn
+    }
+
+    async fn sort(&self) -> f64 {
+        self.0.sort
+    }
+
+    async fn ep(&self) -> f64 {
+        self.0.ep
+    }
+
+    async fn airdate(&self) -> &str {
+        &self.0.airdate
+    }
+
+    async fn duration(&self) -> &str {
+        &self.0.duration
+    }
+}
+
+/// 角色
+#[Object(name = "Character")]
+impl CharacterNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn summary(&self) -> &str {
+        &self.0.summary
+    }
+
+    async fn gender(&self) -> &str {
+        &self.0.gender
+    }
+
+    async fn nsfw(&self) -> bool {
+        self.0.nsfw
+    }
+
+    /// 大尺寸图片地址
+    async fn image(&self) -> &str {
+        &self.0.images.large
+    }
+
+    /// 关联人物（声优等），每个出演条目各一条
+    async fn persons(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<CharacterPersonNode>> {
+        let persons = load(ctx, CharacterPersonsKey(self.0.id)).await?;
+        Ok(persons.into_iter().map(CharacterPersonNode).collect())
+    }
+}
+
+/// 角色关联人物
+#[Object(name = "CharacterPerson")]
+impl CharacterPersonNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn staff(&self) -> &str {
+        &self.0.staff
+    }
+
+    async fn subject_id(&self) -> u32 {
+        self.0.subject_id
+    }
+
+    async fn subject_name(&self) -> &str {
+        &self.0.subject_name
+    }
+
+    async fn subject_name_cn(&self) -> &str {
+        &self.0.subject_name_cn
+    }
+
+    /// 人物详情
+    async fn person(&self, ctx: &Context<'_>) -> async_graphql::Result<PersonNode> {
+        load(ctx, PersonKey(self.0.id)).await.map(PersonNode)
+    }
+
+    /// 出演条目详情
+    async fn subject(&self, ctx: &Context<'_>) -> async_graphql::Result<SubjectNode> {
+        load(ctx, SubjectKey(self.0.subject_id))
+            .await
+            .map(SubjectNode)
+    }
+}
+
+/// 人物
+#[Object(name = "Person")]
+impl PersonNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn summary(&self) -> &str {
+        &self.0.summary
+    }
+
+    async fn gender(&self) -> Option<&str> {
+        self.0.gender.as_deref()
+    }
+
+    async fn careers(&self) -> Vec<String> {
+        careers(&self.0.career)
+    }
+
+    /// 大尺寸图片地址
+    async fn image(&self) -> &str {
+        &self.0.images.large
+    }
+
+    /// 参与的条目
+    async fn subjects(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<PersonSubjectNode>> {
+        let subjects = load(ctx, PersonSubjectsKey(self.0.id)).await?;
+        Ok(subjects.into_iter().map(PersonSubjectNode).collect())
+    }
+}
+
+/// 人物参与的条目
+#[Object(name = "PersonSubject")]
+impl PersonSubjectNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    async fn r#type(&self) -> u8 {
+        int(&self.0.r#type)
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn name_cn(&self) -> &str {
+        &self.0.name_cn
+    }
+
+    /// 在条目中担任的职位
+    async fn staff(&self) -> &str {
+        &self.0.staff
+    }
+
+    /// 条目详情
+    async fn subject(&self, ctx: &Context<'_>) -> async_graphql::Result<SubjectNode> {
+        load(ctx, SubjectKey(self.0.id)).await.map(SubjectNode)
+    }
+}
+
+/// 用户
+#[Object(name = "User")]
+impl UserNode {
+    async fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    async fn username(&self) -> &str {
+        &self.0.username
+    }
+
+    async fn nickname(&self) -> &str {
+        &self.0.nickname
+    }
+
+    async fn sign(&self) -> &str {
+        &self.0.sign
+    }
+
+    /// 大尺寸头像地址
+    async fn avatar(&self) -> &str {
+        &self.0.avatar.large
+    }
+
+    /// 条目收藏
+    ///
+    /// 可按条目类型（1书籍、2动画、3音乐、4游戏、6三次元）与
+    /// 收藏类型（1想看、2看过、3在看、4搁置、5抛弃）筛选
+    async fn collections(
+        &self,
+        ctx: &Context<'_>,
+        subject_type: Option<u8>,
+        r#type: Option<u8>,
+        #[graphql(default = 30)] limit: u32,
+        #[graphql(default = 0)] offset: u32,
+    ) -> async_graphql::Result<Vec<CollectionNode>> {
+        let subject_type = from_code::<SubjectType>("条目类型", subject_type)?;
+        let r#type = from_code::<CollectionType>("收藏类型", r#type)?;
+        let loader = ctx.data_unchecked::<BangumiDataLoader>().loader();
+        let page = loader
+            .client
+            .get_collection_subjects(
+                &self.0.username,
+                subject_type,
+                r#type,
+                Some(limit.min(PAGE_LIMIT)),
+                Some(offset),
+            )
+            .await?;
+        Ok(page
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .map(CollectionNode)
+            .collect())
+    }
+}
+
+/// 用户收藏的条目
+#[Object(name = "Collection")]
+impl CollectionNode {
+    async fn subject_id(&self) -> u32 {
+        self.0.subject_id
+    }
+
+    async fn subject_type(&self) -> u8 {
+        int(&self.0.subject_type)
+    }
+
+    /// 收藏类型（1想看、2看过、3在看、4搁置、5抛弃）
+    async fn r#type(&self) -> u8 {
+        int(&self.0.r#type)
+    }
+
+    async fn rate(&self) -> u32 {
+        self.0.rate
+    }
+
+    async fn ep_status(&self) -> u32 {
+        self.0.ep_status
+    }
+
+    async fn vol_status(&self) -> u32 {
+        self.0.vol_status
+    }
+
+    async fn comment(&self) -> Option<&str> {
+        self.0.comment.as_deref()
+    }
+
+    async fn tags(&self) -> &[String] {
+        &self.0.tags
+    }
+
+    async fn updated_at(&self) -> &str {
+        &self.0.updated_at
+    }
+
+    async fn private(&self) -> bool {
+        self.0.private
+    }
+
+    /// 条目详情
+    async fn subject(&self, ctx: &Context<'_>) -> async_graphql::Result<SubjectNode> {
+        load(ctx, SubjectKey(self.0.subject_id))
+            .await
+            .map(SubjectNode)
+    }
+}
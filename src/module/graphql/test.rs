@@ -0,0 +1,208 @@
+use crate::{
+    common::{
+        fixture::{self, Fixture},
+        mock::{MockRoute, MockServer},
+    },
+    module::graphql::service::schema,
+};
+
+fn actor_json(id: u32) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": format!("Person {id}"),
+        "type": 1,
+        "career": ["seiyu"],
+        "images": fixture::images(),
+        "short_summary": "",
+        "locked": false
+    })
+}
+
+fn person_json(id: u32) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": format!("Person {id}"),
+        "type": 1,
+        "career": ["seiyu", "artist"],
+        "images": fixture::images(),
+        "summary": "",
+        "locked": false,
+        "last_modified": "2024-01-01T00:00:00Z",
+        "infobox": [],
+        "gender": "female",
+        "blood_type": null,
+        "birth_year": null,
+        "birth_mon": null,
+        "birth_day": null,
+        "stat": {"comments": 0, "collects": 0}
+    })
+}
+
+fn character_json(id: u32, actors: &[u32]) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": format!("Character {id}"),
+        "type": 1,
+        "images": fixture::images(),
+        "relation": "主角",
+        "actors": actors.iter().map(|&actor| actor_json(actor)).collect::<Vec<_>>()
+    })
+}
+
+fn episode_json(id: u32, r#type: u8, sort: f64) -> Fixture {
+    fixture::episode(id, sort).with("type", r#type)
+}
+
+fn server() -> MockServer {
+    MockServer::start(vec![
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/1",
+            200,
+            fixture::subject(1)
+                .named("first", "")
+                .with("eps", 2)
+                .tags("tags", &["原创"])
+                .json(),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/2",
+            200,
+            fixture::subject(2).named("second", "").json(),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/1/characters",
+            200,
+            serde_json::json!([character_json(10, &[7]), character_json(11, &[7, 8])]),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/1/subjects",
+            200,
+            serde_json::json!([fixture::related(2, "续集").with("name", "second")]),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/episodes",
+            200,
+            serde_json::json!({
+                "total": 3,
+                "limit": 100,
+                "offset": 0,
+                "data": [episode_json(1, 0, 1.0), episode_json(2, 0, 2.0), episode_json(3, 1, 1.0)]
+            }),
+        ),
+        MockRoute::json("GET", "/v0/persons/7", 200, person_json(7)),
+        MockRoute::json("GET", "/v0/persons/8", 200, person_json(8)),
+    ])
+}
+
+fn count(server: &MockServer, path: &str) -> usize {
+    server
+        .requests()
+        .iter()
+        .filter(|request| request.path == path)
+        .count()
+}
+
+#[tokio::test]
+async fn test_nested_query_is_batched() {
+    let server = server();
+    let schema = schema(server.client());
+
+    let response = schema
+        .execute(
+            r#"{
+                subject(id: 1) {
+                    name
+                    tags { name }
+                    characters { name actors { name careers } }
+                    relatedSubjects { relation subject { name } }
+                    episodes(type: 0) { sort }
+                }
+            }"#,
+        )
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let subject = &data["subject"];
+    assert_eq!(subject["name"], "first");
+    assert_eq!(subject["tags"][0]["name"], "原创");
+    assert_eq!(subject["characters"][1]["actors"][1]["name"], "Person 8");
+    assert_eq!(
+        subject["characters"][0]["actors"][0]["careers"],
+        serde_json::json!(["seiyu", "artist"])
+    );
+    assert_eq!(subject["relatedSubjects"][0]["relation"], "续集");
+    assert_eq!(subject["relatedSubjects"][0]["subject"]["name"], "second");
+    assert_eq!(
+        subject["episodes"],
+        serde_json::json!([{"sort": 1.0}, {"sort": 2.0}])
+    );
+
+    // 两个角色共用的声优只请求一次
+    assert_eq!(count(&server, "/v0/persons/7"), 1);
+    assert_eq!(count(&server, "/v0/persons/8"), 1);
+    assert_eq!(count(&server, "/v0/subjects/1"), 1);
+}
+
+#[tokio::test]
+async fn test_subjects_dedup_and_errors() {
+    let server = server();
+    let schema = schema(server.client());
+
+    let response = schema
+        .execute("{ subjects(ids: [1, 2, 1]) { id name } }")
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let ids: Vec<u64> = data["subjects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|subject| subject["id"].as_u64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![1, 2, 1]);
+    assert_eq!(count(&server, "/v0/subjects/1"), 1);
+
+    // 单个实体请求失败时只影响对应字段
+    let response = schema
+        .execute("{ a: subject(id: 1) { name } b: subject(id: 3) { name } }")
+        .await;
+    assert_eq!(response.errors.len(), 1);
+    assert_eq!(
+        response.errors[0].path,
+        vec![async_graphql::PathSegment::Field("b".to_string())]
+    );
+}
+
+#[cfg(feature = "store")]
+#[tokio::test]
+async fn test_cached_schema() {
+    use crate::module::{
+        graphql::service::cached_schema,
+        store::model::{CachedClient, Store},
+    };
+
+    let server = server();
+    let client = CachedClient::new(server.client(), Store::open_in_memory().unwrap(), None);
+    let schema = cached_schema(client);
+
+    // 第二次查询从本地镜像读取，不再请求API
+    for _ in 0..2 {
+        let response = schema
+            .execute("{ subject(id: 1) { name relatedSubjects { subject { name } } } }")
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["subject"]["relatedSubjects"][0]["subject"]["name"],
+            "second"
+        );
+    }
+    assert_eq!(count(&server, "/v0/subjects/1"), 1);
+    assert_eq!(count(&server, "/v0/subjects/1/subjects"), 1);
+    assert_eq!(count(&server, "/v0/subjects/2"), 1);
+}
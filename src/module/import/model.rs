@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::module::collection::model::CollectionType;
+
+/// 导入数据的来源站点
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    /// MyAnimeList XML导出
+    Mal,
+    /// AniList JSON导出
+    AniList,
+}
+
+/// AniList用户设置的评分格式（`MediaListOptions.scoreFormat`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AniListScoreFormat {
+    /// 百分制（0-100）
+    #[serde(rename = "POINT_100")]
+    Point100,
+    /// 带一位小数的十分制（0-10.0）
+    #[serde(rename = "POINT_10_DECIMAL")]
+    Point10Decimal,
+    /// 十分制（0-10）
+    #[serde(rename = "POINT_10")]
+    Point10,
+    /// 五星制（0-5）
+    #[serde(rename = "POINT_5")]
+    Point5,
+    /// 三档表情（0-3）
+    #[serde(rename = "POINT_3")]
+    Point3,
+}
+
+/// 从其他站点导出文件中解析出的单条收藏
+///
+/// 已将状态、评分等字段转换为Bangumi的表示方式
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportEntry {
+    /// 来源站点
+    pub source: ImportSource,
+    /// 来源站点中的条目ID
+    pub source_id: u32,
+    /// MyAnimeList条目ID（可选，AniList条目可能未关联）
+    pub mal_id: Option<u32>,
+    /// 条目标题列表（按优先级排列，用于模糊匹配）
+    pub titles: Vec<String>,
+    /// 收藏类型
+    pub r#type: CollectionType,
+    /// 评分（0-10分，0表示未评分）
+    pub rate: u32,
+    /// 已观看集数
+    pub progress: u32,
+    /// 标签列表
+    pub tags: Vec<String>,
+    /// 评论（可选）
+    pub comment: Option<String>,
+}
+
+/// 单条收藏与Bangumi条目的匹配结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum MatchOutcome {
+    /// 唯一匹配到的条目
+    Matched {
+        /// Bangumi条目ID
+        subject_id: u32,
+        /// 匹配依据（"mapping"表示ID映射表，"title"表示标题搜索）
+        method: String,
+    },
+    /// 存在多个相近的候选条目，需要人工确认
+    Ambiguous {
+        /// 候选条目ID列表（按相似度降序）
+        candidates: Vec<u32>,
+    },
+    /// 未找到匹配的条目
+    Unmatched,
+}
+
+/// 导入计划中的单项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportItem {
+    /// 待导入的收藏
+    pub entry: ImportEntry,
+    /// 匹配结果
+    pub outcome: MatchOutcome,
+}
+
+/// 导入计划（试运行结果）
+///
+/// 在实际写入前列出每条收藏的匹配情况，便于检查未匹配或有歧义的条目
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportPlan {
+    /// 计划中的全部条目
+    pub items: Vec<ImportItem>,
+}
+
+/// 导入进度状态
+///
+/// 记录已成功写入的收藏，可序列化保存，用于中断后继续导入
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportState {
+    /// 已写入的收藏（来源站点与来源ID）
+    pub applied: BTreeSet<(ImportSource, u32)>,
+}
@@ -0,0 +1,515 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+
+use crate::{
+    common::model::BangumiClient,
+    module::{
+        collection::model::{
+            CollectionEpisodeType, CollectionEpisodesUpdate, CollectionSubjectUpdate,
+            CollectionType,
+        },
+        episode::model::EpisodeType,
+        subject::model::{Subject, SubjectSearch, SubjectSearchFilter, SubjectType},
+    },
+};
+
+use super::model::{
+    AniListScoreFormat, ImportEntry, ImportItem, ImportPlan, ImportSource, ImportState,
+    MatchOutcome,
+};
+
+/// 标题相似度达到该值才视为匹配
+const MATCH_THRESHOLD: f64 = 0.8;
+
+/// 最佳候选与次佳候选的相似度差距小于该值时视为有歧义
+const AMBIGUITY_MARGIN: f64 = 0.1;
+
+/// 标题搜索时获取的候选数量
+const SEARCH_LIMIT: u32 = 5;
+
+/// MyAnimeList XML导出文件的根节点
+#[derive(Debug, Deserialize)]
+struct MalExport {
+    #[serde(default)]
+    anime: Vec<MalAnime>,
+}
+
+/// MyAnimeList XML导出中的单条动画收藏
+#[derive(Debug, Deserialize)]
+struct MalAnime {
+    series_animedb_id: u32,
+    #[serde(default)]
+    series_title: String,
+    #[serde(default)]
+    my_watched_episodes: u32,
+    #[serde(default)]
+    my_score: u32,
+    #[serde(default)]
+    my_status: String,
+    #[serde(default)]
+    my_comments: String,
+    #[serde(default)]
+    my_tags: String,
+}
+
+/// AniList导出的收藏列表集合（GraphQL `MediaListCollection` 结构）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AniListCollection {
+    lists: Vec<AniListList>,
+    user: Option<AniListUser>,
+}
+
+/// 导出中附带的用户信息，仅用于读取评分格式
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AniListUser {
+    media_list_options: Option<AniListOptions>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AniListOptions {
+    score_format: Option<AniListScoreFormat>,
+}
+
+/// AniList导出外层的`data`包装
+#[derive(Debug, Deserialize)]
+struct AniListResponse {
+    data: AniListData,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AniListData {
+    media_list_collection: AniListCollection,
+}
+
+#[derive(Debug, Deserialize)]
+struct AniListList {
+    entries: Vec<AniListEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AniListEntry {
+    media_id: u32,
+    status: String,
+    #[serde(default)]
+    score: f64,
+    #[serde(default)]
+    progress: u32,
+    notes: Option<String>,
+    media: AniListMedia,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AniListMedia {
+    id_mal: Option<u32>,
+    title: AniListTitle,
+}
+
+#[derive(Debug, Deserialize)]
+struct AniListTitle {
+    romaji: Option<String>,
+    english: Option<String>,
+    native: Option<String>,
+}
+
+/// 解析MyAnimeList的动画XML导出文件
+///
+/// # 参数
+/// - `xml`: 导出文件内容
+///
+/// # 返回
+/// 返回转换为Bangumi表示方式的收藏列表，无法识别的状态按"想看"处理
+pub fn parse_mal_xml(xml: &str) -> Result<Vec<ImportEntry>> {
+    let export: MalExport = quick_xml::de::from_str(xml)?;
+
+    let entries = export
+        .anime
+        .into_iter()
+        .map(|anime| {
+            let r#type = match anime.my_status.trim() {
+                "Watching" | "1" => CollectionType::Doing,
+                "Completed" | "2" => CollectionType::Done,
+                "On-Hold" | "3" => CollectionType::OnHold,
+                "Dropped" | "4" => CollectionType::Dropped,
+                _ => CollectionType::Wish,
+            };
+            let tags = anime
+                .my_tags
+                .split(',')
+                .map(|tag| tag.trim().to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+            ImportEntry {
+                source: ImportSource::Mal,
+                source_id: anime.series_animedb_id,
+                mal_id: Some(anime.series_animedb_id),
+                titles: vec![anime.series_title],
+                r#type,
+                rate: anime.my_score.min(10),
+                progress: anime.my_watched_episodes,
+                tags,
+                comment: Some(anime.my_comments).filter(|comment| !comment.is_empty()),
+            }
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// 解析AniList的JSON导出文件
+///
+/// 支持GraphQL `MediaListCollection` 结构，外层可带有`data`包装。
+/// 评分按列表的评分格式换算为十分制
+///
+/// # 参数
+/// - `json`: 导出文件内容
+/// - `score_format`: 可选，评分格式，默认读取导出中的`user.mediaListOptions.scoreFormat`
+///
+/// # 返回
+/// 返回转换为Bangumi表示方式的收藏列表
+pub fn parse_anilist_json(
+    json: &str,
+    score_format: Option<AniListScoreFormat>,
+) -> Result<Vec<ImportEntry>> {
+    // 按顶层是否有`data`选择结构，解析失败时报告对应结构的错误
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    let collection = match value.get("data") {
+        Some(_) => {
+            serde_json::from_str::<AniListResponse>(json)?
+                .data
+                .media_list_collection
+        }
+        None => serde_json::from_str::<AniListCollection>(json)?,
+    };
+    let exported = collection
+        .user
+        .as_ref()
+        .and_then(|user| user.media_list_options.as_ref())
+        .and_then(|options| options.score_format);
+    let Some(score_format) = score_format.or(exported) else {
+        bail!("无法确定AniList评分格式，请在导出中包含user.mediaListOptions.scoreFormat或手动指定")
+    };
+
+    let entries = collection
+        .lists
+        .into_iter()
+        .flat_map(|list| list.entries)
+        .map(|entry| {
+            let r#type = match entry.status.as_str() {
+                "CURRENT" | "REPEATING" => CollectionType::Doing,
+                "COMPLETED" => CollectionType::Done,
+                "PAUSED" => CollectionType::OnHold,
+                "DROPPED" => CollectionType::Dropped,
+                _ => CollectionType::Wish,
+            };
+            let score = anilist_score(entry.score, score_format);
+            let title = entry.media.title;
+            let titles = [title.native, title.romaji, title.english]
+                .into_iter()
+                .flatten()
+                .filter(|title| !title.is_empty())
+                .collect();
+            ImportEntry {
+                source: ImportSource::AniList,
+                source_id: entry.media_id,
+                mal_id: entry.media.id_mal,
+                titles,
+                r#type,
+                rate: score.round().clamp(0.0, 10.0) as u32,
+                progress: entry.progress,
+                tags: Vec::new(),
+                comment: entry.notes.filter(|notes| !notes.is_empty()),
+            }
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// 将AniList评分按其评分格式换算为0-10分
+///
+/// 三档表情按1、2、3对应3、7、10分处理
+fn anilist_score(score: f64, format: AniListScoreFormat) -> f64 {
+    match format {
+        AniListScoreFormat::Point100 => score / 10.0,
+        AniListScoreFormat::Point10Decimal | AniListScoreFormat::Point10 => score,
+        AniListScoreFormat::Point5 => score * 2.0,
+        AniListScoreFormat::Point3 => score * 10.0 / 3.0,
+    }
+}
+
+/// 解析离线ID映射表
+///
+/// 每行格式为`mal_id,bangumi_id`，忽略空行、`#`开头的注释行以及无法解析的表头
+///
+/// # 返回
+/// 返回MyAnimeList条目ID到Bangumi条目ID的映射
+pub fn parse_id_mapping(csv: &str) -> HashMap<u32, u32> {
+    csv.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (mal_id, bangumi_id) = line.split_once(',')?;
+            Some((mal_id.trim().parse().ok()?, bangumi_id.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// 归一化标题：转为小写并去除空白与标点
+pub(crate) fn normalize_title(title: &str) -> Vec<char> {
+    title
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+/// 计算两个标题的相似度
+///
+/// 基于归一化后字符二元组的Dice系数，取值范围为0到1
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = normalize_title(a);
+    let b = normalize_title(b);
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+    if a.len() < 2 || b.len() < 2 {
+        return 0.0;
+    }
+
+    let mut bigrams: HashMap<(char, char), usize> = HashMap::new();
+    for pair in a.windows(2) {
+        *bigrams.entry((pair[0], pair[1])).or_default() += 1;
+    }
+    let mut overlap = 0;
+    for pair in b.windows(2) {
+        if let Some(count) = bigrams.get_mut(&(pair[0], pair[1]))
+            && *count > 0
+        {
+            *count -= 1;
+            overlap += 1;
+        }
+    }
+
+    (2 * overlap) as f64 / (a.len() + b.len() - 2) as f64
+}
+
+/// 根据标题从候选条目中挑选匹配项
+///
+/// 候选条目的原名与中文名均参与比较，取与任一标题的最高相似度
+///
+/// # 参数
+/// - `entry`: 待匹配的收藏
+/// - `candidates`: 候选条目（通常来自标题搜索）
+///
+/// # 返回
+/// 返回匹配、歧义或未匹配的结果
+pub fn match_candidates(entry: &ImportEntry, candidates: &[Subject]) -> MatchOutcome {
+    let mut best: HashMap<u32, f64> = HashMap::new();
+    for subject in candidates {
+        let score = entry
+            .titles
+            .iter()
+            .flat_map(|title| {
+                [
+                    title_similarity(title, &subject.name),
+                    title_similarity(title, &subject.name_cn),
+                ]
+            })
+            .fold(0.0, f64::max);
+        // 同一条目可能重复出现在候选中，保留其最高相似度
+        let best = best.entry(subject.id).or_insert(score);
+        *best = best.max(score);
+    }
+    let mut scored: Vec<(u32, f64)> = best
+        .into_iter()
+        .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    match scored.as_slice() {
+        [] => MatchOutcome::Unmatched,
+        [(subject_id, _)] => MatchOutcome::Matched {
+            subject_id: *subject_id,
+            method: "title".to_string(),
+        },
+        [(best_id, best), (_, second), ..] if best - second >= AMBIGUITY_MARGIN => {
+            MatchOutcome::Matched {
+                subject_id: *best_id,
+                method: "title".to_string(),
+            }
+        }
+        [(_, best), ..] => MatchOutcome::Ambiguous {
+            candidates: scored
+                .iter()
+                .filter(|(_, score)| best - score < AMBIGUITY_MARGIN)
+                .map(|(id, _)| *id)
+                .collect(),
+        },
+    }
+}
+
+impl ImportPlan {
+    /// 已匹配的条目
+    pub fn matched(&self) -> impl Iterator<Item = &ImportItem> {
+        self.items
+            .iter()
+            .filter(|item| matches!(item.outcome, MatchOutcome::Matched { .. }))
+    }
+
+    /// 有歧义的条目
+    pub fn ambiguous(&self) -> impl Iterator<Item = &ImportItem> {
+        self.items
+            .iter()
+            .filter(|item| matches!(item.outcome, MatchOutcome::Ambiguous { .. }))
+    }
+
+    /// 未匹配的条目
+    pub fn unmatched(&self) -> impl Iterator<Item = &ImportItem> {
+        self.items
+            .iter()
+            .filter(|item| item.outcome == MatchOutcome::Unmatched)
+    }
+}
+
+impl BangumiClient {
+    /// 生成导入计划（试运行）
+    ///
+    /// 优先使用离线ID映射表匹配条目，映射缺失时按标题搜索动画条目并进行模糊匹配。
+    /// 该方法只读取数据，不会修改任何收藏
+    ///
+    /// # 参数
+    /// - `entries`: 待导入的收藏列表
+    /// - `mapping`: MyAnimeList条目ID到Bangumi条目ID的映射
+    ///
+    /// # 返回
+    /// 返回包含每条收藏匹配结果的导入计划
+    pub async fn plan_import(
+        &self,
+        entries: Vec<ImportEntry>,
+        mapping: &HashMap<u32, u32>,
+    ) -> Result<ImportPlan> {
+        let mut plan = ImportPlan::default();
+
+        for entry in entries {
+            if let Some(subject_id) = entry.mal_id.and_then(|id| mapping.get(&id)) {
+                plan.items.push(ImportItem {
+                    outcome: MatchOutcome::Matched {
+                        subject_id: *subject_id,
+                        method: "mapping".to_string(),
+                    },
+                    entry,
+                });
+                continue;
+            }
+
+            let mut candidates = Vec::new();
+            for title in entry.titles.iter().filter(|title| !title.is_empty()) {
+                let payload = SubjectSearch {
+                    keyword: title.clone(),
+                    sort: None,
+                    filter: Some(SubjectSearchFilter {
+                        r#type: vec![SubjectType::Anime],
+                        meta_tags: Vec::new(),
+                        tag: Vec::new(),
+                        air_date: Vec::new(),
+                        rating: Vec::new(),
+                        rank: Vec::new(),
+                        nsfw: true,
+                    }),
+                };
+                let page = self
+                    .search_subjects(Some(SEARCH_LIMIT), None, Some(payload))
+                    .await?;
+                candidates.extend(page.data.unwrap_or_default());
+            }
+
+            plan.items.push(ImportItem {
+                outcome: match_candidates(&entry, &candidates),
+                entry,
+            });
+        }
+
+        Ok(plan)
+    }
+
+    /// 执行导入计划
+    ///
+    /// 仅写入已匹配的条目，并跳过`state`中已记录的收藏，因此可以在中断后重复调用继续导入。
+    /// 每条收藏写入成功后立即记录到`state`（需认证）
+    ///
+    /// # 参数
+    /// - `plan`: 导入计划
+    /// - `state`: 导入进度状态
+    ///
+    /// # 返回
+    /// 返回本次写入的收藏数量
+    pub async fn apply_import(&self, plan: &ImportPlan, state: &mut ImportState) -> Result<usize> {
+        let mut applied = 0;
+
+        for item in plan.matched() {
+            let MatchOutcome::Matched { subject_id, .. } = item.outcome else {
+                continue;
+            };
+            let key = (item.entry.source, item.entry.source_id);
+            if state.applied.contains(&key) {
+                continue;
+            }
+
+            let entry = &item.entry;
+            let payload = CollectionSubjectUpdate {
+                r#type: Some(entry.r#type.clone()),
+                rate: Some(entry.rate).filter(|rate| *rate > 0),
+                ep_status: None,
+                vol_status: None,
+                comment: entry.comment.clone(),
+                private: None,
+                tags: Some(entry.tags.clone()).filter(|tags| !tags.is_empty()),
+            };
+            self.post_collection_subject(subject_id, Some(payload))
+                .await?;
+
+            // 按排序标记前若干集正片为已看
+            if entry.progress > 0 {
+                let mut episodes = BangumiClient::fetch_all(|limit, offset| {
+                    self.get_episodes(
+                        subject_id,
+                        Some(EpisodeType::Normal),
+                        Some(limit),
+                        Some(offset),
+                    )
+                })
+                .await?;
+                episodes.sort_by(|a, b| a.sort.total_cmp(&b.sort));
+                let episode_id: Vec<u32> = episodes
+                    .iter()
+                    .take(entry.progress as usize)
+                    .map(|episode| episode.id)
+                    .collect();
+                if !episode_id.is_empty() {
+                    let payload = CollectionEpisodesUpdate {
+                        episode_id,
+                        r#type: CollectionEpisodeType::Done,
+                    };
+                    self.patch_collection_episodes(subject_id, Some(payload))
+                        .await?;
+                }
+            }
+
+            state.applied.insert(key);
+            applied += 1;
+        }
+
+        Ok(applied)
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::model::BangumiClient,
+    module::{
+        collection::model::CollectionType,
+        import::{
+            model::{AniListScoreFormat, ImportSource, ImportState, MatchOutcome},
+            service::{
+                match_candidates, parse_anilist_json, parse_id_mapping, parse_mal_xml,
+                title_similarity,
+            },
+        },
+        subject::model::Subject,
+    },
+};
+
+const MAL_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<myanimelist>
+  <myinfo>
+    <user_name>sai</user_name>
+    <user_export_type>1</user_export_type>
+  </myinfo>
+  <anime>
+    <series_animedb_id>5114</series_animedb_id>
+    <series_title><![CDATA[Fullmetal Alchemist: Brotherhood]]></series_title>
+    <series_episodes>64</series_episodes>
+    <my_watched_episodes>64</my_watched_episodes>
+    <my_score>10</my_score>
+    <my_status>Completed</my_status>
+    <my_comments><![CDATA[]]></my_comments>
+    <my_tags><![CDATA[action, classic]]></my_tags>
+  </anime>
+  <anime>
+    <series_animedb_id>1</series_animedb_id>
+    <series_title><![CDATA[Cowboy Bebop]]></series_title>
+    <my_watched_episodes>3</my_watched_episodes>
+    <my_score>0</my_score>
+    <my_status>Watching</my_status>
+    <my_comments><![CDATA[rewatch]]></my_comments>
+    <my_tags></my_tags>
+  </anime>
+</myanimelist>
+"#;
+
+fn subject(id: u32, name: &str, name_cn: &str) -> Subject {
+    let count: serde_json::Map<String, serde_json::Value> = (1..=10)
+        .map(|score| (score.to_string(), serde_json::json!(0)))
+        .collect();
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "type": 2,
+        "name": name,
+        "name_cn": name_cn,
+        "summary": "",
+        "series": false,
+        "nsfw": false,
+        "locked": false,
+        "date": null,
+        "platform": "TV",
+        "images": {"large": "", "common": "", "medium": "", "small": "", "grid": ""},
+        "infobox": [],
+        "volumes": 0,
+        "eps": 12,
+        "rating": {"rank": 0, "total": 0, "count": count, "score": 0.0},
+        "collection": {"wish": 0, "collect": 0, "doing": 0, "on_hold": 0, "dropped": 0},
+        "meta_tags": [],
+        "tags": []
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_parse_mal_xml() {
+    let entries = parse_mal_xml(MAL_XML).unwrap();
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0].source, ImportSource::Mal);
+    assert_eq!(entries[0].mal_id, Some(5114));
+    assert_eq!(entries[0].titles, vec!["Fullmetal Alchemist: Brotherhood"]);
+    assert!(matches!(entries[0].r#type, CollectionType::Done));
+    assert_eq!(entries[0].rate, 10);
+    assert_eq!(entries[0].tags, vec!["action", "classic"]);
+    assert_eq!(entries[0].comment, None);
+
+    assert!(matches!(entries[1].r#type, CollectionType::Doing));
+    assert_eq!(entries[1].progress, 3);
+    assert!(entries[1].tags.is_empty());
+    assert_eq!(entries[1].comment.as_deref(), Some("rewatch"));
+}
+
+#[test]
+fn test_parse_anilist_json() {
+    let collection = serde_json::json!({
+        "lists": [{
+            "entries": [{
+                "mediaId": 21,
+                "status": "PAUSED",
+                "score": 85,
+                "progress": 100,
+                "notes": null,
+                "media": {
+                    "idMal": 21,
+                    "title": {"romaji": "One Piece", "english": "ONE PIECE", "native": "ワンピース"}
+                }
+            }]
+        }],
+        "user": {"mediaListOptions": {"scoreFormat": "POINT_100"}}
+    });
+    let wrapped = serde_json::json!({"data": {"MediaListCollection": collection}});
+
+    for json in [collection.to_string(), wrapped.to_string()] {
+        let entries = parse_anilist_json(&json, None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].source, ImportSource::AniList);
+        assert_eq!(entries[0].mal_id, Some(21));
+        assert_eq!(
+            entries[0].titles,
+            vec!["ワンピース", "One Piece", "ONE PIECE"]
+        );
+        assert!(matches!(entries[0].r#type, CollectionType::OnHold));
+        assert_eq!(entries[0].rate, 9);
+        assert_eq!(entries[0].progress, 100);
+    }
+
+    // 带`data`包装的导出解析失败时报告包装结构中的错误
+    let broken = serde_json::json!({"data": {"MediaListCollection": {"lists": 1}}});
+    let error = parse_anilist_json(&broken.to_string(), None).unwrap_err();
+    assert!(!error.to_string().contains("missing field `lists`"));
+    assert!(error.to_string().contains("invalid type"));
+}
+
+#[test]
+fn test_parse_anilist_score_format() {
+    let collection = |score: u32| {
+        serde_json::json!({
+            "lists": [{
+                "entries": [{
+                    "mediaId": 1,
+                    "status": "COMPLETED",
+                    "score": score,
+                    "progress": 26,
+                    "media": {"title": {"romaji": "Cowboy Bebop"}}
+                }]
+            }]
+        })
+        .to_string()
+    };
+    let rate = |score: u32, format: AniListScoreFormat| {
+        parse_anilist_json(&collection(score), Some(format)).unwrap()[0].rate
+    };
+
+    // 百分制的10分是1分而不是10分
+    assert_eq!(rate(10, AniListScoreFormat::Point100), 1);
+    assert_eq!(rate(10, AniListScoreFormat::Point10), 10);
+    assert_eq!(rate(4, AniListScoreFormat::Point5), 8);
+    assert_eq!(rate(2, AniListScoreFormat::Point3), 7);
+    assert_eq!(rate(0, AniListScoreFormat::Point3), 0);
+
+    // 既未导出也未指定评分格式时报错，而不是猜测
+    assert!(parse_anilist_json(&collection(10), None).is_err());
+}
+
+#[test]
+fn test_parse_id_mapping() {
+    let mapping = parse_id_mapping("mal_id,bangumi_id\n# comment\n5114, 55770\n\n1,253\nbad\n");
+    assert_eq!(mapping, HashMap::from([(5114, 55770), (1, 253)]));
+}
+
+#[test]
+fn test_title_similarity() {
+    assert_eq!(title_similarity("Cowboy Bebop", "cowboy-bebop!"), 1.0);
+    assert!(title_similarity("Fullmetal Alchemist", "Fullmetal Alchemist: Brotherhood") > 0.6);
+    assert!(title_similarity("Cowboy Bebop", "Trigun") < 0.2);
+    assert_eq!(title_similarity("", "Trigun"), 0.0);
+}
+
+#[test]
+fn test_match_candidates() {
+    let entry = parse_mal_xml(MAL_XML).unwrap().remove(1);
+
+    let outcome = match_candidates(
+        &entry,
+        &[
+            subject(253, "カウボーイビバップ", "COWBOY BEBOP"),
+            subject(300, "Trigun", "枪神"),
+        ],
+    );
+    assert_eq!(
+        outcome,
+        MatchOutcome::Matched {
+            subject_id: 253,
+            method: "title".to_string()
+        }
+    );
+
+    let outcome = match_candidates(
+        &entry,
+        &[
+            subject(253, "Cowboy Bebop", ""),
+            subject(254, "Cowboy Bebop", "星际牛仔"),
+        ],
+    );
+    assert_eq!(
+        outcome,
+        MatchOutcome::Ambiguous {
+            candidates: vec![253, 254]
+        }
+    );
+
+    // 重复出现的候选按最高相似度计一次
+    let outcome = match_candidates(
+        &entry,
+        &[
+            subject(253, "Cowboy Bebop", ""),
+            subject(254, "Cowboy Bebop: Tengoku no Tobira", ""),
+            subject(253, "Cowboy Bebop TV", "星际牛仔"),
+        ],
+    );
+    assert_eq!(
+        outcome,
+        MatchOutcome::Matched {
+            subject_id: 253,
+            method: "title".to_string()
+        }
+    );
+
+    let outcome = match_candidates(&entry, &[subject(300, "Trigun", "枪神")]);
+    assert_eq!(outcome, MatchOutcome::Unmatched);
+}
+
+#[test]
+fn test_import_state_roundtrip() {
+    let mut state = ImportState::default();
+    state.applied.insert((ImportSource::Mal, 5114));
+    state.applied.insert((ImportSource::AniList, 21));
+
+    let json = serde_json::to_string(&state).unwrap();
+    let restored: ImportState = serde_json::from_str(&json).unwrap();
+    assert!(restored.applied.contains(&(ImportSource::Mal, 5114)));
+    assert!(restored.applied.contains(&(ImportSource::AniList, 21)));
+}
+
+#[tokio::test]
+async fn test_plan_import() {
+    let client = BangumiClient::default();
+    let entries = parse_mal_xml(MAL_XML).unwrap();
+    let result = client.plan_import(entries, &HashMap::new()).await;
+    assert!(result.is_ok(), "生成导入计划失败: {:?}", result.err());
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use reqwest::Method;
+use http::Method;
 
 use crate::{
     common::model::BangumiClient,
@@ -25,7 +25,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::POST, &url);
 
         // 发送请求并解析响应为Index结构体
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -47,7 +47,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析响应为Index结构体
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -75,7 +75,7 @@ impl BangumiClient {
         request_builder = request_builder.json(&payload);
 
         // 发送请求并解析响应为更新后的Index结构体
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -118,7 +118,7 @@ impl BangumiClient {
         }
 
         // 发送请求并解析响应为分页的Subject列表
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -179,7 +179,7 @@ impl BangumiClient {
         request_builder = request_builder.json(&payload);
 
         // 发送请求并忽略响应内容
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -205,7 +205,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::DELETE, &url);
 
         // 发送请求并忽略响应内容
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
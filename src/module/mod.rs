@@ -1,10 +1,30 @@
+/// 接口特征要求返回的Future实现`Send`，而wasm32上的请求不满足，因此不在wasm32上编译
+#[cfg(not(target_arch = "wasm32"))]
+pub mod api;
+#[cfg(feature = "archive")]
+pub mod archive;
+pub mod calendar;
 pub mod character;
 pub mod collection;
 pub mod episode;
+pub mod export;
+pub mod franchise;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod import;
 pub mod indice;
 pub mod person;
+pub mod rating;
+pub mod recommend;
 pub mod revision;
+pub mod scrobble;
+pub mod search;
+pub mod season;
+pub mod stats;
+#[cfg(feature = "store")]
+pub mod store;
 pub mod subject;
+pub mod sync;
 pub mod user;
 
 pub mod model;
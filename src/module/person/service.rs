@@ -1,6 +1,6 @@
 use anyhow::Result;
 use bytes::Bytes;
-use reqwest::Method;
+use http::Method;
 
 use crate::{
     common::model::BangumiClient,
@@ -44,7 +44,7 @@ impl BangumiClient {
         request_builder = request_builder.json(&payload);
 
         // 发送请求并解析分页的人物详情结果
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -66,7 +66,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析人物详情
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -90,7 +90,7 @@ impl BangumiClient {
         request_builder = request_builder.query(&[("type", &r#type)]);
 
         // 发送请求并获取图片二进制数据
-        let res = self.request_send(request_builder).await?.bytes().await?;
+        let res = self.request_send(request_builder).await?.bytes();
 
         Ok(res)
     }
@@ -112,7 +112,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析条目列表
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -134,7 +134,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析角色列表
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
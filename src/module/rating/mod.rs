@@ -0,0 +1,6 @@
+/// 评分分析
+pub mod model;
+pub mod service;
+
+#[cfg(test)]
+pub mod test;
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use crate::module::subject::model::RatingCount;
+
+/// 评分分布的统计指标
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatingMetrics {
+    /// 评分人数
+    pub total: u32,
+    /// 平均分
+    pub mean: f64,
+    /// 标准差
+    pub std_dev: f64,
+    /// 中位数
+    pub median: f64,
+    /// 争议度（0-1），标准差相对最大可能标准差（4.5）的比例
+    pub controversy: f64,
+}
+
+/// 贝叶斯加权评分的先验
+///
+/// 评分人数较少时结果向先验平均分收缩，人数越多越接近实际平均分
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BayesianPrior {
+    /// 先验平均分（通常取全站或同类条目的平均分）
+    pub mean: f64,
+    /// 先验权重，相当于预先存在的虚拟评分人数
+    pub weight: f64,
+}
+
+impl Default for BayesianPrior {
+    fn default() -> Self {
+        Self {
+            mean: 6.5,
+            weight: 100.0,
+        }
+    }
+}
+
+/// 计算贝叶斯加权平均分
+///
+/// 公式为`(prior.weight * prior.mean + votes * score) / (prior.weight + votes)`，
+/// 评分人数与先验权重均为0时返回先验平均分
+pub fn bayesian_average(score: f64, votes: u32, prior: &BayesianPrior) -> f64 {
+    let votes = votes as f64;
+    let weight = prior.weight.max(0.0);
+    if weight + votes == 0.0 {
+        return prior.mean;
+    }
+    (weight * prior.mean + votes * score) / (weight + votes)
+}
+
+/// 两个评分分布的比较结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatingComparison {
+    /// 平均分之差（左减右）
+    pub mean_diff: f64,
+    /// 标准差之差（左减右）
+    pub std_dev_diff: f64,
+    /// 争议度之差（左减右）
+    pub controversy_diff: f64,
+    /// 各分数占比之差（左减右，下标0对应1分）
+    pub share_diff: [f64; 10],
+    /// 总变差距离（0-1），0表示分布形状完全相同
+    pub distance: f64,
+}
+
+/// 可以提供评分信息的类型
+///
+/// 为条目详情、列表中的精简评分以及收藏中的条目信息提供统一的评分计算入口
+pub trait Rated {
+    /// 平均分（站内显示的评分）
+    fn score(&self) -> f64;
+
+    /// 评分人数
+    fn votes(&self) -> u32;
+
+    /// 评分分布（部分类型不提供）
+    fn distribution(&self) -> Option<&RatingCount> {
+        None
+    }
+
+    /// 贝叶斯加权评分
+    fn bayesian_score(&self, prior: &BayesianPrior) -> f64 {
+        bayesian_average(self.score(), self.votes(), prior)
+    }
+
+    /// 评分分布的统计指标，没有分布或无人评分时返回None
+    fn metrics(&self) -> Option<RatingMetrics> {
+        self.distribution()?.metrics()
+    }
+}
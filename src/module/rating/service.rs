@@ -0,0 +1,207 @@
+use crate::module::{
+    collection::model::UserSubject,
+    subject::model::{Rating, RatingCount, SubjectSmallRating},
+};
+
+use super::model::{BayesianPrior, Rated, RatingComparison, RatingMetrics, bayesian_average};
+
+/// 1-10分评分的最大可能标准差（一半1分、一半10分）
+const MAX_STD_DEV: f64 = 4.5;
+
+impl From<[u32; 10]> for RatingCount {
+    fn from(counts: [u32; 10]) -> Self {
+        let [
+            param_1,
+            param_2,
+            param_3,
+            param_4,
+            param_5,
+            param_6,
+            param_7,
+            param_8,
+            param_9,
+            param_10,
+        ] = counts;
+        RatingCount {
+            param_1,
+            param_2,
+            param_3,
+            param_4,
+            param_5,
+            param_6,
+            param_7,
+            param_8,
+            param_9,
+            param_10,
+        }
+    }
+}
+
+impl From<&RatingCount> for [u32; 10] {
+    fn from(count: &RatingCount) -> Self {
+        count.as_array()
+    }
+}
+
+impl RatingCount {
+    /// 以数组形式返回各分数的投票数量（下标0对应1分）
+    pub fn as_array(&self) -> [u32; 10] {
+        [
+            self.param_1,
+            self.param_2,
+            self.param_3,
+            self.param_4,
+            self.param_5,
+            self.param_6,
+            self.param_7,
+            self.param_8,
+            self.param_9,
+            self.param_10,
+        ]
+    }
+
+    /// 遍历各分数及其投票数量，按1分到10分的顺序
+    pub fn iter(&self) -> impl Iterator<Item = (u8, u32)> {
+        self.as_array()
+            .into_iter()
+            .enumerate()
+            .map(|(index, count)| (index as u8 + 1, count))
+    }
+
+    /// 评分总人数
+    pub fn total(&self) -> u32 {
+        self.as_array().iter().sum()
+    }
+
+    /// 平均分，无人评分时返回None
+    pub fn mean(&self) -> Option<f64> {
+        let total = self.total();
+        (total > 0).then(|| {
+            self.iter()
+                .map(|(score, count)| score as f64 * count as f64)
+                .sum::<f64>()
+                / total as f64
+        })
+    }
+
+    /// 总体标准差，无人评分时返回None
+    pub fn std_dev(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let variance = self
+            .iter()
+            .map(|(score, count)| (score as f64 - mean).powi(2) * count as f64)
+            .sum::<f64>()
+            / self.total() as f64;
+        Some(variance.sqrt())
+    }
+
+    /// 中位数，评分人数为偶数时取中间两个分数的平均值，无人评分时返回None
+    pub fn median(&self) -> Option<f64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        // 第n个评分（从0开始）对应的分数
+        let nth = |n: u32| {
+            let mut seen = 0;
+            self.iter()
+                .find(|(_, count)| {
+                    seen += count;
+                    seen > n
+                })
+                .map(|(score, _)| score as f64)
+                .unwrap_or_default()
+        };
+        Some(if total % 2 == 1 {
+            nth(total / 2)
+        } else {
+            (nth(total / 2 - 1) + nth(total / 2)) / 2.0
+        })
+    }
+
+    /// 争议度（0-1）
+    ///
+    /// 以标准差相对最大可能标准差的比例衡量，评分越两极分化越接近1，无人评分时返回None
+    pub fn controversy(&self) -> Option<f64> {
+        Some(self.std_dev()? / MAX_STD_DEV)
+    }
+
+    /// 汇总全部统计指标，无人评分时返回None
+    pub fn metrics(&self) -> Option<RatingMetrics> {
+        Some(RatingMetrics {
+            total: self.total(),
+            mean: self.mean()?,
+            std_dev: self.std_dev()?,
+            median: self.median()?,
+            controversy: self.controversy()?,
+        })
+    }
+
+    /// 按分布计算贝叶斯加权评分，无人评分时返回先验平均分
+    pub fn bayesian_score(&self, prior: &BayesianPrior) -> f64 {
+        bayesian_average(self.mean().unwrap_or_default(), self.total(), prior)
+    }
+
+    /// 各分数的占比（下标0对应1分），无人评分时返回None
+    pub fn shares(&self) -> Option<[f64; 10]> {
+        let total = self.total();
+        (total > 0).then(|| self.as_array().map(|count| count as f64 / total as f64))
+    }
+
+    /// 与另一个评分分布比较
+    ///
+    /// # 返回
+    /// 任一分布无人评分时返回None
+    pub fn compare(&self, other: &RatingCount) -> Option<RatingComparison> {
+        let (left, right) = (self.metrics()?, other.metrics()?);
+        let (left_shares, right_shares) = (self.shares()?, other.shares()?);
+        let share_diff: [f64; 10] =
+            std::array::from_fn(|index| left_shares[index] - right_shares[index]);
+        Some(RatingComparison {
+            mean_diff: left.mean - right.mean,
+            std_dev_diff: left.std_dev - right.std_dev,
+            controversy_diff: left.controversy - right.controversy,
+            distance: share_diff.iter().map(|diff| diff.abs()).sum::<f64>() / 2.0,
+            share_diff,
+        })
+    }
+}
+
+impl Rated for Rating {
+    fn score(&self) -> f64 {
+        self.score
+    }
+
+    fn votes(&self) -> u32 {
+        self.total
+    }
+
+    fn distribution(&self) -> Option<&RatingCount> {
+        Some(&self.count)
+    }
+}
+
+impl Rated for SubjectSmallRating {
+    fn score(&self) -> f64 {
+        self.score
+    }
+
+    fn votes(&self) -> u32 {
+        self.total
+    }
+
+    fn distribution(&self) -> Option<&RatingCount> {
+        Some(&self.count)
+    }
+}
+
+/// 收藏中的条目信息不包含评分人数，以收藏总人数近似
+impl Rated for UserSubject {
+    fn score(&self) -> f64 {
+        self.score
+    }
+
+    fn votes(&self) -> u32 {
+        self.collection_total
+    }
+}
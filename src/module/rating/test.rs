@@ -0,0 +1,132 @@
+use crate::module::{
+    collection::model::UserSubject,
+    rating::model::{BayesianPrior, Rated, bayesian_average},
+    subject::model::{Rating, RatingCount, SubjectSmallRating},
+};
+
+const EPSILON: f64 = 1e-9;
+
+fn rating_json(counts: [u32; 10], score: f64) -> serde_json::Value {
+    let count: serde_json::Map<String, serde_json::Value> = counts
+        .iter()
+        .enumerate()
+        .map(|(index, count)| ((index + 1).to_string(), serde_json::json!(count)))
+        .collect();
+    serde_json::json!({
+        "rank": 1,
+        "total": counts.iter().sum::<u32>(),
+        "count": count,
+        "score": score
+    })
+}
+
+#[test]
+fn test_rating_count_array_view() {
+    let count = RatingCount::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    assert_eq!(count.param_1, 1);
+    assert_eq!(count.param_10, 10);
+    assert_eq!(count.as_array(), [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    assert_eq!(count.total(), 55);
+    assert_eq!(count.iter().next(), Some((1, 1)));
+    assert_eq!(count.iter().last(), Some((10, 10)));
+    assert_eq!(<[u32; 10]>::from(&count), count.as_array());
+}
+
+#[test]
+fn test_rating_count_metrics() {
+    let count = RatingCount::from([0, 0, 0, 0, 0, 2, 0, 2, 0, 0]);
+    let metrics = count.metrics().unwrap();
+    assert_eq!(metrics.total, 4);
+    assert!((metrics.mean - 7.0).abs() < EPSILON);
+    assert!((metrics.std_dev - 1.0).abs() < EPSILON);
+    assert!((metrics.median - 7.0).abs() < EPSILON);
+
+    let odd = RatingCount::from([0, 0, 0, 0, 1, 0, 1, 0, 1, 0]);
+    assert_eq!(odd.median(), Some(7.0));
+
+    // 完全两极分化的评分争议度为1
+    let split = RatingCount::from([5, 0, 0, 0, 0, 0, 0, 0, 0, 5]);
+    assert!((split.controversy().unwrap() - 1.0).abs() < EPSILON);
+    let unanimous = RatingCount::from([0, 0, 0, 0, 0, 0, 0, 9, 0, 0]);
+    assert_eq!(unanimous.controversy(), Some(0.0));
+
+    let empty = RatingCount::from([0; 10]);
+    assert_eq!(empty.metrics(), None);
+    assert_eq!(empty.median(), None);
+}
+
+#[test]
+fn test_bayesian_score() {
+    let prior = BayesianPrior {
+        mean: 6.0,
+        weight: 10.0,
+    };
+    assert!((bayesian_average(9.0, 10, &prior) - 7.5).abs() < EPSILON);
+    assert!((bayesian_average(9.0, 0, &prior) - 6.0).abs() < EPSILON);
+    assert!(
+        (bayesian_average(
+            9.0,
+            0,
+            &BayesianPrior {
+                mean: 6.0,
+                weight: 0.0
+            }
+        ) - 6.0)
+            .abs()
+            < EPSILON
+    );
+
+    // 评分人数越多越接近实际平均分
+    let few = RatingCount::from([0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+    let many = RatingCount::from([0, 0, 0, 0, 0, 0, 0, 0, 0, 200]);
+    assert!(few.bayesian_score(&prior) < many.bayesian_score(&prior));
+    assert!(many.bayesian_score(&prior) < 10.0);
+}
+
+#[test]
+fn test_compare_distributions() {
+    let left = RatingCount::from([0, 0, 0, 0, 0, 0, 0, 1, 0, 1]);
+    let right = RatingCount::from([0, 0, 0, 0, 0, 1, 0, 1, 0, 0]);
+    let comparison = left.compare(&right).unwrap();
+    assert!((comparison.mean_diff - 2.0).abs() < EPSILON);
+    assert!((comparison.std_dev_diff).abs() < EPSILON);
+    assert!((comparison.share_diff[9] - 0.5).abs() < EPSILON);
+    assert!((comparison.share_diff[5] + 0.5).abs() < EPSILON);
+    assert!((comparison.distance - 0.5).abs() < EPSILON);
+
+    assert_eq!(left.compare(&left).unwrap().distance, 0.0);
+    assert_eq!(left.compare(&RatingCount::from([0; 10])), None);
+}
+
+#[test]
+fn test_rated_sources() {
+    let counts = [0, 0, 0, 0, 0, 0, 0, 3, 0, 1];
+    let rating: Rating = serde_json::from_value(rating_json(counts, 8.5)).unwrap();
+    let small: SubjectSmallRating = serde_json::from_value(rating_json(counts, 8.5)).unwrap();
+    let user_subject: UserSubject = serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "type": 2,
+        "name": "",
+        "name_cn": "",
+        "short_summary": "",
+        "date": "",
+        "images": {"large": "", "common": "", "medium": "", "small": "", "grid": ""},
+        "volumes": 0,
+        "eps": 12,
+        "collection_total": 4,
+        "score": 8.5,
+        "rank": 0,
+        "tags": []
+    }))
+    .unwrap();
+
+    let prior = BayesianPrior::default();
+    let expected = bayesian_average(8.5, 4, &prior);
+    assert_eq!(rating.bayesian_score(&prior), expected);
+    assert_eq!(small.bayesian_score(&prior), expected);
+    assert_eq!(user_subject.bayesian_score(&prior), expected);
+
+    assert_eq!(rating.metrics(), small.metrics());
+    assert_eq!(rating.metrics().unwrap().median, 8.0);
+    assert_eq!(user_subject.metrics(), None);
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::module::subject::model::SubjectType;
+
+/// 推荐参数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecommendOptions {
+    /// 返回的推荐数量
+    pub limit: usize,
+    /// 视为喜欢的最低评分
+    pub like_rate: u32,
+    /// 视为不喜欢的最高评分，相似的条目会被降权
+    pub dislike_rate: u32,
+    /// 看过或在看但未评分的条目的权重
+    pub unrated_weight: f64,
+    /// 与喜欢的条目存在关联关系时增加的分数
+    pub relation_weight: f64,
+    /// 站内评分（折算为0-1）在总分中的权重
+    pub rating_weight: f64,
+    /// 拉取关联条目时使用的种子条目数量（按评分降序）
+    pub seed_limit: usize,
+    /// 按画像标签搜索候选条目时使用的标签数量
+    pub search_tags: usize,
+    /// 候选条目数量上限
+    pub candidate_limit: usize,
+    /// 仅推荐这些类型的条目（为空时不限制）
+    pub subject_types: Vec<SubjectType>,
+}
+
+impl Default for RecommendOptions {
+    fn default() -> Self {
+        Self {
+            limit: 20,
+            like_rate: 7,
+            dislike_rate: 4,
+            unrated_weight: 0.5,
+            relation_weight: 0.3,
+            rating_weight: 0.1,
+            seed_limit: 20,
+            search_tags: 3,
+            candidate_limit: 100,
+            subject_types: Vec::new(),
+        }
+    }
+}
+
+/// 推荐理由
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecommendReason {
+    /// 与用户高分收藏的条目相似
+    SimilarTo {
+        /// 收藏中的条目ID
+        subject_id: u32,
+        /// 收藏中的条目名称
+        name: String,
+        /// 用户的评分（未评分时为0）
+        rate: u32,
+        /// 标签相似度（0-1）
+        similarity: f64,
+    },
+    /// 与用户喜欢的条目存在关联关系
+    RelatedTo {
+        /// 收藏中的条目ID
+        subject_id: u32,
+        /// 收藏中的条目名称
+        name: String,
+        /// 关系描述（如续集、前传）
+        relation: String,
+    },
+    /// 与用户偏好的标签重合
+    SharedTags {
+        /// 重合的标签（按贡献降序）
+        tags: Vec<String>,
+    },
+}
+
+impl fmt::Display for RecommendReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecommendReason::SimilarTo { name, rate, .. } if *rate > 0 => {
+                write!(f, "因为你给《{name}》打了{rate}分")
+            }
+            RecommendReason::SimilarTo { name, .. } => write!(f, "因为你看过《{name}》"),
+            RecommendReason::RelatedTo { name, relation, .. } => {
+                write!(f, "《{name}》的{relation}")
+            }
+            RecommendReason::SharedTags { tags } => {
+                write!(f, "你常看的标签：{}", tags.join("、"))
+            }
+        }
+    }
+}
+
+/// 单个推荐结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recommendation {
+    /// 条目ID
+    pub subject_id: u32,
+    /// 条目类型
+    pub r#type: SubjectType,
+    /// 条目原名
+    pub name: String,
+    /// 条目中文名
+    pub name_cn: String,
+    /// 推荐分数（越高越推荐）
+    pub score: f64,
+    /// 推荐理由（按重要性排序）
+    pub reasons: Vec<RecommendReason>,
+}
@@ -0,0 +1,444 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+
+use crate::{
+    common::model::BangumiClient,
+    module::{
+        collection::model::{CollectionSubject, CollectionType},
+        subject::model::{
+            Subject, SubjectSearch, SubjectSearchFilter, SubjectSubject, SubjectType,
+        },
+    },
+};
+
+use super::model::{RecommendOptions, RecommendReason, Recommendation};
+
+/// 每个标签搜索的候选数量
+const SEARCH_LIMIT: u32 = 20;
+
+/// 推荐理由中展示的标签数量
+const REASON_TAG_LIMIT: usize = 3;
+
+/// 不喜欢的条目对分数的影响系数
+const DISLIKE_PENALTY: f64 = 0.5;
+
+/// 标签向量（标签名到权重）
+type TagVector = HashMap<String, f64>;
+
+/// 计算标签的词频权重
+///
+/// 使用增强词频`0.5 + 0.5 * count / max`，避免热门条目的标注人数压过冷门条目
+fn term_frequency<'a>(tags: impl Iterator<Item = (&'a str, u32)>) -> Vec<(&'a str, f64)> {
+    let tags: Vec<(&str, u32)> = tags.collect();
+    let max = tags
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or_default();
+    tags.into_iter()
+        .map(|(name, count)| {
+            let tf = if max == 0 {
+                1.0
+            } else {
+                0.5 + 0.5 * count as f64 / max as f64
+            };
+            (name, tf)
+        })
+        .collect()
+}
+
+/// 收藏中条目的标签词频
+fn collection_terms(item: &CollectionSubject) -> Vec<(&str, f64)> {
+    item.subject
+        .as_ref()
+        .map(|subject| {
+            term_frequency(
+                subject
+                    .tags
+                    .iter()
+                    .map(|tag| (tag.name.as_str(), tag.count)),
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// 候选条目的标签词频（公共标签的词频固定为1）
+fn subject_terms(subject: &Subject) -> Vec<(&str, f64)> {
+    let mut terms = term_frequency(
+        subject
+            .tags
+            .iter()
+            .map(|tag| (tag.name.as_str(), tag.count)),
+    );
+    for meta_tag in &subject.meta_tags {
+        match terms.iter_mut().find(|(name, _)| name == meta_tag) {
+            Some(term) => term.1 = 1.0,
+            None => terms.push((meta_tag.as_str(), 1.0)),
+        }
+    }
+    terms
+}
+
+/// 基于全部文档的逆文档频率表
+struct TfIdf {
+    idf: HashMap<String, f64>,
+}
+
+impl TfIdf {
+    /// 统计每个标签出现的文档数并计算平滑后的逆文档频率
+    fn new<'a>(documents: impl Iterator<Item = Vec<(&'a str, f64)>>) -> Self {
+        let mut document_count = 0;
+        let mut frequency: HashMap<String, u32> = HashMap::new();
+        for terms in documents {
+            document_count += 1;
+            let unique: HashSet<&str> = terms.into_iter().map(|(name, _)| name).collect();
+            for name in unique {
+                *frequency.entry(name.to_string()).or_default() += 1;
+            }
+        }
+        let idf = frequency
+            .into_iter()
+            .map(|(name, df)| {
+                let idf = ((1.0 + document_count as f64) / (1.0 + df as f64)).ln() + 1.0;
+                (name, idf)
+            })
+            .collect();
+        Self { idf }
+    }
+
+    /// 生成L2归一化的TF-IDF向量
+    fn vector(&self, terms: Vec<(&str, f64)>) -> TagVector {
+        let mut vector: TagVector = terms
+            .into_iter()
+            .map(|(name, tf)| {
+                let idf = self.idf.get(name).copied().unwrap_or(1.0);
+                (name.to_string(), tf * idf)
+            })
+            .collect();
+        let norm = vector
+            .values()
+            .map(|value| value * value)
+            .sum::<f64>()
+            .sqrt();
+        if norm > 0.0 {
+            vector.values_mut().for_each(|value| *value /= norm);
+        }
+        vector
+    }
+}
+
+/// 两个归一化向量的余弦相似度
+fn cosine(a: &TagVector, b: &TagVector) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    small
+        .iter()
+        .filter_map(|(name, value)| large.get(name).map(|other| value * other))
+        .sum()
+}
+
+/// 收藏条目在用户画像中的权重
+///
+/// 高分为正、低分为负；未评分时看过、在看记为较小的正权重，抛弃记为较小的负权重，
+/// 想看与搁置不参与计算
+pub fn seed_weight(item: &CollectionSubject, options: &RecommendOptions) -> Option<f64> {
+    match item.rate {
+        0 => match item.r#type {
+            CollectionType::Done | CollectionType::Doing => Some(options.unrated_weight),
+            CollectionType::Dropped => Some(-options.unrated_weight),
+            _ => None,
+        },
+        rate if rate >= options.like_rate => Some((rate - options.like_rate + 1) as f64),
+        rate if rate <= options.dislike_rate => Some(-((options.dislike_rate + 1 - rate) as f64)),
+        _ => None,
+    }
+}
+
+/// 收藏条目的显示名称（优先使用中文名）
+fn collection_name(item: &CollectionSubject) -> String {
+    item.subject
+        .as_ref()
+        .map(|subject| {
+            if subject.name_cn.is_empty() {
+                subject.name.clone()
+            } else {
+                subject.name_cn.clone()
+            }
+        })
+        .unwrap_or_else(|| item.subject_id.to_string())
+}
+
+/// 根据用户收藏生成推荐
+///
+/// 完全基于已获取的数据计算，不依赖外部服务：
+/// - 以收藏条目与候选条目的标签、公共标签构建TF-IDF向量
+/// - 按用户评分加权计算候选条目与收藏条目的相似度，低分条目的相似度会被扣除
+/// - 与喜欢的条目存在关联关系（续集、前传等）时额外加分
+/// - 站内评分作为少量的质量加成
+///
+/// # 参数
+/// - `collections`: 用户的条目收藏（任何状态的收藏条目都不会被推荐）
+/// - `candidates`: 候选条目
+/// - `relations`: 收藏条目ID到其关联条目的映射（来自`get_subject_subjects`）
+/// - `options`: 推荐参数
+///
+/// # 返回
+/// 返回按分数降序排列的推荐结果及推荐理由
+pub fn recommend(
+    collections: &[CollectionSubject],
+    candidates: &[Subject],
+    relations: &HashMap<u32, Vec<SubjectSubject>>,
+    options: &RecommendOptions,
+) -> Vec<Recommendation> {
+    let collected: HashSet<u32> = collections.iter().map(|item| item.subject_id).collect();
+    let mut seen = HashSet::new();
+    let candidates: Vec<&Subject> = candidates
+        .iter()
+        .filter(|subject| !collected.contains(&subject.id) && seen.insert(subject.id))
+        .filter(|subject| {
+            options.subject_types.is_empty() || options.subject_types.contains(&subject.r#type)
+        })
+        .collect();
+
+    let tfidf = TfIdf::new(
+        collections
+            .iter()
+            .map(collection_terms)
+            .chain(candidates.iter().map(|subject| subject_terms(subject))),
+    );
+
+    // 参与计算的收藏条目：(条目, 权重, 标签向量)
+    let seeds: Vec<(&CollectionSubject, f64, TagVector)> = collections
+        .iter()
+        .filter_map(|item| {
+            let weight = seed_weight(item, options)?;
+            Some((item, weight, tfidf.vector(collection_terms(item))))
+        })
+        .collect();
+    let positive_total: f64 = seeds.iter().map(|seed| seed.1.max(0.0)).sum();
+    let negative_total: f64 = seeds.iter().map(|seed| (-seed.1).max(0.0)).sum();
+    let max_weight = seeds.iter().map(|seed| seed.1).fold(0.0, f64::max);
+
+    // 用户画像：喜欢的条目标签向量的加权和
+    let mut profile = TagVector::new();
+    for (_, weight, vector) in seeds.iter().filter(|seed| seed.1 > 0.0) {
+        for (name, value) in vector {
+            *profile.entry(name.clone()).or_default() += weight * value;
+        }
+    }
+
+    let mut recommendations: Vec<Recommendation> = candidates
+        .into_iter()
+        .filter_map(|subject| {
+            let vector = tfidf.vector(subject_terms(subject));
+            let mut reasons = Vec::new();
+
+            let mut positive = 0.0;
+            let mut negative = 0.0;
+            let mut best: Option<(&CollectionSubject, f64, f64)> = None;
+            for (item, weight, seed_vector) in &seeds {
+                let similarity = cosine(&vector, seed_vector);
+                if *weight > 0.0 {
+                    positive += weight * similarity;
+                    if similarity > 0.0 && best.is_none_or(|(_, w, s)| weight * similarity > w * s)
+                    {
+                        best = Some((item, *weight, similarity));
+                    }
+                } else {
+                    negative += -weight * similarity;
+                }
+            }
+            let mut content = 0.0;
+            if positive_total > 0.0 {
+                content += positive / positive_total;
+            }
+            if negative_total > 0.0 {
+                content -= DISLIKE_PENALTY * negative / negative_total;
+            }
+
+            // 与喜欢的条目的关联关系，取权重最高的一条
+            let related = seeds
+                .iter()
+                .filter(|seed| seed.1 > 0.0)
+                .filter_map(|(item, weight, _)| {
+                    let relation = relations
+                        .get(&item.subject_id)?
+                        .iter()
+                        .find(|related| related.id == subject.id)?;
+                    Some((item, *weight, relation))
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            let mut relation_score = 0.0;
+            if let Some((item, weight, relation)) = related {
+                relation_score = options.relation_weight * weight / max_weight;
+                reasons.push(RecommendReason::RelatedTo {
+                    subject_id: item.subject_id,
+                    name: collection_name(item),
+                    relation: relation.relation.clone(),
+                });
+            }
+
+            if content <= 0.0 && related.is_none() {
+                return None;
+            }
+
+            if let Some((item, _, similarity)) = best {
+                reasons.push(RecommendReason::SimilarTo {
+                    subject_id: item.subject_id,
+                    name: collection_name(item),
+                    rate: item.rate,
+                    similarity,
+                });
+            }
+
+            let mut shared: Vec<(&String, f64)> = vector
+                .iter()
+                .filter_map(|(name, value)| Some((name, value * profile.get(name)?)))
+                .filter(|(_, contribution)| *contribution > 0.0)
+                .collect();
+            shared.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+            if !shared.is_empty() {
+                reasons.push(RecommendReason::SharedTags {
+                    tags: shared
+                        .into_iter()
+                        .take(REASON_TAG_LIMIT)
+                        .map(|(name, _)| name.clone())
+                        .collect(),
+                });
+            }
+
+            let quality = options.rating_weight * subject.rating.score / 10.0;
+            Some(Recommendation {
+                subject_id: subject.id,
+                r#type: subject.r#type.clone(),
+                name: subject.name.clone(),
+                name_cn: subject.name_cn.clone(),
+                score: content.max(0.0) + relation_score + quality,
+                reasons,
+            })
+        })
+        .collect();
+
+    recommendations.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.subject_id.cmp(&b.subject_id))
+    });
+    recommendations.truncate(options.limit);
+    recommendations
+}
+
+impl BangumiClient {
+    /// 为用户生成推荐
+    ///
+    /// 拉取用户的全部收藏后，以评分最高的收藏条目为种子获取其关联条目，
+    /// 再按种子条目最常见的标签搜索同类型条目补充候选，最后在本地计算推荐结果
+    ///
+    /// # 参数
+    /// - `username`: 用户名
+    /// - `options`: 推荐参数
+    ///
+    /// # 返回
+    /// 返回按分数降序排列的推荐结果及推荐理由
+    pub async fn recommend_for_user(
+        &self,
+        username: &str,
+        options: &RecommendOptions,
+    ) -> Result<Vec<Recommendation>> {
+        let collections: Vec<CollectionSubject> = BangumiClient::fetch_all(|limit, offset| {
+            self.get_collection_subjects(username, None, None, Some(limit), Some(offset))
+        })
+        .await?;
+        let collected: HashSet<u32> = collections.iter().map(|item| item.subject_id).collect();
+
+        let mut seeds: Vec<(&CollectionSubject, f64)> = collections
+            .iter()
+            .filter_map(|item| Some((item, seed_weight(item, options)?)))
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+        seeds.sort_by(|a, b| b.1.total_cmp(&a.1));
+        seeds.truncate(options.seed_limit);
+
+        let wanted_type = |subject_type: &SubjectType| {
+            options.subject_types.is_empty() || options.subject_types.contains(subject_type)
+        };
+
+        // 种子条目的关联条目
+        let mut relations = HashMap::new();
+        let mut related_ids = Vec::new();
+        for (item, _) in &seeds {
+            let related = self.get_subject_subjects(item.subject_id).await?;
+            for subject in &related {
+                if !collected.contains(&subject.id)
+                    && wanted_type(&subject.r#type)
+                    && !related_ids.contains(&subject.id)
+                {
+                    related_ids.push(subject.id);
+                }
+            }
+            relations.insert(item.subject_id, related);
+        }
+
+        // 按种子条目中最常见的标签搜索同类型条目
+        let mut tag_counts: HashMap<&str, f64> = HashMap::new();
+        let mut seed_types = Vec::new();
+        for (item, weight) in &seeds {
+            if !seed_types.contains(&item.subject_type) {
+                seed_types.push(item.subject_type.clone());
+            }
+            for (name, tf) in collection_terms(item) {
+                *tag_counts.entry(name).or_default() += weight * tf;
+            }
+        }
+        let mut top_tags: Vec<(&str, f64)> = tag_counts.into_iter().collect();
+        top_tags.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+        let search_types = if options.subject_types.is_empty() {
+            seed_types
+        } else {
+            options.subject_types.clone()
+        };
+
+        let mut candidates: Vec<Subject> = Vec::new();
+        // 关联条目需要获取详情才有标签信息
+        for subject_id in related_ids {
+            if candidates.len() >= options.candidate_limit {
+                break;
+            }
+            candidates.push(self.get_subject(subject_id).await?);
+        }
+        for (tag, _) in top_tags.into_iter().take(options.search_tags) {
+            let payload = SubjectSearch {
+                keyword: String::new(),
+                sort: None,
+                filter: Some(SubjectSearchFilter {
+                    r#type: search_types.clone(),
+                    meta_tags: Vec::new(),
+                    tag: vec![tag.to_string()],
+                    air_date: Vec::new(),
+                    rating: Vec::new(),
+                    rank: Vec::new(),
+                    nsfw: false,
+                }),
+            };
+            let found = self
+                .search_subjects(Some(SEARCH_LIMIT), None, Some(payload))
+                .await?
+                .data
+                .unwrap_or_default();
+            for subject in found {
+                if candidates.len() >= options.candidate_limit {
+                    break;
+                }
+                if !collected.contains(&subject.id)
+                    && !candidates
+                        .iter()
+                        .any(|candidate| candidate.id == subject.id)
+                {
+                    candidates.push(subject);
+                }
+            }
+        }
+
+        Ok(recommend(&collections, &candidates, &relations, options))
+    }
+}
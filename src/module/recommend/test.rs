@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::{
+        fixture::{self, Fixture, parse_all},
+        mock::{MockRoute, MockServer},
+    },
+    module::{
+        collection::model::CollectionSubject,
+        recommend::{
+            model::{RecommendOptions, RecommendReason},
+            service::recommend,
+        },
+        subject::model::{Subject, SubjectSubject},
+    },
+};
+
+fn collection_json(subject_id: u32, r#type: u8, rate: u32, tags: &[&str]) -> Fixture {
+    fixture::collection(subject_id)
+        .with("type", r#type)
+        .with("rate", rate)
+        .with("subject.name_cn", format!("条目{subject_id}"))
+        .tags("subject.tags", tags)
+}
+
+fn subject_json(id: u32, score: f64, tags: &[&str], meta_tags: &[&str]) -> Fixture {
+    fixture::subject(id)
+        .with("rating.score", score)
+        .with("meta_tags", meta_tags)
+        .tags("tags", tags)
+}
+
+fn collections() -> Vec<CollectionSubject> {
+    parse_all([
+        collection_json(1, 2, 9, &["科幻", "机战", "原创"]),
+        collection_json(2, 2, 2, &["恋爱", "校园"]),
+        collection_json(3, 1, 0, &["日常"]),
+    ])
+}
+
+fn candidates() -> Vec<Subject> {
+    parse_all([
+        subject_json(10, 8.0, &["科幻", "机战"], &["TV"]),
+        subject_json(11, 9.0, &["恋爱", "校园"], &[]),
+        subject_json(12, 6.0, &["科幻", "日常"], &[]),
+        subject_json(13, 9.0, &["美食"], &[]),
+        subject_json(1, 9.0, &["科幻", "机战"], &[]),
+    ])
+}
+
+#[test]
+fn test_recommend() {
+    let relations: HashMap<u32, Vec<SubjectSubject>> =
+        HashMap::from([(1, parse_all([fixture::related(10, "续集")]))]);
+    let result = recommend(
+        &collections(),
+        &candidates(),
+        &relations,
+        &RecommendOptions::default(),
+    );
+
+    // 已收藏的条目、与低分条目相似的条目以及毫无关联的条目都不会被推荐
+    let ids: Vec<u32> = result.iter().map(|item| item.subject_id).collect();
+    assert_eq!(ids, vec![10, 12]);
+    assert!(result[0].score > result[1].score);
+
+    let reasons = &result[0].reasons;
+    assert!(matches!(
+        &reasons[0],
+        RecommendReason::RelatedTo { subject_id: 1, relation, .. } if relation == "续集"
+    ));
+    assert!(matches!(
+        &reasons[1],
+        RecommendReason::SimilarTo { subject_id: 1, rate: 9, similarity, .. } if *similarity > 0.5
+    ));
+    assert_eq!(reasons[0].to_string(), "《条目1》的续集");
+    assert_eq!(reasons[1].to_string(), "因为你给《条目1》打了9分");
+    match &reasons[2] {
+        RecommendReason::SharedTags { tags } => {
+            assert!(tags.contains(&"科幻".to_string()));
+            assert!(tags.contains(&"机战".to_string()));
+        }
+        reason => panic!("意外的推荐理由: {reason:?}"),
+    }
+
+    let limited = recommend(
+        &collections(),
+        &candidates(),
+        &relations,
+        &RecommendOptions {
+            limit: 1,
+            ..RecommendOptions::default()
+        },
+    );
+    assert_eq!(limited.len(), 1);
+}
+
+#[test]
+fn test_recommend_without_liked_subjects() {
+    let collections: Vec<CollectionSubject> = parse_all([collection_json(2, 2, 2, &["恋爱"])]);
+    let result = recommend(
+        &collections,
+        &candidates(),
+        &HashMap::new(),
+        &RecommendOptions::default(),
+    );
+    assert!(result.is_empty());
+}
+
+#[tokio::test]
+async fn test_recommend_for_user() {
+    let server = MockServer::start(vec![
+        MockRoute::json(
+            "GET",
+            "/v0/users/sai/collections",
+            200,
+            serde_json::json!({
+                "total": 3,
+                "limit": 100,
+                "offset": 0,
+                "data": serde_json::to_value(collections()).unwrap()
+            }),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/1/subjects",
+            200,
+            serde_json::json!([fixture::related(10, "续集"), fixture::related(2, "前传")]),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/10",
+            200,
+            subject_json(10, 8.0, &["科幻", "机战"], &[]).json(),
+        ),
+        MockRoute::json(
+            "POST",
+            "/v0/search/subjects",
+            200,
+            serde_json::json!({
+                "total": 2,
+                "limit": 20,
+                "offset": 0,
+                "data": [
+                    subject_json(12, 6.0, &["科幻", "日常"], &[]),
+                    subject_json(1, 9.0, &["科幻"], &[])
+                ]
+            }),
+        ),
+    ]);
+    let client = server.client();
+
+    let options = RecommendOptions {
+        search_tags: 1,
+        ..RecommendOptions::default()
+    };
+    let result = client.recommend_for_user("sai", &options).await.unwrap();
+    let ids: Vec<u32> = result.iter().map(|item| item.subject_id).collect();
+    assert_eq!(ids, vec![10, 12]);
+
+    // 仅以喜欢的条目为种子，已收藏的关联条目不会被请求
+    let paths: Vec<String> = server
+        .requests()
+        .into_iter()
+        .map(|request| request.path)
+        .collect();
+    assert!(!paths.contains(&"/v0/subjects/2/subjects".to_string()));
+    assert!(!paths.contains(&"/v0/subjects/2".to_string()));
+    assert_eq!(
+        paths
+            .iter()
+            .filter(|path| *path == "/v0/search/subjects")
+            .count(),
+        1
+    );
+}
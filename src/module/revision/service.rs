@@ -1,5 +1,5 @@
 use anyhow::Result;
-use reqwest::Method;
+use http::Method;
 
 use crate::{common::model::BangumiClient, module::model::Paged};
 
@@ -42,7 +42,7 @@ impl BangumiClient {
         }
 
         // 发送请求并解析分页的通用修订信息
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -64,7 +64,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析人物修订详情
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -103,7 +103,7 @@ impl BangumiClient {
         }
 
         // 发送请求并解析分页的通用修订信息
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -125,7 +125,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析角色修订详情
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -163,7 +163,7 @@ impl BangumiClient {
             request_builder = request_builder.query(&[("offset", &param_value)]);
         }
         // 发送请求并解析分页的通用修订信息
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -185,7 +185,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析条目修订详情
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -223,7 +223,7 @@ impl BangumiClient {
             request_builder = request_builder.query(&[("offset", &param_value)]);
         }
         // 发送请求并解析分页的通用修订信息
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -245,7 +245,7 @@ impl BangumiClient {
         let request_builder = self.request_builder(Method::GET, &url);
 
         // 发送请求并解析剧集修订详情
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -0,0 +1,6 @@
+/// 媒体文件记录
+pub mod model;
+pub mod service;
+
+#[cfg(all(test, any(feature = "reqwest", feature = "ureq")))]
+pub mod test;
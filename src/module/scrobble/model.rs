@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// 从发布文件名中解析出的信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParsedRelease {
+    /// 作品标题
+    pub title: String,
+    /// 集数（支持小数，如总集篇的12.5）
+    pub episode: f64,
+    /// 季数（可选，来自`S01E05`形式或标题末尾的`S2`、`第二季`等标记）
+    pub season: Option<u32>,
+    /// 发布组（可选，来自文件名开头的方括号）
+    pub group: Option<String>,
+}
+
+/// 标题别名缓存
+///
+/// 记录归一化后的标题到条目ID的映射，可序列化保存，避免重复搜索
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AliasCache {
+    /// 归一化标题到条目ID的映射
+    pub aliases: HashMap<String, u32>,
+}
+
+/// 一次记录操作的结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScrobbleResult {
+    /// 解析出的文件信息
+    pub release: ParsedRelease,
+    /// 匹配到的条目ID
+    pub subject_id: u32,
+    /// 被标记为已看的剧集ID
+    pub episode_id: u32,
+}
@@ -0,0 +1,502 @@
+use anyhow::{Result, bail};
+
+use crate::{
+    common::model::BangumiClient,
+    module::{
+        collection::model::{CollectionEpisodeType, CollectionEpisodeUpdate},
+        episode::model::{Episode, EpisodeType},
+        import::service::{normalize_title, title_similarity},
+        subject::model::{
+            Subject, SubjectRelation, SubjectSearch, SubjectSearchFilter, SubjectType,
+        },
+    },
+};
+
+use super::model::{AliasCache, ParsedRelease, ScrobbleResult};
+
+/// 标题相似度达到该值才视为匹配
+const MATCH_THRESHOLD: f64 = 0.6;
+
+/// 标题搜索时获取的候选数量
+const SEARCH_LIMIT: u32 = 10;
+
+/// 比较集数时允许的误差
+const EP_EPSILON: f64 = 1e-6;
+
+/// 解析以数字开头的集数
+///
+/// 支持一位小数（如`12.5`）以及版本后缀（如`05v2`）
+///
+/// # 返回
+/// 返回集数及消耗的字节数
+fn parse_number(text: &str) -> Option<(f64, usize)> {
+    let digits = text.bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let mut end = digits;
+    let bytes = text.as_bytes();
+    // 仅接受一位小数，避免将`07.1080p`误读为小数
+    if bytes.get(end) == Some(&b'.')
+        && bytes.get(end + 1).is_some_and(u8::is_ascii_digit)
+        && !bytes.get(end + 2).is_some_and(u8::is_ascii_digit)
+    {
+        end += 2;
+    }
+    let value = text[..end].parse().ok()?;
+    if matches!(bytes.get(end), Some(b'v' | b'V'))
+        && bytes.get(end + 1).is_some_and(u8::is_ascii_digit)
+    {
+        end += 1 + text[end + 1..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+    }
+    Some((value, end))
+}
+
+/// 判断集数后的内容是否为分隔位置（结尾、空白或括号）
+fn is_boundary(rest: &str) -> bool {
+    rest.chars()
+        .next()
+        .is_none_or(|c| c.is_whitespace() || "[(【.-_".contains(c))
+}
+
+/// 判断方括号内容是否为画质、编码等技术标签
+fn is_tech_tag(tag: &str) -> bool {
+    let tag = tag.to_lowercase();
+    [
+        "1080", "720", "480", "2160", "4k", "x264", "x265", "hevc", "avc", "aac", "flac", "web",
+        "bd", "mp4", "mkv", "gb", "big5", "chs", "cht", "简", "繁",
+    ]
+    .iter()
+    .any(|keyword| tag.contains(keyword))
+}
+
+/// 清理标题：去除括号标签、替换分隔符并合并空白
+fn clean_title(title: &str) -> String {
+    let mut cleaned = String::new();
+    let mut depth = 0;
+    for c in title.chars() {
+        match c {
+            '[' | '(' | '【' => depth += 1,
+            ']' | ')' | '】' => depth = (depth - 1).max(0),
+            _ if depth == 0 => cleaned.push(c),
+            _ => {}
+        }
+    }
+    if !cleaned.contains(' ') {
+        cleaned = cleaned.replace(['_', '.'], " ");
+    }
+    cleaned
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c == '-' || c == ' ')
+        .to_string()
+}
+
+/// 解析不超过两位的中文数字（如`二`、`十二`）
+fn parse_chinese_number(text: &str) -> Option<u32> {
+    let digit = |c: char| "零一二三四五六七八九".chars().position(|d| d == c);
+    let chars: Vec<char> = text.chars().collect();
+    let value = match chars.as_slice() {
+        ['十'] => 10,
+        [c] => digit(*c)?,
+        ['十', ones] => 10 + digit(*ones)?,
+        [tens, '十'] => digit(*tens)? * 10,
+        [tens, '十', ones] => digit(*tens)? * 10 + digit(*ones)?,
+        _ => return None,
+    };
+    Some(value as u32)
+}
+
+/// 从标题末尾分离季数标记
+///
+/// 支持`第2季`、`第二期`、`S2`、`Season 2`、`2nd Season`以及`II`至`V`的罗马数字
+///
+/// # 返回
+/// 返回去除标记后的标题及季数，没有季数标记时原样返回标题
+pub fn split_season(title: &str) -> (&str, Option<u32>) {
+    let title = title.trim_end();
+
+    // 第2季 / 第二期 / 第3部
+    if let Some(body) = title.strip_suffix(['季', '期', '部'])
+        && let Some(index) = body.rfind('第')
+    {
+        let number = &body[index + '第'.len_utf8()..];
+        let season = number.parse().ok().or_else(|| parse_chinese_number(number));
+        if season.is_some() {
+            return (body[..index].trim_end(), season);
+        }
+    }
+
+    let Some((head, last)) = title.rsplit_once(' ') else {
+        return (title, None);
+    };
+    let head = head.trim_end();
+    let lower = last.to_ascii_lowercase();
+
+    // S2 / S02
+    if let Some(number) = lower.strip_prefix('s')
+        && !number.is_empty()
+        && let Ok(season) = number.parse()
+    {
+        return (head, Some(season));
+    }
+
+    // Season 2
+    if let Ok(season) = lower.parse()
+        && let Some(head) = head
+            .strip_suffix("Season")
+            .or_else(|| head.strip_suffix("season"))
+    {
+        return (head.trim_end(), Some(season));
+    }
+
+    // 2nd Season
+    if lower == "season"
+        && let Some((rest, ordinal)) = head.rsplit_once(' ')
+        && let Some(number) = ["st", "nd", "rd", "th"].iter().find_map(|suffix| {
+            ordinal
+                .to_ascii_lowercase()
+                .strip_suffix(suffix)
+                .map(str::to_string)
+        })
+        && let Ok(season) = number.parse()
+    {
+        return (rest.trim_end(), Some(season));
+    }
+
+    // II / III / IV / V
+    if let Some(season) = ["II", "III", "IV", "V"]
+        .iter()
+        .position(|roman| *roman == last)
+    {
+        return (head, Some(season as u32 + 2));
+    }
+
+    (title, None)
+}
+
+/// 条目名称中标明的季数，没有标记时视为第一季
+fn subject_season(name: &str, name_cn: &str) -> u32 {
+    split_season(name)
+        .1
+        .or(split_season(name_cn).1)
+        .unwrap_or(1)
+}
+
+/// 解析动画发布文件名
+///
+/// 支持以下常见形式：
+/// - `[Group] Title - 05 [1080p].mkv`
+/// - `Title.S01E05.1080p.mkv`
+/// - `[Group] 标题 第05话 [GB].mp4`
+/// - `[Group][Title][05][1080p].mkv`
+/// - `Title 05.mkv`
+///
+/// # 参数
+/// - `filename`: 文件名或文件路径
+///
+/// # 返回
+/// 成功返回解析结果，无法识别集数或标题时返回None
+pub fn parse_release(filename: &str) -> Option<ParsedRelease> {
+    // 去除目录与扩展名
+    let name = filename.rsplit(['/', '\\']).next()?;
+    let name = match name.rsplit_once('.') {
+        Some((stem, ext))
+            if (2..=4).contains(&ext.len()) && ext.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            stem
+        }
+        _ => name,
+    };
+
+    // 去除开头的发布组
+    let mut rest = name.trim();
+    let mut group = None;
+    if let Some(inner) = rest.strip_prefix('[')
+        && let Some(end) = inner.find(']')
+    {
+        group = Some(inner[..end].trim().to_string()).filter(|group| !group.is_empty());
+        rest = inner[end + 1..].trim_start();
+    }
+
+    let release = |title: &str, episode: f64, season: Option<u32>| {
+        let title = clean_title(title);
+        // 标题中的季数标记（如`Title S2 - 05`）同样视为季数
+        let (title, marked) = split_season(&title);
+        (!title.is_empty()).then(|| ParsedRelease {
+            title: title.to_string(),
+            episode,
+            season: season.or(marked),
+            group: group.clone(),
+        })
+    };
+
+    // S01E05
+    let lower = rest.to_ascii_lowercase();
+    for (index, _) in lower.match_indices('s') {
+        if index > 0 && lower.as_bytes()[index - 1].is_ascii_alphanumeric() {
+            continue;
+        }
+        let Some((season, consumed)) = parse_number(&lower[index + 1..]) else {
+            continue;
+        };
+        let after = index + 1 + consumed;
+        if lower.as_bytes().get(after) != Some(&b'e') {
+            continue;
+        }
+        if let Some((episode, consumed)) = parse_number(&lower[after + 1..])
+            && is_boundary(&lower[after + 1 + consumed..])
+        {
+            return release(&rest[..index], episode, Some(season as u32));
+        }
+    }
+
+    // 第05话 / 第05話 / 第05集
+    for (index, _) in rest.match_indices('第') {
+        let after = index + '第'.len_utf8();
+        if let Some((episode, consumed)) = parse_number(&rest[after..])
+            && rest[after + consumed..].starts_with(['话', '話', '集'])
+        {
+            return release(&rest[..index], episode, None);
+        }
+    }
+
+    // Title - 05
+    for (index, _) in rest.rmatch_indices(" - ") {
+        let after = rest[index + 3..].trim_start();
+        if let Some((episode, consumed)) = parse_number(after)
+            && is_boundary(&after[consumed..])
+        {
+            return release(&rest[..index], episode, None);
+        }
+    }
+
+    // [Title][05][1080p]
+    if rest.starts_with('[') {
+        let mut title = None;
+        let mut episode = None;
+        for segment in rest.split(['[', ']']).map(str::trim) {
+            if segment.is_empty() {
+                continue;
+            }
+            match parse_number(segment) {
+                Some((number, consumed)) if consumed == segment.len() && episode.is_none() => {
+                    episode = Some(number)
+                }
+                _ if title.is_none() && !is_tech_tag(segment) => title = Some(segment),
+                _ => {}
+            }
+        }
+        if let (Some(title), Some(episode)) = (title, episode) {
+            return release(title, episode, None);
+        }
+    }
+
+    // Title 05
+    let stripped = clean_title(rest);
+    let (title, last) = stripped.rsplit_once(' ')?;
+    let (episode, consumed) = parse_number(last)?;
+    (consumed == last.len())
+        .then(|| release(title, episode, None))
+        .flatten()
+}
+
+impl AliasCache {
+    /// 生成缓存键：归一化标题，非第一季时附加季数
+    fn key(release: &ParsedRelease) -> String {
+        let title: String = normalize_title(&release.title).into_iter().collect();
+        match release.season {
+            Some(season) if season > 1 => format!("{title}#s{season}"),
+            _ => title,
+        }
+    }
+
+    /// 查询发布文件对应的条目ID
+    pub fn get(&self, release: &ParsedRelease) -> Option<u32> {
+        self.aliases.get(&Self::key(release)).copied()
+    }
+
+    /// 记录发布文件对应的条目ID
+    pub fn insert(&mut self, release: &ParsedRelease, subject_id: u32) {
+        self.aliases.insert(Self::key(release), subject_id);
+    }
+}
+
+/// 从剧集列表中找到与集数对应的正片
+///
+/// 集数优先匹配`ep`，找不到时匹配`sort`
+pub fn find_episode(episodes: &[Episode], number: f64) -> Option<&Episode> {
+    let normal = || {
+        episodes
+            .iter()
+            .filter(|episode| episode.r#type == EpisodeType::Normal)
+    };
+    normal()
+        .find(|episode| (episode.ep - number).abs() < EP_EPSILON)
+        .or_else(|| normal().find(|episode| (episode.sort - number).abs() < EP_EPSILON))
+}
+
+impl BangumiClient {
+    /// 解析发布文件对应的条目
+    ///
+    /// 优先查询别名缓存，未命中时按标题搜索动画条目，在名称标明同一季数的候选中
+    /// 选取相似度最高的结果（名称没有季数标记的条目视为第一季）。
+    /// 候选中没有对应季数时，从第一季沿"续集"关系找到第N季。匹配成功后写入缓存
+    ///
+    /// # 参数
+    /// - `release`: 解析出的文件信息
+    /// - `cache`: 标题别名缓存
+    ///
+    /// # 返回
+    /// 匹配成功返回条目ID，未找到返回None
+    pub async fn resolve_release(
+        &self,
+        release: &ParsedRelease,
+        cache: &mut AliasCache,
+    ) -> Result<Option<u32>> {
+        if let Some(subject_id) = cache.get(release) {
+            return Ok(Some(subject_id));
+        }
+
+        let payload = SubjectSearch {
+            keyword: release.title.clone(),
+            sort: None,
+            filter: Some(SubjectSearchFilter {
+                r#type: vec![SubjectType::Anime],
+                meta_tags: Vec::new(),
+                tag: Vec::new(),
+                air_date: Vec::new(),
+                rating: Vec::new(),
+                rank: Vec::new(),
+                nsfw: true,
+            }),
+        };
+        let candidates = self
+            .search_subjects(Some(SEARCH_LIMIT), None, Some(payload))
+            .await?
+            .data
+            .unwrap_or_default();
+
+        // 比较去除季数标记后的名称，续作与第一季的相似度相同，再按季数筛选
+        let scored: Vec<(&Subject, u32, f64)> = candidates
+            .iter()
+            .map(|subject| {
+                let score = title_similarity(&release.title, split_season(&subject.name).0).max(
+                    title_similarity(&release.title, split_season(&subject.name_cn).0),
+                );
+                let season = subject_season(&subject.name, &subject.name_cn);
+                (subject, season, score)
+            })
+            .filter(|(_, _, score)| *score >= MATCH_THRESHOLD)
+            .collect();
+        let best_of = |season: u32| {
+            scored
+                .iter()
+                .filter(|(_, candidate, _)| *candidate == season)
+                .max_by(|a, b| a.2.total_cmp(&b.2))
+                .map(|(subject, _, _)| subject.id)
+        };
+
+        let best = match release.season {
+            // 未标明季数时优先第一季，否则取相似度最高的候选
+            None => best_of(1).or_else(|| {
+                scored
+                    .iter()
+                    .max_by(|a, b| a.2.total_cmp(&b.2))
+                    .map(|(subject, _, _)| subject.id)
+            }),
+            Some(season) => match (best_of(season), best_of(1)) {
+                (Some(subject_id), _) => Some(subject_id),
+                (None, Some(first)) if season > 1 => self.follow_sequels(first, season).await?,
+                _ => None,
+            },
+        };
+
+        if let Some(subject_id) = best {
+            cache.insert(release, subject_id);
+        }
+        Ok(best)
+    }
+
+    /// 从第一季沿"续集"关系找到系列的第N季
+    ///
+    /// 同一条目有多个续集（如剧场版与第二季）时，优先名称中标明该季数的动画
+    ///
+    /// # 返回
+    /// 返回第N季的条目ID，续集关系中断时返回None
+    async fn follow_sequels(&self, first: u32, season: u32) -> Result<Option<u32>> {
+        let mut current = first;
+        for step in 2..=season {
+            let sequels: Vec<_> = self
+                .get_subject_subjects(current)
+                .await?
+                .into_iter()
+                .filter(|related| {
+                    related.r#type == SubjectType::Anime
+                        && related.relation_type() == SubjectRelation::Sequel
+                })
+                .collect();
+            let next = sequels
+                .iter()
+                .find(|related| subject_season(&related.name, &related.name_cn) == step)
+                .or(sequels.first());
+            let Some(next) = next else {
+                return Ok(None);
+            };
+            current = next.id;
+        }
+        Ok(Some(current))
+    }
+
+    /// 根据媒体文件名记录观看（需认证）
+    ///
+    /// 解析文件名得到标题与集数，匹配条目及对应正片后将该集标记为已看
+    ///
+    /// # 参数
+    /// - `filename`: 文件名或文件路径
+    /// - `cache`: 标题别名缓存（匹配成功后会被更新）
+    ///
+    /// # 返回
+    /// 返回记录结果，无法解析文件名或找不到对应条目、剧集时返回错误
+    pub async fn scrobble(&self, filename: &str, cache: &mut AliasCache) -> Result<ScrobbleResult> {
+        let Some(release) = parse_release(filename) else {
+            bail!(format!("无法解析文件名: {filename}"))
+        };
+        let Some(subject_id) = self.resolve_release(&release, cache).await? else {
+            bail!(format!("未找到匹配的条目: {}", release.title))
+        };
+
+        let episodes = BangumiClient::fetch_all(|limit, offset| {
+            self.get_episodes(
+                subject_id,
+                Some(EpisodeType::Normal),
+                Some(limit),
+                Some(offset),
+            )
+        })
+        .await?;
+        let Some(episode) = find_episode(&episodes, release.episode) else {
+            bail!(format!(
+                "条目 {subject_id} 中未找到第 {} 集",
+                release.episode
+            ))
+        };
+        let episode_id = episode.id;
+
+        let payload = CollectionEpisodeUpdate {
+            r#type: CollectionEpisodeType::Done,
+        };
+        self.put_collection_episode(episode_id, Some(payload))
+            .await?;
+
+        Ok(ScrobbleResult {
+            release,
+            subject_id,
+            episode_id,
+        })
+    }
+}
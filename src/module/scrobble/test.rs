@@ -0,0 +1,268 @@
+use crate::{
+    common::{
+        fixture::{self, Fixture},
+        mock::{MockRoute, MockServer},
+    },
+    module::scrobble::{
+        model::{AliasCache, ParsedRelease},
+        service::{parse_release, split_season},
+    },
+};
+
+fn search_route(subjects: Vec<Fixture>) -> MockRoute {
+    MockRoute::json(
+        "POST",
+        "/v0/search/subjects",
+        200,
+        serde_json::json!({
+            "total": subjects.len(), "limit": 10, "offset": 0, "data": subjects
+        }),
+    )
+}
+
+fn parsed(filename: &str) -> (String, f64, Option<u32>, Option<String>) {
+    let release = parse_release(filename).expect(filename);
+    (
+        release.title,
+        release.episode,
+        release.season,
+        release.group,
+    )
+}
+
+#[test]
+fn test_parse_release_dash() {
+    assert_eq!(
+        parsed("[SubsPlease] Sousou no Frieren - 05 [1080p].mkv"),
+        (
+            "Sousou no Frieren".to_string(),
+            5.0,
+            None,
+            Some("SubsPlease".to_string())
+        )
+    );
+    assert_eq!(
+        parsed("/media/anime/[Group] Re - Zero - 12.5v2 (BD 1080p).mkv").0,
+        "Re - Zero"
+    );
+    assert_eq!(parsed("[Group] Re - Zero - 12.5v2 (BD 1080p).mkv").1, 12.5);
+}
+
+#[test]
+fn test_parse_release_season_episode() {
+    assert_eq!(
+        parsed("Spy.x.Family.S02E07.1080p.WEB.mkv"),
+        ("Spy x Family".to_string(), 7.0, Some(2), None)
+    );
+    assert_eq!(
+        parsed("Mushishi s01e26.mp4"),
+        ("Mushishi".to_string(), 26.0, Some(1), None)
+    );
+}
+
+#[test]
+fn test_parse_release_chinese() {
+    assert_eq!(
+        parsed("[喵萌奶茶屋] 葬送的芙莉莲 第05话 [1080p][简日双语].mp4"),
+        (
+            "葬送的芙莉莲".to_string(),
+            5.0,
+            None,
+            Some("喵萌奶茶屋".to_string())
+        )
+    );
+    assert_eq!(parsed("孤独摇滚 第12集.mkv").1, 12.0);
+}
+
+#[test]
+fn test_parse_release_brackets_and_fallback() {
+    assert_eq!(
+        parsed("[LoliHouse][Bocchi the Rock!][03][1080p][HEVC].mkv"),
+        (
+            "Bocchi the Rock!".to_string(),
+            3.0,
+            None,
+            Some("LoliHouse".to_string())
+        )
+    );
+    assert_eq!(parsed("Cowboy Bebop 09.avi").0, "Cowboy Bebop");
+    assert_eq!(parse_release("readme.txt"), None);
+}
+
+#[test]
+fn test_split_season() {
+    assert_eq!(split_season("Show S2"), ("Show", Some(2)));
+    assert_eq!(
+        split_season("Spy x Family Season 2"),
+        ("Spy x Family", Some(2))
+    );
+    assert_eq!(
+        split_season("Kaguya-sama 3rd Season"),
+        ("Kaguya-sama", Some(3))
+    );
+    assert_eq!(split_season("间谍过家家 第二季"), ("间谍过家家", Some(2)));
+    assert_eq!(
+        split_season("进击的巨人 第十二期"),
+        ("进击的巨人", Some(12))
+    );
+    assert_eq!(split_season("Overlord III"), ("Overlord", Some(3)));
+    assert_eq!(split_season("Mob Psycho 100"), ("Mob Psycho 100", None));
+    assert_eq!(split_season("Steins;Gate"), ("Steins;Gate", None));
+
+    assert_eq!(
+        parsed("[Group] Show S2 - 05 [1080p].mkv"),
+        ("Show".to_string(), 5.0, Some(2), Some("Group".to_string()))
+    );
+}
+
+#[test]
+fn test_alias_cache() {
+    let mut cache = AliasCache::default();
+    let release = parse_release("[A] Bocchi the Rock! - 01.mkv").unwrap();
+    cache.insert(&release, 328609);
+
+    let other = parse_release("bocchi_the_rock_02.mkv").unwrap();
+    assert_eq!(cache.get(&other), Some(328609));
+
+    let season = ParsedRelease {
+        season: Some(2),
+        ..other
+    };
+    assert_eq!(cache.get(&season), None);
+}
+
+#[tokio::test]
+async fn test_scrobble_with_mock_server() {
+    let episode = |id: u32, ep: f64| fixture::episode(id, ep).with("subject_id", 328609);
+    let server = MockServer::start(vec![
+        search_route(vec![
+            fixture::subject(328609).named("ぼっち・ざ・ろっく！", "孤独摇滚！"),
+        ]),
+        MockRoute::json(
+            "GET",
+            "/v0/episodes",
+            200,
+            serde_json::json!({
+                "total": 2, "limit": 100, "offset": 0,
+                "data": [episode(1001, 1.0), episode(1002, 2.0)]
+            }),
+        ),
+        MockRoute::json(
+            "PUT",
+            "/v0/users/-/collections/-/episodes/1002",
+            204,
+            serde_json::Value::Null,
+        ),
+    ]);
+    let client = server.client();
+
+    let mut cache = AliasCache::default();
+    let result = client
+        .scrobble("[Sub] 孤独摇滚！ 第02话 [1080p].mp4", &mut cache)
+        .await
+        .unwrap();
+    assert_eq!(result.subject_id, 328609);
+    assert_eq!(result.episode_id, 1002);
+    assert_eq!(cache.get(&result.release), Some(328609));
+
+    // 再次记录时命中缓存，不会重复搜索
+    client
+        .scrobble("[Sub] 孤独摇滚！ 第02话 [1080p].mp4", &mut cache)
+        .await
+        .unwrap();
+    let searches = server
+        .requests()
+        .iter()
+        .filter(|request| request.path == "/v0/search/subjects")
+        .count();
+    assert_eq!(searches, 1);
+}
+
+#[tokio::test]
+async fn test_resolve_release_season() {
+    let server = MockServer::start(vec![search_route(vec![
+        fixture::subject(1).named("SPY×FAMILY", "间谍过家家"),
+        fixture::subject(2).named("SPY×FAMILY Season 2", "间谍过家家 第二季"),
+    ])]);
+    let client = server.client();
+    let mut cache = AliasCache::default();
+
+    let second = parse_release("[Group] SPY×FAMILY S2 - 05 [1080p].mkv").unwrap();
+    assert_eq!(
+        client.resolve_release(&second, &mut cache).await.unwrap(),
+        Some(2)
+    );
+    let first = parse_release("[Group] SPY×FAMILY - 05 [1080p].mkv").unwrap();
+    assert_eq!(
+        client.resolve_release(&first, &mut cache).await.unwrap(),
+        Some(1)
+    );
+    assert_eq!(cache.get(&second), Some(2));
+    assert_eq!(cache.get(&first), Some(1));
+}
+
+#[tokio::test]
+async fn test_resolve_release_sequel_chain() {
+    let related = |id: u32, name: &str, relation: &str, r#type: u8| {
+        fixture::related(id, relation)
+            .with("name", name)
+            .with("type", r#type)
+    };
+    // 搜索结果中只有第一季，第三季需沿续集关系查找
+    let server = MockServer::start(vec![
+        search_route(vec![fixture::subject(10).named("蟲師", "虫师")]),
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/10/subjects",
+            200,
+            serde_json::json!([
+                related(11, "蟲師 特別篇 日蝕む翳", "续集", 2),
+                related(12, "蟲師 続章", "续集", 2),
+                related(13, "蟲師 画集", "改编", 1)
+            ]),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/11/subjects",
+            200,
+            serde_json::json!([related(12, "蟲師 続章", "续集", 2)]),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/12/subjects",
+            200,
+            serde_json::json!([]),
+        ),
+    ]);
+    let client = server.client();
+    let mut cache = AliasCache::default();
+
+    let release = parse_release("Mushishi.S02E03.mkv").unwrap();
+    let release = ParsedRelease {
+        title: "虫师".to_string(),
+        ..release
+    };
+    assert_eq!(
+        client.resolve_release(&release, &mut cache).await.unwrap(),
+        Some(11)
+    );
+
+    let release = ParsedRelease {
+        season: Some(3),
+        ..release
+    };
+    assert_eq!(
+        client.resolve_release(&release, &mut cache).await.unwrap(),
+        Some(12)
+    );
+
+    // 续集关系中断时不匹配
+    let release = ParsedRelease {
+        season: Some(4),
+        ..release
+    };
+    assert_eq!(
+        client.resolve_release(&release, &mut cache).await.unwrap(),
+        None
+    );
+}
@@ -0,0 +1,6 @@
+/// 离线全文搜索
+pub mod model;
+pub mod service;
+
+#[cfg(test)]
+pub mod test;
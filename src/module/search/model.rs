@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use crate::module::{
+    character::model::Character, person::model::PersonDetail, subject::model::Subject,
+};
+
+/// 汉字拼音表
+///
+/// 用于将汉字转换为不带声调的拼音以支持拼音及拼音首字母搜索。
+/// 本crate不内置拼音数据，可从Unihan的`kMandarin`数据或`字 拼音`格式的文本加载
+#[derive(Debug, Clone, Default)]
+pub struct PinyinTable {
+    /// 汉字到拼音（小写、无声调，ü记作v）的映射
+    pub(crate) readings: HashMap<char, String>,
+}
+
+/// 带权重的检索键
+#[derive(Debug, Clone)]
+pub(crate) struct SearchKey {
+    /// 规范化后去除空白的文本
+    pub(crate) text: String,
+    /// 权重（名称高于别名，别名高于拼音/罗马字转写）
+    pub(crate) weight: f64,
+}
+
+/// 单类实体的倒排索引
+///
+/// 以检索键中的单字与相邻双字为索引项，查询时取最稀有的索引项缩小候选范围后再逐一匹配
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TextIndex {
+    /// 每个文档的检索键
+    pub(crate) keys: Vec<Vec<SearchKey>>,
+    /// 索引项到文档序号的映射（单字以`'\0'`补位）
+    pub(crate) grams: HashMap<(char, char), Vec<u32>>,
+}
+
+/// 离线全文搜索索引
+///
+/// 对本地的条目、角色与人物数据建立索引，查询参数与远端搜索接口相同。
+/// 支持名称、中文名与信息框别名的关键词匹配，中日文按单字与双字切分，
+/// 片假名统一为平假名，假名与汉字（需提供拼音表）同时以罗马字/拼音参与匹配
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    /// 拼音表
+    pub(crate) pinyin: PinyinTable,
+    /// 条目
+    pub(crate) subjects: Vec<Subject>,
+    /// 条目索引
+    pub(crate) subject_index: TextIndex,
+    /// 角色
+    pub(crate) characters: Vec<Character>,
+    /// 角色索引
+    pub(crate) character_index: TextIndex,
+    /// 人物
+    pub(crate) persons: Vec<PersonDetail>,
+    /// 人物索引
+    pub(crate) person_index: TextIndex,
+}
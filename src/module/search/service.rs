@@ -0,0 +1,820 @@
+use std::{cmp::Ordering, collections::HashSet};
+
+use crate::module::{
+    character::model::{Character, CharacterSearch},
+    model::{InfoBoxItem, Paged},
+    person::model::{PersonDetail, PersonSearch},
+    subject::model::{Subject, SubjectSearch, SubjectSearchSort},
+};
+
+use super::model::{PinyinTable, SearchIndex, SearchKey, TextIndex};
+
+/// 未指定数量时每页返回的结果数
+const DEFAULT_LIMIT: u32 = 10;
+
+/// 名称与中文名的权重
+const NAME_WEIGHT: f64 = 1.0;
+/// 信息框别名的权重
+const ALIAS_WEIGHT: f64 = 0.8;
+/// 拼音/罗马字转写的权重
+const ROMANIZED_WEIGHT: f64 = 0.6;
+/// 拼音首字母的权重
+const INITIALS_WEIGHT: f64 = 0.4;
+
+/// 视为别名的信息框字段
+const ALIAS_KEYS: [&str; 11] = [
+    "中文名",
+    "简体中文名",
+    "第二中文名",
+    "别名",
+    "英文名",
+    "日文名",
+    "外文名",
+    "原名",
+    "纯假名",
+    "罗马字",
+    "昵称",
+];
+
+/// 去除拼音中的声调并统一为小写，ü记作v
+fn strip_tone(reading: &str) -> String {
+    reading
+        .chars()
+        .filter_map(|c| {
+            Some(match c {
+                'ā' | 'á' | 'ǎ' | 'à' => 'a',
+                'ē' | 'é' | 'ě' | 'è' => 'e',
+                'ī' | 'í' | 'ǐ' | 'ì' => 'i',
+                'ō' | 'ó' | 'ǒ' | 'ò' => 'o',
+                'ū' | 'ú' | 'ǔ' | 'ù' => 'u',
+                'ü' | 'ǖ' | 'ǘ' | 'ǚ' | 'ǜ' => 'v',
+                'ń' | 'ň' | 'ǹ' => 'n',
+                'ḿ' => 'm',
+                c if c.is_ascii_alphabetic() => c.to_ascii_lowercase(),
+                _ => return None,
+            })
+        })
+        .collect()
+}
+
+impl PinyinTable {
+    /// 创建空的拼音表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 解析拼音数据
+    ///
+    /// 支持两种逐行格式，以`#`开头的行被忽略：
+    /// - Unihan格式：`U+4E2D kMandarin zhōng`，字段以制表符分隔（其他字段的行被跳过）
+    /// - 简单格式：`中 zhong1`
+    ///
+    /// 多音字仅保留最先出现的读音
+    pub fn parse(text: &str) -> Self {
+        let mut table = Self::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let (Some(first), Some(second)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let entry = match first.strip_prefix("U+") {
+                Some(code) if second == "kMandarin" => u32::from_str_radix(code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .zip(fields.next()),
+                Some(_) => None,
+                None => {
+                    let mut chars = first.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Some((c, second)),
+                        _ => None,
+                    }
+                }
+            };
+            if let Some((c, reading)) = entry
+                && !table.readings.contains_key(&c)
+            {
+                table.insert(c, reading);
+            }
+        }
+        table
+    }
+
+    /// 添加或覆盖一个汉字的读音
+    pub fn insert(&mut self, c: char, reading: &str) {
+        let reading = strip_tone(reading);
+        if !reading.is_empty() {
+            self.readings.insert(c, reading);
+        }
+    }
+
+    /// 查询汉字的拼音
+    pub fn get(&self, c: char) -> Option<&str> {
+        self.readings.get(&c).map(String::as_str)
+    }
+
+    /// 收录的汉字数量
+    pub fn len(&self) -> usize {
+        self.readings.len()
+    }
+
+    /// 是否为空表
+    pub fn is_empty(&self) -> bool {
+        self.readings.is_empty()
+    }
+}
+
+/// 规范化文本
+///
+/// 全角字母数字转为半角，统一为小写，片假名转为平假名，
+/// 标点与符号替换为空白（长音符`ー`保留）
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            let c = match c {
+                '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+                '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+                _ => c,
+            };
+            if c.is_alphanumeric() || c == 'ー' {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect()
+}
+
+/// 平假名的罗马字（平文式）
+fn kana_romaji(c: char) -> Option<&'static str> {
+    Some(match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' => "e",
+        'お' | 'ぉ' => "o",
+        'か' | 'ゕ' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' | 'ゖ' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' | 'ゎ' => "wa",
+        'ゐ' => "i",
+        'ゑ' => "e",
+        'を' => "o",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    })
+}
+
+/// 拗音（如`きゃ`）的辅音部分
+fn youon_consonant(c: char) -> Option<&'static str> {
+    Some(match c {
+        'き' => "ky",
+        'ぎ' => "gy",
+        'し' => "sh",
+        'じ' | 'ぢ' => "j",
+        'ち' => "ch",
+        'に' => "ny",
+        'ひ' => "hy",
+        'び' => "by",
+        'ぴ' => "py",
+        'み' => "my",
+        'り' => "ry",
+        _ => return None,
+    })
+}
+
+/// 将规范化后的文本转写为拼音/罗马字
+///
+/// # 返回
+/// 返回完整转写与拼音首字母；文本中没有可转写的字符时返回None，
+/// 没有汉字时首字母为None
+fn romanize(text: &str, pinyin: &PinyinTable) -> Option<(String, Option<String>)> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut full = String::new();
+    let mut initials = String::new();
+    let (mut kana, mut hanzi) = (false, false);
+    let mut sokuon = false;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let next = chars.get(index + 1).copied();
+        index += 1;
+
+        if let Some(reading) = pinyin.get(c) {
+            hanzi = true;
+            full.push_str(reading);
+            initials.extend(reading.chars().next());
+            continue;
+        }
+        if c == 'っ' {
+            kana = true;
+            sokuon = true;
+            continue;
+        }
+        if c == 'ー' {
+            kana = true;
+            continue;
+        }
+
+        let romaji = match (youon_consonant(c), next) {
+            (Some(consonant), Some(small @ ('ゃ' | 'ゅ' | 'ょ'))) => {
+                index += 1;
+                kana_romaji(small).map(|vowel| format!("{consonant}{}", &vowel[1..]))
+            }
+            _ => kana_romaji(c).map(str::to_string),
+        };
+        match romaji {
+            Some(romaji) => {
+                kana = true;
+                if sokuon {
+                    full.extend(romaji.chars().next());
+                }
+                full.push_str(&romaji);
+            }
+            None => {
+                full.push(c);
+                initials.push(c);
+            }
+        }
+        sokuon = false;
+    }
+
+    (kana || hanzi).then(|| (full, hanzi.then_some(initials)))
+}
+
+/// 取出信息框中的别名
+fn infobox_aliases(infobox: &[InfoBoxItem]) -> Vec<String> {
+    let mut aliases = Vec::new();
+    for item in infobox
+        .iter()
+        .filter(|item| ALIAS_KEYS.contains(&item.key.as_str()))
+    {
+        match &item.value {
+            serde_json::Value::String(value) => aliases.push(value.clone()),
+            serde_json::Value::Array(values) => aliases.extend(
+                values
+                    .iter()
+                    .filter_map(|value| value.get("v").and_then(|v| v.as_str()))
+                    .map(str::to_string),
+            ),
+            _ => {}
+        }
+    }
+    aliases
+}
+
+/// 由名称与别名生成检索键
+fn build_keys<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    aliases: &[String],
+    pinyin: &PinyinTable,
+) -> Vec<SearchKey> {
+    let mut keys: Vec<SearchKey> = Vec::new();
+    let mut push = |text: String, weight: f64| {
+        if text.is_empty() {
+            return;
+        }
+        match keys.iter_mut().find(|key| key.text == text) {
+            Some(key) => key.weight = key.weight.max(weight),
+            None => keys.push(SearchKey { text, weight }),
+        }
+    };
+
+    let sources = names
+        .into_iter()
+        .map(|name| (name, NAME_WEIGHT))
+        .chain(aliases.iter().map(|alias| (alias.as_str(), ALIAS_WEIGHT)));
+    for (source, weight) in sources {
+        let normalized = normalize(source);
+        if let Some((full, initials)) = romanize(&normalized, pinyin) {
+            push(full, ROMANIZED_WEIGHT * weight);
+            if let Some(initials) = initials {
+                push(initials, INITIALS_WEIGHT * weight);
+            }
+        }
+        push(normalized.split_whitespace().collect(), weight);
+    }
+    keys
+}
+
+/// 文本中的单字与相邻双字
+fn grams(text: &str) -> impl Iterator<Item = (char, char)> + '_ {
+    let chars: Vec<char> = text.chars().collect();
+    let unigrams: Vec<(char, char)> = chars.iter().map(|c| (*c, '\0')).collect();
+    let bigrams: Vec<(char, char)> = chars.windows(2).map(|pair| (pair[0], pair[1])).collect();
+    unigrams.into_iter().chain(bigrams)
+}
+
+/// 查询词用于筛选候选的索引项：长度不小于2时使用双字，否则使用单字
+fn query_grams(word: &str) -> Vec<(char, char)> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() >= 2 {
+        chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    } else {
+        chars.iter().map(|c| (*c, '\0')).collect()
+    }
+}
+
+impl TextIndex {
+    /// 添加文档并返回其序号
+    pub(crate) fn add(&mut self, keys: Vec<SearchKey>) -> u32 {
+        let id = self.keys.len() as u32;
+        let unique: HashSet<(char, char)> = keys.iter().flat_map(|key| grams(&key.text)).collect();
+        for gram in unique {
+            self.grams.entry(gram).or_default().push(id);
+        }
+        self.keys.push(keys);
+        id
+    }
+
+    /// 检索文档
+    ///
+    /// 查询按空白切分为多个词，每个词都需要是某个检索键的子串。
+    /// 完全相同计3分、前缀计2分、子串计1分，乘以检索键权重后取最大值累加；
+    /// 去除空白后的整个查询与检索键完全相同时额外加分
+    ///
+    /// # 返回
+    /// 返回(文档序号, 匹配度)，查询为空时返回全部文档且匹配度为0
+    pub(crate) fn search(&self, query: &str) -> Vec<(u32, f64)> {
+        let normalized = normalize(query);
+        let words: Vec<&str> = normalized.split_whitespace().collect();
+        if words.is_empty() {
+            return (0..self.keys.len() as u32).map(|id| (id, 0.0)).collect();
+        }
+        let whole: String = words.concat();
+
+        let mut candidates: Option<&Vec<u32>> = None;
+        for gram in words.iter().flat_map(|word| query_grams(word)) {
+            let Some(postings) = self.grams.get(&gram) else {
+                return Vec::new();
+            };
+            if candidates.is_none_or(|current| postings.len() < current.len()) {
+                candidates = Some(postings);
+            }
+        }
+
+        candidates
+            .into_iter()
+            .flatten()
+            .filter_map(|id| {
+                let keys = &self.keys[*id as usize];
+                let mut score = 0.0;
+                for word in &words {
+                    let best = keys
+                        .iter()
+                        .map(|key| {
+                            let level = if key.text == *word {
+                                3.0
+                            } else if key.text.starts_with(word) {
+                                2.0
+                            } else if key.text.contains(word) {
+                                1.0
+                            } else {
+                                0.0
+                            };
+                            level * key.weight
+                        })
+                        .fold(0.0, f64::max);
+                    if best == 0.0 {
+                        return None;
+                    }
+                    score += best;
+                }
+                if words.len() > 1
+                    && let Some(key) = keys.iter().find(|key| key.text == whole)
+                {
+                    score += 3.0 * key.weight;
+                }
+                Some((*id, score))
+            })
+            .collect()
+    }
+}
+
+/// 判断比较条件是否成立
+///
+/// 条件形如`>=2020-01-01`、`<8`，支持`>=`、`<=`、`>`、`<`、`=`，省略运算符时视为`=`
+fn condition_holds(condition: &str, compare: impl Fn(&str) -> Option<Ordering>) -> bool {
+    let condition = condition.trim();
+    let (operator, value) = [">=", "<=", ">", "<", "="]
+        .iter()
+        .find_map(|operator| {
+            condition
+                .strip_prefix(operator)
+                .map(|value| (*operator, value.trim()))
+        })
+        .unwrap_or(("=", condition));
+    let Some(ordering) = compare(value) else {
+        return false;
+    };
+    match operator {
+        ">=" => ordering != Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        "<" => ordering == Ordering::Less,
+        _ => ordering == Ordering::Equal,
+    }
+}
+
+/// 比较数值条件
+fn number_holds(actual: f64, condition: &str) -> bool {
+    condition_holds(condition, |value| {
+        actual.partial_cmp(&value.parse::<f64>().ok()?)
+    })
+}
+
+/// 比较日期条件，按条件值的长度截取日期，`>=2020-07`、`=2020`等均按前缀比较
+fn date_holds(date: &str, condition: &str) -> bool {
+    condition_holds(condition, |value| {
+        Some(date.get(..value.len()).unwrap_or(date).cmp(value))
+    })
+}
+
+/// 条目的收藏总人数
+fn heat(subject: &Subject) -> u32 {
+    let collection = &subject.collection;
+    [
+        collection.wish,
+        collection.collect,
+        collection.doing,
+        collection.on_hold,
+        collection.dropped,
+    ]
+    .into_iter()
+    .flatten()
+    .sum()
+}
+
+/// 判断条目是否满足筛选条件
+fn subject_matches(subject: &Subject, search: &SubjectSearch) -> bool {
+    let Some(filter) = &search.filter else {
+        return true;
+    };
+    if !filter.r#type.is_empty() && !filter.r#type.contains(&subject.r#type) {
+        return false;
+    }
+    if subject.nsfw != filter.nsfw {
+        return false;
+    }
+    if !filter
+        .meta_tags
+        .iter()
+        .all(|tag| subject.meta_tags.contains(tag))
+    {
+        return false;
+    }
+    if !filter
+        .tag
+        .iter()
+        .all(|tag| subject.tags.iter().any(|item| item.name == *tag))
+    {
+        return false;
+    }
+    if !filter.air_date.is_empty() {
+        let Some(date) = subject.date.as_deref().filter(|date| !date.is_empty()) else {
+            return false;
+        };
+        if !filter
+            .air_date
+            .iter()
+            .all(|condition| date_holds(date, condition))
+        {
+            return false;
+        }
+    }
+    if !filter
+        .rating
+        .iter()
+        .all(|condition| number_holds(subject.rating.score, condition))
+    {
+        return false;
+    }
+    if !filter.rank.is_empty()
+        && (subject.rating.rank == 0
+            || !filter
+                .rank
+                .iter()
+                .all(|condition| number_holds(subject.rating.rank as f64, condition)))
+    {
+        return false;
+    }
+    true
+}
+
+/// 截取分页结果
+fn paginate<T: Clone>(items: Vec<&T>, limit: Option<u32>, offset: Option<u32>) -> Paged<T> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let offset = offset.unwrap_or_default();
+    Paged {
+        total: items.len() as u32,
+        limit,
+        offset,
+        data: Some(
+            items
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .cloned()
+                .collect(),
+        ),
+    }
+}
+
+impl SearchIndex {
+    /// 创建使用指定拼音表的空索引
+    pub fn new(pinyin: PinyinTable) -> Self {
+        SearchIndex {
+            pinyin,
+            ..Self::default()
+        }
+    }
+
+    /// 添加条目
+    pub fn add_subject(&mut self, subject: Subject) {
+        let aliases = infobox_aliases(&subject.infobox);
+        let keys = build_keys(
+            [subject.name.as_str(), subject.name_cn.as_str()],
+            &aliases,
+            &self.pinyin,
+        );
+        self.subject_index.add(keys);
+        self.subjects.push(subject);
+    }
+
+    /// 添加角色
+    pub fn add_character(&mut self, character: Character) {
+        let aliases = infobox_aliases(&character.infobox);
+        let keys = build_keys([character.name.as_str()], &aliases, &self.pinyin);
+        self.character_index.add(keys);
+        self.characters.push(character);
+    }
+
+    /// 添加人物
+    pub fn add_person(&mut self, person: PersonDetail) {
+        let infobox: Vec<InfoBoxItem> = person
+            .infobox
+            .iter()
+            .filter_map(|item| serde_json::from_value(item.clone()).ok())
+            .collect();
+        let aliases = infobox_aliases(&infobox);
+        let keys = build_keys([person.name.as_str()], &aliases, &self.pinyin);
+        self.person_index.add(keys);
+        self.persons.push(person);
+    }
+
+    /// 已索引的条目、角色与人物数量
+    pub fn len(&self) -> usize {
+        self.subjects.len() + self.characters.len() + self.persons.len()
+    }
+
+    /// 索引是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 搜索条目
+    ///
+    /// 参数与[`BangumiClient::search_subjects`](crate::common::model::BangumiClient::search_subjects)相同。
+    /// 筛选条件中`nsfw`为true时仅返回NSFW条目，为false时仅返回非NSFW条目；
+    /// `air_date`、`rating`、`rank`支持`>=`、`<=`、`>`、`<`、`=`条件，同一字段的多个条件需同时满足。
+    /// 未指定排序时按匹配度排序，匹配度相同时按收藏人数降序
+    pub fn search_subjects(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        payload: Option<SubjectSearch>,
+    ) -> Paged<Subject> {
+        let search = payload.unwrap_or(SubjectSearch {
+            keyword: String::new(),
+            sort: None,
+            filter: None,
+        });
+        let mut hits: Vec<(&Subject, f64)> = self
+            .subject_index
+            .search(&search.keyword)
+            .into_iter()
+            .map(|(id, score)| (&self.subjects[id as usize], score))
+            .filter(|(subject, _)| subject_matches(subject, &search))
+            .collect();
+
+        let by_match = |a: &(&Subject, f64), b: &(&Subject, f64)| {
+            b.1.total_cmp(&a.1)
+                .then(heat(b.0).cmp(&heat(a.0)))
+                .then(a.0.id.cmp(&b.0.id))
+        };
+        match search.sort.unwrap_or(SubjectSearchSort::Match) {
+            SubjectSearchSort::Match => hits.sort_by(by_match),
+            SubjectSearchSort::Heat => {
+                hits.sort_by(|a, b| heat(b.0).cmp(&heat(a.0)).then(by_match(a, b)))
+            }
+            SubjectSearchSort::Rank => hits.sort_by(|a, b| {
+                let rank = |subject: &Subject| match subject.rating.rank {
+                    0 => u32::MAX,
+                    rank => rank,
+                };
+                rank(a.0).cmp(&rank(b.0)).then(by_match(a, b))
+            }),
+            SubjectSearchSort::Score => hits.sort_by(|a, b| {
+                b.0.rating
+                    .score
+                    .total_cmp(&a.0.rating.score)
+                    .then(by_match(a, b))
+            }),
+        }
+
+        paginate(
+            hits.into_iter().map(|(subject, _)| subject).collect(),
+            limit,
+            offset,
+        )
+    }
+
+    /// 搜索角色
+    ///
+    /// 参数与[`BangumiClient::search_characters`](crate::common::model::BangumiClient::search_characters)相同。
+    /// 按匹配度排序，匹配度相同时按收藏人数降序
+    pub fn search_characters(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        payload: Option<CharacterSearch>,
+    ) -> Paged<Character> {
+        let keyword = payload.as_ref().map(|search| search.keyword.as_str());
+        let nsfw = payload
+            .as_ref()
+            .and_then(|search| search.filter.as_ref())
+            .and_then(|filter| filter.nsfw);
+        let mut hits: Vec<(&Character, f64)> = self
+            .character_index
+            .search(keyword.unwrap_or_default())
+            .into_iter()
+            .map(|(id, score)| (&self.characters[id as usize], score))
+            .filter(|(character, _)| nsfw.is_none_or(|nsfw| character.nsfw == nsfw))
+            .collect();
+        hits.sort_by(|a, b| {
+            b.1.total_cmp(&a.1)
+                .then(b.0.stat.collects.cmp(&a.0.stat.collects))
+                .then(a.0.id.cmp(&b.0.id))
+        });
+        paginate(
+            hits.into_iter().map(|(character, _)| character).collect(),
+            limit,
+            offset,
+        )
+    }
+
+    /// 搜索人物
+    ///
+    /// 参数与[`BangumiClient::search_persons`](crate::common::model::BangumiClient::search_persons)相同，
+    /// 职业筛选满足任一职业即可。按匹配度排序，匹配度相同时按收藏人数降序
+    pub fn search_persons(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        payload: Option<PersonSearch>,
+    ) -> Paged<PersonDetail> {
+        let keyword = payload.as_ref().map(|search| search.keyword.as_str());
+        let careers = payload
+            .as_ref()
+            .and_then(|search| search.filter.as_ref())
+            .and_then(|filter| filter.career.as_ref())
+            .filter(|careers| !careers.is_empty());
+        let mut hits: Vec<(&PersonDetail, f64)> = self
+            .person_index
+            .search(keyword.unwrap_or_default())
+            .into_iter()
+            .map(|(id, score)| (&self.persons[id as usize], score))
+            .filter(|(person, _)| {
+                careers.is_none_or(|careers| {
+                    person.career.iter().any(|career| {
+                        serde_json::to_value(career)
+                            .ok()
+                            .and_then(|value| value.as_str().map(str::to_string))
+                            .is_some_and(|career| careers.contains(&career))
+                    })
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.1.total_cmp(&a.1)
+                .then(b.0.stat.collects.cmp(&a.0.stat.collects))
+                .then(a.0.id.cmp(&b.0.id))
+        });
+        paginate(
+            hits.into_iter().map(|(person, _)| person).collect(),
+            limit,
+            offset,
+        )
+    }
+}
+
+/// 由多个条目构建仅包含条目的索引（不使用拼音表）
+impl FromIterator<Subject> for SearchIndex {
+    fn from_iter<I: IntoIterator<Item = Subject>>(iter: I) -> Self {
+        let mut index = SearchIndex::default();
+        for subject in iter {
+            index.add_subject(subject);
+        }
+        index
+    }
+}
+
+#[cfg(feature = "store")]
+mod load {
+    use anyhow::Result;
+    use serde::de::DeserializeOwned;
+
+    use crate::module::{search::model::SearchIndex, store::model::Store};
+
+    /// 读取实体表中的全部原始JSON
+    fn read_all<T: DeserializeOwned>(store: &Store, table: &str) -> Result<Vec<T>> {
+        let raws: Vec<String> = store.with_connection(|connection| {
+            let mut statement =
+                connection.prepare(&format!("SELECT raw FROM {table} ORDER BY id"))?;
+            statement.query_map([], |row| row.get(0))?.collect()
+        })?;
+        raws.iter()
+            .map(|raw| Ok(serde_json::from_str(raw)?))
+            .collect()
+    }
+
+    impl SearchIndex {
+        /// 将本地镜像中的全部条目、角色与人物加入索引
+        pub fn load_store(&mut self, store: &Store) -> Result<()> {
+            for subject in read_all(store, "subjects")? {
+                self.add_subject(subject);
+            }
+            for character in read_all(store, "characters")? {
+                self.add_character(character);
+            }
+            for person in read_all(store, "persons")? {
+                self.add_person(person);
+            }
+            Ok(())
+        }
+    }
+}
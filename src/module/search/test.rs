@@ -0,0 +1,260 @@
+use crate::{
+    common::fixture::{self, parse_all},
+    module::{
+        character::model::{Character, CharacterFilter, CharacterSearch},
+        person::model::{PersonDetail, PersonFilter, PersonSearch},
+        search::{
+            model::{PinyinTable, SearchIndex},
+            service::normalize,
+        },
+        subject::model::{
+            Subject, SubjectSearch, SubjectSearchFilter, SubjectSearchSort, SubjectType,
+        },
+    },
+};
+
+const PINYIN: &str = "# 测试用拼音表
+U+8FDB\tkMandarin\tjìn
+U+8FDB\tkDefinition\tadvance
+击 ji1
+的 de5
+巨 jù
+人 rén
+";
+
+fn index() -> SearchIndex {
+    let subjects: Vec<Subject> = parse_all(
+        [
+            fixture::subject(1)
+                .named("進撃の巨人", "进击的巨人")
+                .with("collection.wish", 1)
+                .aliases(&["Attack on Titan"])
+                .with("date", "2013-04-07")
+                .with("rating.score", 8.2)
+                .with("rating.rank", 100),
+            fixture::subject(2)
+                .named("進撃の巨人 Season 2", "进击的巨人 第二季")
+                .with("collection.wish", 2)
+                .with("date", "2017-04-01")
+                .with("rating.score", 7.9)
+                .with("rating.rank", 300),
+            fixture::subject(3)
+                .named("カウボーイビバップ", "星际牛仔")
+                .with("collection.wish", 3)
+                .aliases(&["Cowboy Bebop"])
+                .with("date", "1998-04-03")
+                .with("rating.score", 9.1)
+                .with("rating.rank", 5),
+            fixture::subject(4)
+                .named("ラーメン大好き小泉さん", "")
+                .with("collection.wish", 4)
+                .with("date", "2018-01-04")
+                .with("rating.score", 6.0),
+        ]
+        .into_iter()
+        .map(|subject| subject.with("meta_tags", ["TV"]).tags("tags", &["热血"])),
+    );
+    let mut index = SearchIndex::new(PinyinTable::parse(PINYIN));
+    for subject in subjects {
+        index.add_subject(subject);
+    }
+    index
+}
+
+fn subject_ids(index: &SearchIndex, keyword: &str) -> Vec<u32> {
+    index
+        .search_subjects(
+            None,
+            None,
+            Some(SubjectSearch {
+                keyword: keyword.to_string(),
+                sort: None,
+                filter: None,
+            }),
+        )
+        .data
+        .unwrap()
+        .iter()
+        .map(|subject| subject.id)
+        .collect()
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(normalize("ＡＢＣ・カウボーイ"), "abc かうぼーい");
+    let table = PinyinTable::parse(PINYIN);
+    assert_eq!(table.len(), 5);
+    assert_eq!(table.get('进'), Some("jin"));
+    assert_eq!(table.get('击'), Some("ji"));
+    assert_eq!(table.get('的'), Some("de"));
+}
+
+#[test]
+fn test_search_subjects_keyword() {
+    let index = index();
+    assert_eq!(index.len(), 4);
+
+    // 完全匹配优先于前缀匹配
+    assert_eq!(subject_ids(&index, "进击的巨人"), vec![1, 2]);
+    assert_eq!(subject_ids(&index, "巨人 第二季"), vec![2]);
+    // 信息框别名
+    assert_eq!(subject_ids(&index, "attack titan"), vec![1]);
+    // 片假名与平假名、罗马字
+    assert_eq!(subject_ids(&index, "かうぼーい"), vec![3]);
+    assert_eq!(subject_ids(&index, "ＣＯＷＢＯＹ"), vec![3]);
+    assert_eq!(subject_ids(&index, "ramen"), vec![4]);
+    assert_eq!(subject_ids(&index, "kauboi"), vec![3]);
+    // 拼音与拼音首字母
+    assert_eq!(subject_ids(&index, "jinjidejuren"), vec![1, 2]);
+    assert_eq!(subject_ids(&index, "jjdjr"), vec![1, 2]);
+    assert!(subject_ids(&index, "不存在").is_empty());
+    assert_eq!(subject_ids(&index, "").len(), 4);
+}
+
+#[test]
+fn test_search_subjects_filter() {
+    let index = index();
+    let search = |sort: Option<SubjectSearchSort>, filter: SubjectSearchFilter| {
+        index
+            .search_subjects(
+                Some(2),
+                None,
+                Some(SubjectSearch {
+                    keyword: String::new(),
+                    sort,
+                    filter: Some(filter),
+                }),
+            )
+            .data
+            .unwrap()
+            .iter()
+            .map(|subject| subject.id)
+            .collect::<Vec<u32>>()
+    };
+    let filter = SubjectSearchFilter {
+        r#type: vec![SubjectType::Anime],
+        meta_tags: vec!["TV".to_string()],
+        tag: vec!["热血".to_string()],
+        air_date: vec![">=2013".to_string(), "<2018-01".to_string()],
+        rating: vec![],
+        rank: vec![],
+        nsfw: false,
+    };
+    assert_eq!(search(None, filter.clone()), vec![2, 1]);
+
+    let rated = SubjectSearchFilter {
+        air_date: vec![],
+        rating: vec![">=8".to_string()],
+        ..filter.clone()
+    };
+    assert_eq!(search(Some(SubjectSearchSort::Score), rated), vec![3, 1]);
+    let ranked = SubjectSearchFilter {
+        air_date: vec![],
+        rank: vec!["<=300".to_string()],
+        ..filter.clone()
+    };
+    assert_eq!(search(Some(SubjectSearchSort::Rank), ranked), vec![3, 1]);
+    let books = SubjectSearchFilter {
+        r#type: vec![SubjectType::Book],
+        ..filter
+    };
+    assert!(search(None, books).is_empty());
+
+    let page = index.search_subjects(Some(1), Some(1), None);
+    assert_eq!(page.total, 4);
+    assert_eq!(page.data.unwrap().len(), 1);
+}
+
+#[test]
+fn test_search_characters_and_persons() {
+    let character: Character = serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "name": "エレン・イェーガー",
+        "type": 1,
+        "images": {"large": "", "medium": "", "small": "", "grid": ""},
+        "summary": "",
+        "locked": false,
+        "infobox": [{"key": "简体中文名", "value": "艾伦·耶格尔"}],
+        "gender": "male",
+        "blood_type": null,
+        "birth_year": null,
+        "birth_mon": 3,
+        "birth_day": 30,
+        "stat": {"comments": 0, "collects": 0},
+        "nsfw": false
+    }))
+    .unwrap();
+    let person: PersonDetail = serde_json::from_value(serde_json::json!({
+        "id": 2,
+        "name": "梶裕貴",
+        "type": 1,
+        "career": ["seiyu"],
+        "images": {"large": "", "medium": "", "small": "", "grid": ""},
+        "summary": "",
+        "locked": false,
+        "last_modified": "",
+        "infobox": [{"key": "纯假名", "value": "かじ ゆうき"}],
+        "gender": "male",
+        "blood_type": null,
+        "birth_year": null,
+        "birth_mon": null,
+        "birth_day": null,
+        "stat": {"comments": 0, "collects": 0}
+    }))
+    .unwrap();
+    let mut index = SearchIndex::default();
+    index.add_character(character);
+    index.add_person(person);
+
+    let characters = index.search_characters(
+        None,
+        None,
+        Some(CharacterSearch {
+            keyword: "艾伦".to_string(),
+            filter: Some(CharacterFilter { nsfw: Some(false) }),
+        }),
+    );
+    assert_eq!(characters.total, 1);
+    let characters = index.search_characters(
+        None,
+        None,
+        Some(CharacterSearch {
+            keyword: "eren".to_string(),
+            filter: Some(CharacterFilter { nsfw: Some(true) }),
+        }),
+    );
+    assert_eq!(characters.total, 0);
+
+    let persons = |career: &str| {
+        index
+            .search_persons(
+                None,
+                None,
+                Some(PersonSearch {
+                    keyword: "kaji yuuki".to_string(),
+                    filter: Some(PersonFilter {
+                        career: Some(vec![career.to_string()]),
+                    }),
+                }),
+            )
+            .total
+    };
+    assert_eq!(persons("seiyu"), 1);
+    assert_eq!(persons("writer"), 0);
+}
+
+#[cfg(feature = "store")]
+#[test]
+fn test_load_store() {
+    use crate::module::store::model::Store;
+
+    let store = Store::open_in_memory().unwrap();
+    let subject: Subject = fixture::subject(3)
+        .named("カウボーイビバップ", "星际牛仔")
+        .parse();
+    store.upsert_subject(&subject).unwrap();
+
+    let mut index = SearchIndex::default();
+    index.load_store(&store).unwrap();
+    assert_eq!(subject_ids(&index, "牛仔"), vec![3]);
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use crate::module::{collection::model::CollectionStats, subject::model::SubjectAnimeCategory};
+
+/// 新番表中的单个条目
+///
+/// 汇总条目的基本信息、评分、排名与收藏统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonChartEntry {
+    /// 条目ID
+    pub id: u32,
+    /// 条目原名
+    pub name: String,
+    /// 条目中文名
+    pub name_cn: String,
+    /// 放送日期（格式通常为YYYY-MM-DD，可能为空）
+    pub date: Option<String>,
+    /// 平台（如TV、WEB、剧场版）
+    pub platform: String,
+    /// 话数
+    pub eps: u32,
+    /// 封面图片链接
+    pub image: String,
+    /// 平均评分
+    pub score: f64,
+    /// 评分人数
+    pub votes: u32,
+    /// 排名（未上榜时为None）
+    pub rank: Option<u32>,
+    /// 收藏统计
+    pub collection: CollectionStats,
+    /// 标签（按标注人数降序，最多5个）
+    pub tags: Vec<String>,
+}
+
+/// 新番表中的一个分类
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonChartGroup {
+    /// 动画分类
+    pub category: SubjectAnimeCategory,
+    /// 该分类下的条目（按放送日期排序）
+    pub entries: Vec<SeasonChartEntry>,
+}
+
+/// 季度新番表
+///
+/// 按TV、WEB、OVA、剧场版分组整理某一季度开播的动画
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonChart {
+    /// 年份
+    pub year: u32,
+    /// 季度（1-4，分别对应1月、4月、7月、10月开始的季度）
+    pub quarter: u8,
+    /// 按分类分组的条目
+    pub groups: Vec<SeasonChartGroup>,
+}
@@ -0,0 +1,317 @@
+use std::collections::HashSet;
+
+use anyhow::{Result, bail};
+
+use crate::{
+    common::model::BangumiClient,
+    module::subject::model::{
+        Subject, SubjectAnimeCategory, SubjectBrowseSort, SubjectCategory, SubjectType,
+    },
+};
+
+use super::model::{SeasonChart, SeasonChartEntry, SeasonChartGroup};
+
+/// 每个条目保留的标签数量
+const TAG_LIMIT: usize = 5;
+
+/// 新番表中的分类及其顺序
+const CATEGORIES: [SubjectAnimeCategory; 4] = [
+    SubjectAnimeCategory::Tv,
+    SubjectAnimeCategory::Web,
+    SubjectAnimeCategory::Ova,
+    SubjectAnimeCategory::Movie,
+];
+
+/// 季度包含的月份
+///
+/// # 返回
+/// 季度不在1-4之间时返回None
+pub fn quarter_months(quarter: u8) -> Option<[u32; 3]> {
+    (1..=4).contains(&quarter).then(|| {
+        let first = (quarter as u32 - 1) * 3 + 1;
+        [first, first + 1, first + 2]
+    })
+}
+
+/// 动画分类的显示名称
+fn category_name(category: &SubjectAnimeCategory) -> &'static str {
+    match category {
+        SubjectAnimeCategory::Tv => "TV",
+        SubjectAnimeCategory::Web => "WEB",
+        SubjectAnimeCategory::Ova => "OVA",
+        SubjectAnimeCategory::Movie => "剧场版",
+        SubjectAnimeCategory::Other => "其他",
+    }
+}
+
+/// 转义Markdown表格单元格中的特殊字符
+fn markdown_cell(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(['\r', '\n'], " ")
+}
+
+/// 转义HTML文本
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl From<&Subject> for SeasonChartEntry {
+    fn from(subject: &Subject) -> Self {
+        let mut tags = subject.tags.clone();
+        tags.sort_by_key(|tag| std::cmp::Reverse(tag.count));
+        SeasonChartEntry {
+            id: subject.id,
+            name: subject.name.clone(),
+            name_cn: subject.name_cn.clone(),
+            date: subject.date.clone().filter(|date| !date.is_empty()),
+            platform: subject.platform.clone(),
+            eps: subject.eps,
+            image: subject.images.common.clone(),
+            score: subject.rating.score,
+            votes: subject.rating.total,
+            rank: Some(subject.rating.rank).filter(|rank| *rank > 0),
+            collection: subject.collection.clone(),
+            tags: tags
+                .into_iter()
+                .take(TAG_LIMIT)
+                .map(|tag| tag.name)
+                .collect(),
+        }
+    }
+}
+
+impl SeasonChartEntry {
+    /// 显示用的标题（优先使用中文名）
+    pub fn title(&self) -> &str {
+        if self.name_cn.is_empty() {
+            &self.name
+        } else {
+            &self.name_cn
+        }
+    }
+
+    /// 条目页面链接
+    pub fn url(&self) -> String {
+        format!("https://bgm.tv/subject/{}", self.id)
+    }
+
+    /// 表格中的各列内容：放送日期、话数、评分、排名、想看、在看、看过
+    fn columns(&self) -> [String; 7] {
+        let count = |value: Option<u32>| value.unwrap_or_default().to_string();
+        [
+            self.date.clone().unwrap_or_else(|| "-".to_string()),
+            if self.eps > 0 {
+                self.eps.to_string()
+            } else {
+                "-".to_string()
+            },
+            if self.votes > 0 {
+                format!("{:.1}", self.score)
+            } else {
+                "-".to_string()
+            },
+            self.rank
+                .map(|rank| rank.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            count(self.collection.wish),
+            count(self.collection.doing),
+            count(self.collection.collect),
+        ]
+    }
+}
+
+/// 表头（标题列之后的各列）
+const COLUMN_NAMES: [&str; 7] = ["放送日期", "话数", "评分", "排名", "想看", "在看", "看过"];
+
+/// 整理季度新番表
+///
+/// 按条目ID去重（保留最先出现的分类），按分类分组后按放送日期排序，
+/// 缺失日期的条目排在最后，日期相同时按ID排序
+///
+/// # 参数
+/// - `year`: 年份
+/// - `quarter`: 季度（1-4）
+/// - `subjects`: 条目及其所属分类
+///
+/// # 返回
+/// 返回按TV、WEB、OVA、剧场版顺序分组的新番表，其他分类排在最后
+pub fn build_season_chart(
+    year: u32,
+    quarter: u8,
+    subjects: Vec<(SubjectAnimeCategory, Subject)>,
+) -> SeasonChart {
+    let mut seen = HashSet::new();
+    let mut groups: Vec<SeasonChartGroup> = CATEGORIES
+        .iter()
+        .map(|category| SeasonChartGroup {
+            category: category.clone(),
+            entries: Vec::new(),
+        })
+        .collect();
+
+    for (category, subject) in subjects {
+        if !seen.insert(subject.id) {
+            continue;
+        }
+        let entry = SeasonChartEntry::from(&subject);
+        match groups.iter_mut().find(|group| group.category == category) {
+            Some(group) => group.entries.push(entry),
+            None => groups.push(SeasonChartGroup {
+                category,
+                entries: vec![entry],
+            }),
+        }
+    }
+
+    for group in &mut groups {
+        group.entries.sort_by(|a, b| {
+            match (&a.date, &b.date) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+            .then(a.id.cmp(&b.id))
+        });
+    }
+
+    SeasonChart {
+        year,
+        quarter,
+        groups,
+    }
+}
+
+impl SeasonChart {
+    /// 季度标题，如`2024年1月新番`
+    pub fn title(&self) -> String {
+        let month = quarter_months(self.quarter)
+            .map(|months| months[0])
+            .unwrap_or_default();
+        format!("{}年{}月新番", self.year, month)
+    }
+
+    /// 新番表中的条目总数
+    pub fn len(&self) -> usize {
+        self.groups.iter().map(|group| group.entries.len()).sum()
+    }
+
+    /// 新番表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 生成Markdown格式的新番表
+    ///
+    /// 每个非空分类对应一个二级标题及表格，标题列链接到条目页面
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n", self.title());
+        for group in self.groups.iter().filter(|group| !group.entries.is_empty()) {
+            markdown.push_str(&format!(
+                "\n## {}（{}）\n\n",
+                category_name(&group.category),
+                group.entries.len()
+            ));
+            markdown.push_str(&format!("| 标题 | {} |\n", COLUMN_NAMES.join(" | ")));
+            markdown.push_str(&format!("|---{}|\n", "|---:".repeat(COLUMN_NAMES.len())));
+            for entry in &group.entries {
+                markdown.push_str(&format!(
+                    "| [{}]({}) | {} |\n",
+                    markdown_cell(entry.title()),
+                    entry.url(),
+                    entry
+                        .columns()
+                        .map(|column| markdown_cell(&column))
+                        .join(" | ")
+                ));
+            }
+        }
+        markdown
+    }
+
+    /// 生成HTML格式的新番表
+    ///
+    /// 每个非空分类对应一个`<h2>`标题及`<table>`，不包含样式，可直接嵌入页面
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<section class=\"season-chart\">\n<h1>{}</h1>\n",
+            html_escape(&self.title())
+        );
+        for group in self.groups.iter().filter(|group| !group.entries.is_empty()) {
+            html.push_str(&format!(
+                "<h2>{}（{}）</h2>\n<table>\n<thead><tr><th>标题</th>",
+                category_name(&group.category),
+                group.entries.len()
+            ));
+            for name in COLUMN_NAMES {
+                html.push_str(&format!("<th>{name}</th>"));
+            }
+            html.push_str("</tr></thead>\n<tbody>\n");
+            for entry in &group.entries {
+                html.push_str(&format!(
+                    "<tr><td><a href=\"{}\" title=\"{}\">{}</a></td>",
+                    entry.url(),
+                    html_escape(&entry.name),
+                    html_escape(entry.title())
+                ));
+                for column in entry.columns() {
+                    html.push_str(&format!("<td>{}</td>", html_escape(&column)));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</tbody>\n</table>\n");
+        }
+        html.push_str("</section>\n");
+        html
+    }
+}
+
+impl BangumiClient {
+    /// 生成季度新番表
+    ///
+    /// 按TV、WEB、OVA、剧场版分别浏览该季度三个月开播的动画，合并去重后分组整理。
+    /// 浏览接口返回的条目已包含评分、排名与收藏统计，无需逐个请求详情
+    ///
+    /// # 参数
+    /// - `year`: 年份
+    /// - `quarter`: 季度（1-4，分别对应1月、4月、7月、10月）
+    ///
+    /// # 返回
+    /// 返回分组整理的新番表，季度不合法时返回错误
+    pub async fn season_chart(&self, year: u32, quarter: u8) -> Result<SeasonChart> {
+        let Some(months) = quarter_months(quarter) else {
+            bail!(format!("季度必须在1到4之间: {quarter}"))
+        };
+
+        let mut subjects = Vec::new();
+        for category in CATEGORIES {
+            for month in months {
+                let page = BangumiClient::fetch_all(|limit, offset| {
+                    self.get_subjects(
+                        SubjectType::Anime,
+                        Some(SubjectCategory::Anime(category.clone())),
+                        None,
+                        None,
+                        Some(SubjectBrowseSort::Date),
+                        Some(year),
+                        Some(month),
+                        Some(limit),
+                        Some(offset),
+                    )
+                })
+                .await?;
+                subjects.extend(page.into_iter().map(|subject| (category.clone(), subject)));
+            }
+        }
+
+        Ok(build_season_chart(year, quarter, subjects))
+    }
+}
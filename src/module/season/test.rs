@@ -0,0 +1,168 @@
+use crate::{
+    common::{
+        fixture::{self, Fixture},
+        mock::{MockRoute, MockServer},
+    },
+    module::{
+        season::service::{build_season_chart, quarter_months},
+        subject::model::{Subject, SubjectAnimeCategory},
+    },
+};
+
+fn subject_json(id: u32, name_cn: &str, date: Option<&str>, rank: u32) -> Fixture {
+    fixture::subject(id)
+        .with("name_cn", name_cn)
+        .with("date", date)
+        .with("images.common", format!("https://lain.bgm.tv/{id}.jpg"))
+        .with("rating.rank", rank)
+        .with("rating.total", if rank > 0 { 100 } else { 0 })
+        .with("collection.wish", 10)
+        .with("collection.collect", 20)
+        .with("collection.doing", 30)
+        .with(
+            "tags",
+            serde_json::json!([
+                {"name": "原创", "count": 5, "total_cont": 0},
+                {"name": "TV", "count": 50, "total_cont": 0}
+            ]),
+        )
+}
+
+fn subject(id: u32, name_cn: &str, date: Option<&str>, rank: u32) -> Subject {
+    subject_json(id, name_cn, date, rank).parse()
+}
+
+#[test]
+fn test_quarter_months() {
+    assert_eq!(quarter_months(1), Some([1, 2, 3]));
+    assert_eq!(quarter_months(4), Some([10, 11, 12]));
+    assert_eq!(quarter_months(0), None);
+    assert_eq!(quarter_months(5), None);
+}
+
+#[test]
+fn test_build_season_chart() {
+    let chart = build_season_chart(
+        2024,
+        2,
+        vec![
+            (
+                SubjectAnimeCategory::Tv,
+                subject(3, "C", Some("2024-04-10"), 0),
+            ),
+            (SubjectAnimeCategory::Tv, subject(1, "A", None, 0)),
+            (
+                SubjectAnimeCategory::Tv,
+                subject(2, "B", Some("2024-04-05"), 120),
+            ),
+            (
+                SubjectAnimeCategory::Movie,
+                subject(4, "D", Some("2024-05-01"), 0),
+            ),
+            // 重复出现的条目保留第一次的分类
+            (
+                SubjectAnimeCategory::Web,
+                subject(2, "B", Some("2024-04-05"), 120),
+            ),
+        ],
+    );
+
+    assert_eq!(chart.title(), "2024年4月新番");
+    assert_eq!(chart.len(), 4);
+    let categories: Vec<_> = chart.groups.iter().map(|group| &group.category).collect();
+    assert_eq!(
+        categories,
+        vec![
+            &SubjectAnimeCategory::Tv,
+            &SubjectAnimeCategory::Web,
+            &SubjectAnimeCategory::Ova,
+            &SubjectAnimeCategory::Movie
+        ]
+    );
+
+    let tv: Vec<u32> = chart.groups[0]
+        .entries
+        .iter()
+        .map(|entry| entry.id)
+        .collect();
+    assert_eq!(tv, vec![2, 3, 1]);
+    assert!(chart.groups[1].entries.is_empty());
+    assert_eq!(chart.groups[3].entries[0].id, 4);
+
+    let entry = &chart.groups[0].entries[0];
+    assert_eq!(entry.rank, Some(120));
+    assert_eq!(entry.tags, vec!["TV", "原创"]);
+    assert_eq!(chart.groups[0].entries[1].rank, None);
+}
+
+#[test]
+fn test_season_chart_tables() {
+    let chart = build_season_chart(
+        2024,
+        1,
+        vec![
+            (
+                SubjectAnimeCategory::Tv,
+                subject(1, "[Re] A|B", Some("2024-01-05"), 10),
+            ),
+            (SubjectAnimeCategory::Movie, subject(2, "<电影>", None, 0)),
+        ],
+    );
+
+    let markdown = chart.to_markdown();
+    assert!(markdown.starts_with("# 2024年1月新番\n"));
+    assert!(markdown.contains("## TV（1）"));
+    assert!(markdown.contains("## 剧场版（1）"));
+    assert!(!markdown.contains("## WEB"));
+    assert!(markdown.contains(
+        "| [\\[Re\\] A\\|B](https://bgm.tv/subject/1) | 2024-01-05 | 12 | 7.5 | 10 | 10 | 30 | 20 |"
+    ));
+    assert!(
+        markdown.contains("| [<电影>](https://bgm.tv/subject/2) | - | 12 | - | - | 10 | 30 | 20 |")
+    );
+
+    let html = chart.to_html();
+    assert!(html.contains("<h2>TV（1）</h2>"));
+    assert!(html.contains("&lt;电影&gt;"));
+    assert!(!html.contains("<电影>"));
+    assert_eq!(html.matches("<table>").count(), 2);
+}
+
+#[tokio::test]
+async fn test_season_chart() {
+    let server = MockServer::start(vec![MockRoute::json(
+        "GET",
+        "/v0/subjects",
+        200,
+        serde_json::json!({
+            "total": 2,
+            "limit": 100,
+            "offset": 0,
+            "data": [
+                subject_json(2, "B", Some("2024-07-08"), 0),
+                subject_json(1, "A", Some("2024-07-01"), 0)
+            ]
+        }),
+    )]);
+    let client = server.client();
+
+    let chart = client.season_chart(2024, 3).await.unwrap();
+    assert_eq!(chart.len(), 2);
+    let tv: Vec<u32> = chart.groups[0]
+        .entries
+        .iter()
+        .map(|entry| entry.id)
+        .collect();
+    assert_eq!(tv, vec![1, 2]);
+
+    // 四个分类各请求三个月
+    let requests = server.requests();
+    assert_eq!(requests.len(), 12);
+    assert!(requests[0].query.contains("cat=1"));
+    assert!(requests[0].query.contains("year=2024"));
+    assert!(requests[0].query.contains("month=7"));
+    assert!(requests[11].query.contains("cat=3"));
+    assert!(requests[11].query.contains("month=9"));
+
+    assert!(client.season_chart(2024, 5).await.is_err());
+}
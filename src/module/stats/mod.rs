@@ -0,0 +1,6 @@
+/// 用户收藏统计
+pub mod model;
+pub mod service;
+
+#[cfg(all(test, any(feature = "reqwest", feature = "ureq")))]
+pub mod test;
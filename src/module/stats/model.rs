@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use crate::module::{collection::model::CollectionType, subject::model::SubjectType};
+
+/// 按类型统计的数量
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeCount<T> {
+    /// 类型
+    pub r#type: T,
+    /// 数量
+    pub count: u32,
+}
+
+/// 标签出现次数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagCount {
+    /// 标签名
+    pub name: String,
+    /// 出现在多少个收藏条目中
+    pub count: u32,
+}
+
+/// 评分统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatingStats {
+    /// 1-10分各分数的打分次数（下标0对应1分）
+    pub histogram: [u32; 10],
+    /// 打过分的条目数
+    pub rated: u32,
+    /// 用户的平均打分
+    pub mean: Option<f64>,
+    /// 打过分的条目的站内平均分（忽略暂无站内评分的条目）
+    pub site_mean: Option<f64>,
+    /// 用户打分与站内评分之差的平均值，正数表示用户打分偏高
+    pub mean_deviation: Option<f64>,
+}
+
+/// 观看时长统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchTime {
+    /// 已观看的集数
+    pub episodes: u32,
+    /// 估算的总时长（秒）
+    pub seconds: u64,
+    /// 缺少时长信息、按默认时长估算的集数
+    pub estimated_episodes: u32,
+}
+
+/// 某一年的收藏活动
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearActivity {
+    /// 年份（取自收藏的最后更新时间）
+    pub year: i32,
+    /// 当年更新的收藏数
+    pub count: u32,
+    /// 当年标记为看过的收藏数
+    pub done: u32,
+    /// 当年更新的收藏中打过分的平均分
+    pub mean_rate: Option<f64>,
+}
+
+/// 用户收藏统计
+///
+/// 可直接序列化为JSON供统计面板或年度总结使用
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserStats {
+    /// 用户名
+    pub username: String,
+    /// 收藏总数
+    pub total: u32,
+    /// 评分统计
+    pub rating: RatingStats,
+    /// 各收藏状态的数量
+    pub collection_types: Vec<TypeCount<CollectionType>>,
+    /// 各条目类型的数量
+    pub subject_types: Vec<TypeCount<SubjectType>>,
+    /// 最常见的条目标签
+    pub top_tags: Vec<TagCount>,
+    /// 动画与三次元的观看时长
+    pub watch_time: WatchTime,
+    /// 按年份统计的收藏活动（按年份升序）
+    pub yearly: Vec<YearActivity>,
+}
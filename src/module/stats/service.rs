@@ -0,0 +1,280 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+
+use crate::{
+    common::model::BangumiClient,
+    module::{
+        collection::model::{CollectionSubject, CollectionType},
+        episode::model::{Episode, EpisodeType},
+        subject::model::SubjectType,
+    },
+};
+
+use super::model::{RatingStats, TagCount, TypeCount, UserStats, WatchTime, YearActivity};
+
+/// 统计结果中保留的标签数量
+const TOP_TAG_LIMIT: usize = 20;
+
+/// 每个条目参与统计的标签数量（条目标签按标注人数降序）
+const SUBJECT_TAG_LIMIT: usize = 10;
+
+/// 缺少时长信息时每集的默认时长（秒）
+pub const DEFAULT_EPISODE_SECONDS: u32 = 24 * 60;
+
+/// 判断条目类型是否按剧集统计观看时长
+fn is_watchable(subject_type: &SubjectType) -> bool {
+    matches!(subject_type, SubjectType::Anime | SubjectType::Real)
+}
+
+/// 收藏中已观看的集数
+///
+/// 优先使用观看进度，标记为看过但未记录进度时使用条目总集数
+pub fn watched_episodes(item: &CollectionSubject) -> u32 {
+    if item.ep_status > 0 {
+        return item.ep_status;
+    }
+    match (&item.r#type, &item.subject) {
+        (CollectionType::Done, Some(subject)) => subject.eps,
+        _ => 0,
+    }
+}
+
+/// 估算观看前若干集所花费的时长
+///
+/// 按排序取前`watched`集正片累加`duration_seconds`，缺少时长的剧集使用已知时长的平均值，
+/// 整个条目都缺少时长时使用[`DEFAULT_EPISODE_SECONDS`]
+///
+/// # 返回
+/// 返回总时长（秒）以及按估算时长计入的集数
+pub fn estimate_watch_seconds(watched: u32, episodes: &[Episode]) -> (u64, u32) {
+    let mut normal: Vec<&Episode> = episodes
+        .iter()
+        .filter(|episode| episode.r#type == EpisodeType::Normal)
+        .collect();
+    normal.sort_by(|a, b| a.sort.total_cmp(&b.sort));
+
+    let known: Vec<u64> = normal
+        .iter()
+        .map(|episode| episode.duration_seconds as u64)
+        .filter(|seconds| *seconds > 0)
+        .collect();
+    let fallback = if known.is_empty() {
+        DEFAULT_EPISODE_SECONDS as u64
+    } else {
+        known.iter().sum::<u64>() / known.len() as u64
+    };
+
+    let mut seconds = 0;
+    let mut estimated = 0;
+    for index in 0..watched as usize {
+        match normal.get(index).map(|episode| episode.duration_seconds) {
+            Some(duration) if duration > 0 => seconds += duration as u64,
+            _ => {
+                seconds += fallback;
+                estimated += 1;
+            }
+        }
+    }
+    (seconds, estimated)
+}
+
+/// 从时间字符串中取出年份
+fn parse_year(value: &str) -> Option<i32> {
+    value.trim().get(..4)?.parse().ok()
+}
+
+/// 计算平均值，没有数据时返回None
+fn mean(sum: f64, count: u32) -> Option<f64> {
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// 计算用户收藏统计
+///
+/// # 参数
+/// - `username`: 用户名
+/// - `collections`: 用户的全部条目收藏
+/// - `episodes`: 条目ID到剧集列表的映射，用于计算观看时长；缺失的条目按默认时长估算
+///
+/// # 返回
+/// 返回可序列化的统计结果
+pub fn compute_stats(
+    username: &str,
+    collections: &[CollectionSubject],
+    episodes: &HashMap<u32, Vec<Episode>>,
+) -> UserStats {
+    let mut histogram = [0u32; 10];
+    let (mut rate_sum, mut rated) = (0.0, 0);
+    let (mut site_sum, mut deviation_sum, mut site_rated) = (0.0, 0.0, 0);
+    let mut collection_counts: HashMap<CollectionType, u32> = HashMap::new();
+    let mut subject_counts: HashMap<u8, u32> = HashMap::new();
+    let mut tags: HashMap<&str, u32> = HashMap::new();
+    let mut watch_time = WatchTime {
+        episodes: 0,
+        seconds: 0,
+        estimated_episodes: 0,
+    };
+    // 年份 -> (更新数, 看过数, 评分总和, 打分数)
+    let mut years: BTreeMap<i32, (u32, u32, f64, u32)> = BTreeMap::new();
+
+    for item in collections {
+        *collection_counts.entry(item.r#type.clone()).or_default() += 1;
+        *subject_counts
+            .entry(item.subject_type.clone() as u8)
+            .or_default() += 1;
+
+        let rate = (1..=10).contains(&item.rate).then_some(item.rate);
+        if let Some(rate) = rate {
+            histogram[rate as usize - 1] += 1;
+            rate_sum += rate as f64;
+            rated += 1;
+            if let Some(subject) = &item.subject
+                && subject.score > 0.0
+            {
+                site_sum += subject.score;
+                deviation_sum += rate as f64 - subject.score;
+                site_rated += 1;
+            }
+        }
+
+        if let Some(subject) = &item.subject {
+            for tag in subject.tags.iter().take(SUBJECT_TAG_LIMIT) {
+                *tags.entry(tag.name.as_str()).or_default() += 1;
+            }
+        }
+
+        if is_watchable(&item.subject_type) {
+            let watched = watched_episodes(item);
+            let list = episodes.get(&item.subject_id).map(Vec::as_slice);
+            let (seconds, estimated) = estimate_watch_seconds(watched, list.unwrap_or_default());
+            watch_time.episodes += watched;
+            watch_time.seconds += seconds;
+            watch_time.estimated_episodes += estimated;
+        }
+
+        if let Some(year) = parse_year(&item.updated_at) {
+            let activity = years.entry(year).or_default();
+            activity.0 += 1;
+            if item.r#type == CollectionType::Done {
+                activity.1 += 1;
+            }
+            if let Some(rate) = rate {
+                activity.2 += rate as f64;
+                activity.3 += 1;
+            }
+        }
+    }
+
+    let collection_types = [
+        CollectionType::Wish,
+        CollectionType::Done,
+        CollectionType::Doing,
+        CollectionType::OnHold,
+        CollectionType::Dropped,
+    ]
+    .into_iter()
+    .filter_map(|r#type| {
+        let count = collection_counts.get(&r#type).copied()?;
+        Some(TypeCount { r#type, count })
+    })
+    .collect();
+
+    let subject_types = [
+        SubjectType::Book,
+        SubjectType::Anime,
+        SubjectType::Music,
+        SubjectType::Game,
+        SubjectType::Real,
+    ]
+    .into_iter()
+    .filter_map(|r#type| {
+        let count = subject_counts.get(&(r#type.clone() as u8)).copied()?;
+        Some(TypeCount { r#type, count })
+    })
+    .collect();
+
+    let mut top_tags: Vec<TagCount> = tags
+        .into_iter()
+        .map(|(name, count)| TagCount {
+            name: name.to_string(),
+            count,
+        })
+        .collect();
+    top_tags.sort_by(|a, b| b.count.cmp(&a.count).then(a.name.cmp(&b.name)));
+    top_tags.truncate(TOP_TAG_LIMIT);
+
+    let yearly = years
+        .into_iter()
+        .map(|(year, (count, done, sum, rated))| YearActivity {
+            year,
+            count,
+            done,
+            mean_rate: mean(sum, rated),
+        })
+        .collect();
+
+    UserStats {
+        username: username.to_string(),
+        total: collections.len() as u32,
+        rating: RatingStats {
+            histogram,
+            rated,
+            mean: mean(rate_sum, rated),
+            site_mean: mean(site_sum, site_rated),
+            mean_deviation: mean(deviation_sum, site_rated),
+        },
+        collection_types,
+        subject_types,
+        top_tags,
+        watch_time,
+        yearly,
+    }
+}
+
+impl BangumiClient {
+    /// 统计用户的收藏情况
+    ///
+    /// 拉取用户全部条目收藏后计算评分分布、类型分布、常见标签、观看时长与年度活动。
+    /// 开启`include_episodes`时会为每个看过的动画、三次元条目请求剧集列表以获得准确的时长，
+    /// 否则按每集默认时长估算
+    ///
+    /// # 参数
+    /// - `username`: 用户名
+    /// - `include_episodes`: 是否请求剧集时长
+    ///
+    /// # 返回
+    /// 返回可序列化的统计结果
+    pub async fn get_user_stats(
+        &self,
+        username: &str,
+        include_episodes: bool,
+    ) -> Result<UserStats> {
+        let collections = BangumiClient::fetch_all(|limit, offset| {
+            self.get_collection_subjects(username, None, None, Some(limit), Some(offset))
+        })
+        .await?;
+
+        let mut episodes = HashMap::new();
+        if include_episodes {
+            for item in &collections {
+                let watched = watched_episodes(item);
+                if !is_watchable(&item.subject_type) || watched == 0 {
+                    continue;
+                }
+                // 只需覆盖已观看的集数
+                let list = BangumiClient::fetch_up_to(watched, |limit, offset| {
+                    self.get_episodes(
+                        item.subject_id,
+                        Some(EpisodeType::Normal),
+                        Some(limit),
+                        Some(offset),
+                    )
+                })
+                .await?;
+                episodes.insert(item.subject_id, list);
+            }
+        }
+
+        Ok(compute_stats(username, &collections, &episodes))
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::{
+        fixture::{self, Fixture, parse_all},
+        mock::{MockRoute, MockServer},
+    },
+    module::{
+        collection::model::{CollectionSubject, CollectionType},
+        episode::model::Episode,
+        stats::{
+            model::TypeCount,
+            service::{DEFAULT_EPISODE_SECONDS, compute_stats, estimate_watch_seconds},
+        },
+        subject::model::SubjectType,
+    },
+};
+
+fn collections() -> Vec<CollectionSubject> {
+    parse_all([
+        fixture::collection(1)
+            .with("rate", 8)
+            .tags("subject.tags", &["原创", "科幻"])
+            .with("updated_at", "2023-03-01T00:00:00+08:00"),
+        fixture::collection(2)
+            .with("type", 3)
+            .with("rate", 6)
+            .with("ep_status", 3)
+            .tags("subject.tags", &["科幻"])
+            .with("updated_at", "2024-05-01T00:00:00+08:00"),
+        fixture::collection(3)
+            .with("subject_type", 1)
+            .with("subject.type", 1)
+            .with("type", 1)
+            .with("subject.score", 8.0)
+            .tags("subject.tags", &["小说"])
+            .with("updated_at", "2024-06-01T00:00:00+08:00"),
+        fixture::collection(4)
+            .with("subject_type", 6)
+            .with("subject.type", 6)
+            .with("rate", 10)
+            .with("ep_status", 2)
+            .with("subject.score", 0.0)
+            .with("updated_at", "2024-07-01T00:00:00+08:00"),
+    ])
+}
+
+fn episode_json(id: u32, sort: f64, duration_seconds: u32) -> Fixture {
+    fixture::episode(id, sort).with("duration_seconds", duration_seconds)
+}
+
+fn episodes(values: Vec<Fixture>) -> Vec<Episode> {
+    parse_all(values)
+}
+
+#[test]
+fn test_estimate_watch_seconds() {
+    let list = episodes(vec![
+        episode_json(2, 2.0, 0),
+        episode_json(1, 1.0, 1200),
+        episode_json(3, 3.0, 1800),
+    ]);
+    // 第2集缺少时长，按已知时长的平均值计入；第4集不存在同样按平均值计入
+    assert_eq!(estimate_watch_seconds(2, &list), (1200 + 1500, 1));
+    assert_eq!(
+        estimate_watch_seconds(4, &list),
+        (1200 + 1500 + 1800 + 1500, 2)
+    );
+    assert_eq!(
+        estimate_watch_seconds(2, &[]),
+        (2 * DEFAULT_EPISODE_SECONDS as u64, 2)
+    );
+}
+
+#[test]
+fn test_compute_stats() {
+    let episode_map = HashMap::from([(
+        2,
+        episodes(vec![
+            episode_json(1, 1.0, 1000),
+            episode_json(2, 2.0, 1000),
+            episode_json(3, 3.0, 1000),
+        ]),
+    )]);
+    let stats = compute_stats("sai", &collections(), &episode_map);
+
+    assert_eq!(stats.total, 4);
+    assert_eq!(stats.rating.rated, 3);
+    assert_eq!(stats.rating.histogram[7], 1);
+    assert_eq!(stats.rating.histogram[9], 1);
+    assert_eq!(stats.rating.mean, Some(8.0));
+    // 暂无站内评分的条目不参与比较
+    assert_eq!(stats.rating.site_mean, Some(7.0));
+    assert_eq!(stats.rating.mean_deviation, Some(0.0));
+
+    assert_eq!(
+        stats.collection_types,
+        vec![
+            TypeCount {
+                r#type: CollectionType::Wish,
+                count: 1
+            },
+            TypeCount {
+                r#type: CollectionType::Done,
+                count: 2
+            },
+            TypeCount {
+                r#type: CollectionType::Doing,
+                count: 1
+            },
+        ]
+    );
+    assert_eq!(
+        stats.subject_types,
+        vec![
+            TypeCount {
+                r#type: SubjectType::Book,
+                count: 1
+            },
+            TypeCount {
+                r#type: SubjectType::Anime,
+                count: 2
+            },
+            TypeCount {
+                r#type: SubjectType::Real,
+                count: 1
+            },
+        ]
+    );
+
+    assert_eq!(stats.top_tags[0].name, "科幻");
+    assert_eq!(stats.top_tags[0].count, 2);
+    assert_eq!(stats.top_tags.len(), 3);
+
+    // 条目1看过但无进度按12集默认时长，条目2有3集准确时长，条目4看了2集按默认时长
+    assert_eq!(stats.watch_time.episodes, 12 + 3 + 2);
+    assert_eq!(stats.watch_time.estimated_episodes, 14);
+    assert_eq!(
+        stats.watch_time.seconds,
+        14 * DEFAULT_EPISODE_SECONDS as u64 + 3000
+    );
+
+    assert_eq!(stats.yearly.len(), 2);
+    assert_eq!(stats.yearly[0].year, 2023);
+    assert_eq!(stats.yearly[1].count, 3);
+    assert_eq!(stats.yearly[1].done, 1);
+    assert_eq!(stats.yearly[1].mean_rate, Some(8.0));
+
+    let json = serde_json::to_string(&stats).unwrap();
+    let restored = serde_json::from_str(&json).unwrap();
+    assert_eq!(stats, restored);
+}
+
+#[tokio::test]
+async fn test_get_user_stats() {
+    let server = MockServer::start(vec![
+        MockRoute::json(
+            "GET",
+            "/v0/users/sai/collections",
+            200,
+            serde_json::json!({
+                "total": 4,
+                "limit": 100,
+                "offset": 0,
+                "data": serde_json::to_value(collections()).unwrap()
+            }),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/episodes",
+            200,
+            serde_json::json!({
+                "total": 2,
+                "limit": 100,
+                "offset": 0,
+                "data": [episode_json(1, 1.0, 600), episode_json(2, 2.0, 600)]
+            }),
+        ),
+    ]);
+    let client = server.client();
+
+    let stats = client.get_user_stats("sai", true).await.unwrap();
+    assert_eq!(stats.total, 4);
+
+    // 仅为已观看的动画、三次元条目请求剧集
+    let requests = server.requests();
+    let episode_requests = requests
+        .iter()
+        .filter(|request| request.path == "/v0/episodes")
+        .count();
+    assert_eq!(episode_requests, 3);
+
+    let stats = client.get_user_stats("sai", false).await.unwrap();
+    assert_eq!(
+        stats.watch_time.estimated_episodes,
+        stats.watch_time.episodes
+    );
+}
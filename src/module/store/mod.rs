@@ -0,0 +1,6 @@
+/// SQLite本地镜像（需启用`store`特性）
+pub mod model;
+pub mod service;
+
+#[cfg(all(test, any(feature = "reqwest", feature = "ureq")))]
+pub mod test;
@@ -0,0 +1,40 @@
+use std::sync::Mutex;
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::common::model::BangumiClient;
+
+/// 数据库结构版本，保存在`PRAGMA user_version`中
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// SQLite本地镜像
+///
+/// 以规范化的表结构保存条目、人物、角色、剧集、目录及条目关联列表，
+/// 标签、信息框、关联关系均拆分为独立的表，便于直接使用SQL分析。
+/// 每条记录按ID覆盖写入并记录`last_fetched`时间戳
+pub struct Store {
+    /// 数据库连接
+    pub(crate) connection: Mutex<Connection>,
+}
+
+/// 从本地镜像读取的数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cached<T> {
+    /// 数据内容
+    pub value: T,
+    /// 最后一次从API获取的时间（Unix时间戳，秒）
+    pub last_fetched: i64,
+}
+
+/// 优先读取本地镜像的客户端
+///
+/// 本地镜像中存在且未过期的数据直接返回，否则请求API并写入镜像
+pub struct CachedClient {
+    /// API客户端
+    pub client: BangumiClient,
+    /// 本地镜像
+    pub store: Store,
+    /// 可选的数据有效期（秒），为None时镜像中的数据永不过期
+    pub max_age: Option<i64>,
+}
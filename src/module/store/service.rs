@@ -0,0 +1,887 @@
+use std::{
+    path::Path,
+    sync::{Mutex, MutexGuard},
+};
+
+use anyhow::{Result, bail};
+use rusqlite::{Connection, OptionalExtension, Transaction, params};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    common::{model::BangumiClient, time::now_timestamp},
+    module::{
+        character::model::Character,
+        episode::model::{Episode, EpisodeType},
+        indice::model::Index,
+        person::model::PersonDetail,
+        subject::model::{Subject, SubjectCharacter, SubjectPerson, SubjectSubject},
+    },
+};
+
+use super::model::{Cached, CachedClient, SCHEMA_VERSION, Store};
+
+/// 条目的剧集列表
+const LIST_SUBJECT_EPISODES: &str = "subject_episodes";
+/// 条目的关联条目列表
+const LIST_SUBJECT_SUBJECTS: &str = "subject_subjects";
+/// 条目的关联人物列表
+const LIST_SUBJECT_PERSONS: &str = "subject_persons";
+/// 条目的关联角色列表
+const LIST_SUBJECT_CHARACTERS: &str = "subject_characters";
+
+/// 数据库结构
+///
+/// 各实体表的`raw`列保存接口返回的原始JSON，用于无损还原数据；
+/// 其余列及子表为规范化后的字段，供SQL查询使用
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS subjects (
+    id INTEGER PRIMARY KEY,
+    type INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    name_cn TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    series INTEGER NOT NULL,
+    nsfw INTEGER NOT NULL,
+    locked INTEGER NOT NULL,
+    date TEXT,
+    platform TEXT NOT NULL,
+    image TEXT NOT NULL,
+    volumes INTEGER NOT NULL,
+    eps INTEGER NOT NULL,
+    rating_rank INTEGER NOT NULL,
+    rating_total INTEGER NOT NULL,
+    rating_score REAL NOT NULL,
+    collection_wish INTEGER,
+    collection_collect INTEGER,
+    collection_doing INTEGER,
+    collection_on_hold INTEGER,
+    collection_dropped INTEGER,
+    raw TEXT NOT NULL,
+    last_fetched INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS subject_rating_counts (
+    subject_id INTEGER NOT NULL,
+    score INTEGER NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (subject_id, score)
+);
+CREATE TABLE IF NOT EXISTS subject_tags (
+    subject_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    count INTEGER NOT NULL,
+    total_cont INTEGER NOT NULL,
+    PRIMARY KEY (subject_id, name)
+);
+CREATE TABLE IF NOT EXISTS subject_meta_tags (
+    subject_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    PRIMARY KEY (subject_id, name)
+);
+CREATE TABLE IF NOT EXISTS infobox (
+    entity TEXT NOT NULL,
+    entity_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (entity, entity_id, position)
+);
+CREATE TABLE IF NOT EXISTS persons (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    type INTEGER NOT NULL,
+    summary TEXT NOT NULL,
+    locked INTEGER NOT NULL,
+    last_modified TEXT NOT NULL,
+    gender TEXT,
+    blood_type INTEGER,
+    birth_year INTEGER,
+    birth_mon INTEGER,
+    birth_day INTEGER,
+    image TEXT NOT NULL,
+    comments INTEGER NOT NULL,
+    collects INTEGER NOT NULL,
+    raw TEXT NOT NULL,
+    last_fetched INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS person_careers (
+    person_id INTEGER NOT NULL,
+    career TEXT NOT NULL,
+    PRIMARY KEY (person_id, career)
+);
+CREATE TABLE IF NOT EXISTS characters (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    type INTEGER NOT NULL,
+    summary TEXT NOT NULL,
+    locked INTEGER NOT NULL,
+    gender TEXT NOT NULL,
+    blood_type INTEGER,
+    birth_year INTEGER,
+    birth_mon INTEGER,
+    birth_day INTEGER,
+    image TEXT NOT NULL,
+    comments INTEGER NOT NULL,
+    collects INTEGER NOT NULL,
+    nsfw INTEGER NOT NULL,
+    raw TEXT NOT NULL,
+    last_fetched INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS episodes (
+    id INTEGER PRIMARY KEY,
+    subject_id INTEGER NOT NULL,
+    type INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    name_cn TEXT NOT NULL,
+    sort REAL NOT NULL,
+    ep REAL NOT NULL,
+    airdate TEXT NOT NULL,
+    comment INTEGER NOT NULL,
+    duration TEXT NOT NULL,
+    "desc" TEXT NOT NULL,
+    disc INTEGER NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    raw TEXT NOT NULL,
+    last_fetched INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS episodes_subject_id ON episodes (subject_id);
+CREATE TABLE IF NOT EXISTS indices (
+    id INTEGER PRIMARY KEY,
+    title TEXT NOT NULL,
+    "desc" TEXT NOT NULL,
+    total INTEGER,
+    comments INTEGER NOT NULL,
+    collects INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    creator_username TEXT NOT NULL,
+    creator_nickname TEXT NOT NULL,
+    ban INTEGER NOT NULL,
+    nsfw INTEGER NOT NULL,
+    raw TEXT NOT NULL,
+    last_fetched INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS subject_relations (
+    subject_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    related_id INTEGER NOT NULL,
+    related_type INTEGER NOT NULL,
+    relation TEXT NOT NULL,
+    raw TEXT NOT NULL,
+    PRIMARY KEY (subject_id, position)
+);
+CREATE TABLE IF NOT EXISTS subject_persons (
+    subject_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    person_id INTEGER NOT NULL,
+    relation TEXT NOT NULL,
+    eps TEXT NOT NULL,
+    raw TEXT NOT NULL,
+    PRIMARY KEY (subject_id, position)
+);
+CREATE TABLE IF NOT EXISTS subject_characters (
+    subject_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    character_id INTEGER NOT NULL,
+    relation TEXT NOT NULL,
+    raw TEXT NOT NULL,
+    PRIMARY KEY (subject_id, position)
+);
+CREATE TABLE IF NOT EXISTS list_fetches (
+    kind TEXT NOT NULL,
+    owner_id INTEGER NOT NULL,
+    last_fetched INTEGER NOT NULL,
+    PRIMARY KEY (kind, owner_id)
+);
+"#;
+
+/// 将序列化为单个值的枚举转换为文本（字符串不带引号）
+fn plain<T: Serialize>(value: &T) -> Result<String> {
+    Ok(match serde_json::to_value(value)? {
+        serde_json::Value::String(text) => text,
+        other => other.to_string(),
+    })
+}
+
+/// 写入信息框，每一项保存键名与JSON格式的值
+fn write_infobox(
+    tx: &Transaction,
+    entity: &str,
+    entity_id: u32,
+    items: &[serde_json::Value],
+) -> Result<()> {
+    tx.execute(
+        "DELETE FROM infobox WHERE entity = ?1 AND entity_id = ?2",
+        params![entity, entity_id],
+    )?;
+    for (position, item) in items.iter().enumerate() {
+        let key = item
+            .get("key")
+            .and_then(|key| key.as_str())
+            .unwrap_or_default();
+        let value = item.get("value").cloned().unwrap_or_default();
+        tx.execute(
+            "INSERT INTO infobox (entity, entity_id, position, key, value) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![entity, entity_id, position, key, value.to_string()],
+        )?;
+    }
+    Ok(())
+}
+
+/// 记录列表的获取时间
+fn mark_list(tx: &Transaction, kind: &str, owner_id: u32, fetched_at: i64) -> Result<()> {
+    tx.execute(
+        "INSERT INTO list_fetches (kind, owner_id, last_fetched) VALUES (?1, ?2, ?3)
+         ON CONFLICT (kind, owner_id) DO UPDATE SET last_fetched = excluded.last_fetched",
+        params![kind, owner_id, fetched_at],
+    )?;
+    Ok(())
+}
+
+impl Store {
+    /// 打开（或创建）数据库文件并初始化表结构
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// 创建内存数据库（用于测试或临时缓存）
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// 使用已有的连接并初始化表结构
+    ///
+    /// 数据库结构版本高于当前支持的版本时返回错误
+    pub fn from_connection(connection: Connection) -> Result<Self> {
+        let version: u32 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            bail!(format!(
+                "不支持的数据库版本: {version}（当前支持 {SCHEMA_VERSION}）"
+            ));
+        }
+        connection.execute_batch(SCHEMA)?;
+        connection.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        Ok(Store {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// 获取数据库连接
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>> {
+        match self.connection.lock() {
+            Ok(guard) => Ok(guard),
+            Err(_) => bail!("数据库连接已损坏"),
+        }
+    }
+
+    /// 使用数据库连接执行自定义查询
+    ///
+    /// 可用于在镜像数据上直接运行SQL分析
+    pub fn with_connection<T>(
+        &self,
+        f: impl FnOnce(&Connection) -> rusqlite::Result<T>,
+    ) -> Result<T> {
+        let connection = self.lock()?;
+        Ok(f(&connection)?)
+    }
+
+    /// 读取实体表中的原始JSON及获取时间
+    fn read<T: DeserializeOwned>(&self, table: &str, id: u32) -> Result<Option<Cached<T>>> {
+        let connection = self.lock()?;
+        let row: Option<(String, i64)> = connection
+            .query_row(
+                &format!("SELECT raw, last_fetched FROM {table} WHERE id = ?1"),
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        row.map(|(raw, last_fetched)| {
+            Ok(Cached {
+                value: serde_json::from_str(&raw)?,
+                last_fetched,
+            })
+        })
+        .transpose()
+    }
+
+    /// 读取关联列表的原始JSON（按写入顺序）
+    fn read_list<T: DeserializeOwned>(
+        &self,
+        kind: &str,
+        table: &str,
+        subject_id: u32,
+    ) -> Result<Option<Cached<Vec<T>>>> {
+        let connection = self.lock()?;
+        let last_fetched: Option<i64> = connection
+            .query_row(
+                "SELECT last_fetched FROM list_fetches WHERE kind = ?1 AND owner_id = ?2",
+                params![kind, subject_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(last_fetched) = last_fetched else {
+            return Ok(None);
+        };
+        let mut statement = connection.prepare(&format!(
+            "SELECT raw FROM {table} WHERE subject_id = ?1 ORDER BY position"
+        ))?;
+        let value = statement
+            .query_map(params![subject_id], |row| row.get::<_, String>(0))?
+            .map(|raw| Ok(serde_json::from_str(&raw?)?))
+            .collect::<Result<Vec<T>>>()?;
+        Ok(Some(Cached {
+            value,
+            last_fetched,
+        }))
+    }
+
+    /// 写入或更新条目（含评分分布、标签、公共标签与信息框）
+    pub fn upsert_subject(&self, subject: &Subject) -> Result<()> {
+        self.upsert_subjects(std::slice::from_ref(subject))
+    }
+
+    /// 在同一事务中批量写入或更新条目
+    pub fn upsert_subjects(&self, subjects: &[Subject]) -> Result<()> {
+        let mut connection = self.lock()?;
+        let tx = connection.transaction()?;
+        let fetched_at = now_timestamp();
+        for subject in subjects {
+            tx.execute(
+                "INSERT OR REPLACE INTO subjects (
+                    id, type, name, name_cn, summary, series, nsfw, locked, date, platform, image,
+                    volumes, eps, rating_rank, rating_total, rating_score, collection_wish,
+                    collection_collect, collection_doing, collection_on_hold, collection_dropped,
+                    raw, last_fetched
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                    ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
+                params![
+                    subject.id,
+                    subject.r#type.clone() as u8,
+                    subject.name,
+                    subject.name_cn,
+                    subject.summary,
+                    subject.series,
+                    subject.nsfw,
+                    subject.locked,
+                    subject.date,
+                    subject.platform,
+                    subject.images.large,
+                    subject.volumes,
+                    subject.eps,
+                    subject.rating.rank,
+                    subject.rating.total,
+                    subject.rating.score,
+                    subject.collection.wish,
+                    subject.collection.collect,
+                    subject.collection.doing,
+                    subject.collection.on_hold,
+                    subject.collection.dropped,
+                    serde_json::to_string(subject)?,
+                    fetched_at,
+                ],
+            )?;
+
+            tx.execute(
+                "DELETE FROM subject_rating_counts WHERE subject_id = ?1",
+                params![subject.id],
+            )?;
+            for (score, count) in subject.rating.count.iter() {
+                tx.execute(
+                    "INSERT INTO subject_rating_counts (subject_id, score, count) VALUES (?1, ?2, ?3)",
+                    params![subject.id, score, count],
+                )?;
+            }
+
+            tx.execute(
+                "DELETE FROM subject_tags WHERE subject_id = ?1",
+                params![subject.id],
+            )?;
+            for tag in &subject.tags {
+                tx.execute(
+                    "INSERT OR REPLACE INTO subject_tags (subject_id, name, count, total_cont)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![subject.id, tag.name, tag.count, tag.total_cont],
+                )?;
+            }
+
+            tx.execute(
+                "DELETE FROM subject_meta_tags WHERE subject_id = ?1",
+                params![subject.id],
+            )?;
+            for name in &subject.meta_tags {
+                tx.execute(
+                    "INSERT OR IGNORE INTO subject_meta_tags (subject_id, name) VALUES (?1, ?2)",
+                    params![subject.id, name],
+                )?;
+            }
+
+            let infobox = subject
+                .infobox
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<serde_json::Result<Vec<_>>>()?;
+            write_infobox(&tx, "subject", subject.id, &infobox)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 读取条目
+    pub fn get_subject(&self, subject_id: u32) -> Result<Option<Cached<Subject>>> {
+        self.read("subjects", subject_id)
+    }
+
+    /// 写入或更新人物（含职业与信息框）
+    pub fn upsert_person(&self, person: &PersonDetail) -> Result<()> {
+        self.upsert_persons(std::slice::from_ref(person))
+    }
+
+    /// 在同一事务中批量写入或更新人物
+    pub fn upsert_persons(&self, persons: &[PersonDetail]) -> Result<()> {
+        let mut connection = self.lock()?;
+        let tx = connection.transaction()?;
+        let fetched_at = now_timestamp();
+        for person in persons {
+            tx.execute(
+                "INSERT OR REPLACE INTO persons (
+                    id, name, type, summary, locked, last_modified, gender, blood_type, birth_year,
+                    birth_mon, birth_day, image, comments, collects, raw, last_fetched
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    person.id,
+                    plain(&person.r#type)?,
+                    person.name,
+                    person.summary,
+                    person.locked,
+                    person.last_modified,
+                    person.gender,
+                    person.blood_type.as_ref().map(plain).transpose()?,
+                    person.birth_year,
+                    person.birth_mon,
+                    person.birth_day,
+                    person.images.large,
+                    person.stat.comments,
+                    person.stat.collects,
+                    serde_json::to_string(person)?,
+                    fetched_at,
+                ],
+            )?;
+
+            tx.execute(
+                "DELETE FROM person_careers WHERE person_id = ?1",
+                params![person.id],
+            )?;
+            for career in &person.career {
+                tx.execute(
+                    "INSERT OR IGNORE INTO person_careers (person_id, career) VALUES (?1, ?2)",
+                    params![person.id, plain(career)?],
+                )?;
+            }
+
+            write_infobox(&tx, "person", person.id, &person.infobox)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 读取人物
+    pub fn get_person(&self, person_id: u32) -> Result<Option<Cached<PersonDetail>>> {
+        self.read("persons", person_id)
+    }
+
+    /// 写入或更新角色（含信息框）
+    pub fn upsert_character(&self, character: &Character) -> Result<()> {
+        self.upsert_characters(std::slice::from_ref(character))
+    }
+
+    /// 在同一事务中批量写入或更新角色
+    pub fn upsert_characters(&self, characters: &[Character]) -> Result<()> {
+        let mut connection = self.lock()?;
+        let tx = connection.transaction()?;
+        let fetched_at = now_timestamp();
+        for character in characters {
+            tx.execute(
+                "INSERT OR REPLACE INTO characters (
+                    id, name, type, summary, locked, gender, blood_type, birth_year, birth_mon,
+                    birth_day, image, comments, collects, nsfw, raw, last_fetched
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    character.id,
+                    plain(&character.r#type)?,
+                    character.name,
+                    character.summary,
+                    character.locked,
+                    character.gender,
+                    character.blood_type.as_ref().map(plain).transpose()?,
+                    character.birth_year,
+                    character.birth_mon,
+                    character.birth_day,
+                    character.images.large,
+                    character.stat.comments,
+                    character.stat.collects,
+                    character.nsfw,
+                    serde_json::to_string(character)?,
+                    fetched_at,
+                ],
+            )?;
+
+            let infobox = character
+                .infobox
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<serde_json::Result<Vec<_>>>()?;
+            write_infobox(&tx, "character", character.id, &infobox)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 读取角色
+    pub fn get_character(&self, character_id: u32) -> Result<Option<Cached<Character>>> {
+        self.read("characters", character_id)
+    }
+
+    /// 写入或更新剧集
+    pub fn upsert_episodes(&self, episodes: &[Episode]) -> Result<()> {
+        let mut connection = self.lock()?;
+        let tx = connection.transaction()?;
+        let fetched_at = now_timestamp();
+        for episode in episodes {
+            tx.execute(
+                r#"INSERT OR REPLACE INTO episodes (
+                    id, subject_id, type, name, name_cn, sort, ep, airdate, comment, duration,
+                    "desc", disc, duration_seconds, raw, last_fetched
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"#,
+                params![
+                    episode.id,
+                    episode.subject_id,
+                    episode.r#type.clone() as u8,
+                    episode.name,
+                    episode.name_cn,
+                    episode.sort,
+                    episode.ep,
+                    episode.airdate,
+                    episode.comment,
+                    episode.duration,
+                    episode.desc,
+                    episode.disc,
+                    episode.duration_seconds,
+                    serde_json::to_string(episode)?,
+                    fetched_at,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 写入条目的完整剧集列表，并记录列表的获取时间
+    pub fn upsert_subject_episodes(&self, subject_id: u32, episodes: &[Episode]) -> Result<()> {
+        self.upsert_episodes(episodes)?;
+        let mut connection = self.lock()?;
+        let tx = connection.transaction()?;
+        mark_list(&tx, LIST_SUBJECT_EPISODES, subject_id, now_timestamp())?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 读取剧集
+    pub fn get_episode(&self, episode_id: u32) -> Result<Option<Cached<Episode>>> {
+        self.read("episodes", episode_id)
+    }
+
+    /// 读取条目的完整剧集列表（按排序）
+    ///
+    /// 仅在完整列表写入过时返回数据，单独写入的剧集不视为完整列表
+    pub fn get_subject_episodes(&self, subject_id: u32) -> Result<Option<Cached<Vec<Episode>>>> {
+        let connection = self.lock()?;
+        let last_fetched: Option<i64> = connection
+            .query_row(
+                "SELECT last_fetched FROM list_fetches WHERE kind = ?1 AND owner_id = ?2",
+                params![LIST_SUBJECT_EPISODES, subject_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(last_fetched) = last_fetched else {
+            return Ok(None);
+        };
+        let mut statement = connection
+            .prepare("SELECT raw FROM episodes WHERE subject_id = ?1 ORDER BY type, sort, id")?;
+        let value = statement
+            .query_map(params![subject_id], |row| row.get::<_, String>(0))?
+            .map(|raw| Ok(serde_json::from_str(&raw?)?))
+            .collect::<Result<Vec<Episode>>>()?;
+        Ok(Some(Cached {
+            value,
+            last_fetched,
+        }))
+    }
+
+    /// 写入或更新目录
+    pub fn upsert_index(&self, index: &Index) -> Result<()> {
+        let connection = self.lock()?;
+        connection.execute(
+            r#"INSERT OR REPLACE INTO indices (
+                id, title, "desc", total, comments, collects, created_at, updated_at,
+                creator_username, creator_nickname, ban, nsfw, raw, last_fetched
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)"#,
+            params![
+                index.id,
+                index.title,
+                index.desc,
+                index.total,
+                index.stat.comments,
+                index.stat.collects,
+                index.created_at,
+                index.updated_at,
+                index.creator.username,
+                index.creator.nickname,
+                index.ban,
+                index.nsfw,
+                serde_json::to_string(index)?,
+                now_timestamp(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 读取目录
+    pub fn get_index(&self, index_id: u32) -> Result<Option<Cached<Index>>> {
+        self.read("indices", index_id)
+    }
+
+    /// 写入条目的关联条目列表（覆盖原有列表）
+    pub fn upsert_subject_subjects(
+        &self,
+        subject_id: u32,
+        related: &[SubjectSubject],
+    ) -> Result<()> {
+        let mut connection = self.lock()?;
+        let tx = connection.transaction()?;
+        tx.execute(
+            "DELETE FROM subject_relations WHERE subject_id = ?1",
+            params![subject_id],
+        )?;
+        for (position, item) in related.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO subject_relations (subject_id, position, related_id, related_type, relation, raw)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    subject_id,
+                    position,
+                    item.id,
+                    item.r#type.clone() as u8,
+                    item.relation,
+                    serde_json::to_string(item)?,
+                ],
+            )?;
+        }
+        mark_list(&tx, LIST_SUBJECT_SUBJECTS, subject_id, now_timestamp())?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 读取条目的关联条目列表
+    pub fn get_subject_subjects(
+        &self,
+        subject_id: u32,
+    ) -> Result<Option<Cached<Vec<SubjectSubject>>>> {
+        self.read_list(LIST_SUBJECT_SUBJECTS, "subject_relations", subject_id)
+    }
+
+    /// 写入条目的关联人物列表（覆盖原有列表）
+    pub fn upsert_subject_persons(&self, subject_id: u32, persons: &[SubjectPerson]) -> Result<()> {
+        let mut connection = self.lock()?;
+        let tx = connection.transaction()?;
+        tx.execute(
+            "DELETE FROM subject_persons WHERE subject_id = ?1",
+            params![subject_id],
+        )?;
+        for (position, item) in persons.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO subject_persons (subject_id, position, person_id, relation, eps, raw)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    subject_id,
+                    position,
+                    item.id,
+                    item.relation,
+                    item.eps,
+                    serde_json::to_string(item)?,
+                ],
+            )?;
+        }
+        mark_list(&tx, LIST_SUBJECT_PERSONS, subject_id, now_timestamp())?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 读取条目的关联人物列表
+    pub fn get_subject_persons(
+        &self,
+        subject_id: u32,
+    ) -> Result<Option<Cached<Vec<SubjectPerson>>>> {
+        self.read_list(LIST_SUBJECT_PERSONS, "subject_persons", subject_id)
+    }
+
+    /// 写入条目的关联角色列表（覆盖原有列表）
+    pub fn upsert_subject_characters(
+        &self,
+        subject_id: u32,
+        characters: &[SubjectCharacter],
+    ) -> Result<()> {
+        let mut connection = self.lock()?;
+        let tx = connection.transaction()?;
+        tx.execute(
+            "DELETE FROM subject_characters WHERE subject_id = ?1",
+            params![subject_id],
+        )?;
+        for (position, item) in characters.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO subject_characters (subject_id, position, character_id, relation, raw)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    subject_id,
+                    position,
+                    item.id,
+                    item.relation,
+                    serde_json::to_string(item)?,
+                ],
+            )?;
+        }
+        mark_list(&tx, LIST_SUBJECT_CHARACTERS, subject_id, now_timestamp())?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 读取条目的关联角色列表
+    pub fn get_subject_characters(
+        &self,
+        subject_id: u32,
+    ) -> Result<Option<Cached<Vec<SubjectCharacter>>>> {
+        self.read_list(LIST_SUBJECT_CHARACTERS, "subject_characters", subject_id)
+    }
+}
+
+impl CachedClient {
+    /// 创建优先读取本地镜像的客户端
+    ///
+    /// 客户端会启用 [`BangumiClient::with_single_flight`]，镜像未命中的相同并发请求只访问一次API
+    ///
+    /// # 参数
+    /// - `client`: API客户端
+    /// - `store`: 本地镜像
+    /// - `max_age`: 可选的数据有效期（秒），为None时镜像中的数据永不过期
+    pub fn new(client: BangumiClient, store: Store, max_age: Option<i64>) -> Self {
+        CachedClient {
+            client: client.with_single_flight(),
+            store,
+            max_age,
+        }
+    }
+
+    /// 判断镜像中的数据是否仍然有效
+    fn fresh<T>(&self, cached: Option<Cached<T>>) -> Option<T> {
+        let cached = cached?;
+        match self.max_age {
+            Some(max_age) if now_timestamp() - cached.last_fetched > max_age => None,
+            _ => Some(cached.value),
+        }
+    }
+
+    /// 获取条目详情，优先读取本地镜像
+    pub async fn get_subject(&self, subject_id: u32) -> Result<Subject> {
+        if let Some(subject) = self.fresh(self.store.get_subject(subject_id)?) {
+            return Ok(subject);
+        }
+        let subject = self.client.get_subject(subject_id).await?;
+        self.store.upsert_subject(&subject)?;
+        Ok(subject)
+    }
+
+    /// 获取人物详情，优先读取本地镜像
+    pub async fn get_person(&self, person_id: u32) -> Result<PersonDetail> {
+        if let Some(person) = self.fresh(self.store.get_person(person_id)?) {
+            return Ok(person);
+        }
+        let person = self.client.get_person(person_id).await?;
+        self.store.upsert_person(&person)?;
+        Ok(person)
+    }
+
+    /// 获取角色详情，优先读取本地镜像
+    pub async fn get_character(&self, character_id: u32) -> Result<Character> {
+        if let Some(character) = self.fresh(self.store.get_character(character_id)?) {
+            return Ok(character);
+        }
+        let character = self.client.get_character(character_id).await?;
+        self.store.upsert_character(&character)?;
+        Ok(character)
+    }
+
+    /// 获取剧集详情，优先读取本地镜像
+    pub async fn get_episode(&self, episode_id: u32) -> Result<Episode> {
+        if let Some(episode) = self.fresh(self.store.get_episode(episode_id)?) {
+            return Ok(episode);
+        }
+        let episode = self.client.get_episode(episode_id).await?;
+        self.store.upsert_episodes(std::slice::from_ref(&episode))?;
+        Ok(episode)
+    }
+
+    /// 获取条目的全部剧集，优先读取本地镜像
+    ///
+    /// 镜像中没有完整列表时分页拉取所有类型的剧集并整体写入
+    pub async fn get_subject_episodes(&self, subject_id: u32) -> Result<Vec<Episode>> {
+        if let Some(episodes) = self.fresh(self.store.get_subject_episodes(subject_id)?) {
+            return Ok(episodes);
+        }
+        let episodes = BangumiClient::fetch_all(|limit, offset| {
+            self.client
+                .get_episodes(subject_id, None::<EpisodeType>, Some(limit), Some(offset))
+        })
+        .await?;
+        self.store.upsert_subject_episodes(subject_id, &episodes)?;
+        Ok(episodes)
+    }
+
+    /// 获取目录详情，优先读取本地镜像
+    pub async fn get_index(&self, index_id: u32) -> Result<Index> {
+        if let Some(index) = self.fresh(self.store.get_index(index_id)?) {
+            return Ok(index);
+        }
+        let index = self.client.get_index(index_id).await?;
+        self.store.upsert_index(&index)?;
+        Ok(index)
+    }
+
+    /// 获取条目的关联条目，优先读取本地镜像
+    pub async fn get_subject_subjects(&self, subject_id: u32) -> Result<Vec<SubjectSubject>> {
+        if let Some(related) = self.fresh(self.store.get_subject_subjects(subject_id)?) {
+            return Ok(related);
+        }
+        let related = self.client.get_subject_subjects(subject_id).await?;
+        self.store.upsert_subject_subjects(subject_id, &related)?;
+        Ok(related)
+    }
+
+    /// 获取条目的关联人物，优先读取本地镜像
+    pub async fn get_subject_persons(&self, subject_id: u32) -> Result<Vec<SubjectPerson>> {
+        if let Some(persons) = self.fresh(self.store.get_subject_persons(subject_id)?) {
+            return Ok(persons);
+        }
+        let persons = self.client.get_subject_persons(subject_id).await?;
+        self.store.upsert_subject_persons(subject_id, &persons)?;
+        Ok(persons)
+    }
+
+    /// 获取条目的关联角色，优先读取本地镜像
+    pub async fn get_subject_characters(&self, subject_id: u32) -> Result<Vec<SubjectCharacter>> {
+        if let Some(characters) = self.fresh(self.store.get_subject_characters(subject_id)?) {
+            return Ok(characters);
+        }
+        let characters = self.client.get_subject_characters(subject_id).await?;
+        self.store
+            .upsert_subject_characters(subject_id, &characters)?;
+        Ok(characters)
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{
+    common::{
+        fixture::{self, Fixture, parse_all},
+        mock::{MockRoute, MockServer},
+    },
+    module::{
+        character::model::Character,
+        episode::model::Episode,
+        person::model::PersonDetail,
+        store::model::{CachedClient, Store},
+        subject::model::{Subject, SubjectSubject},
+    },
+};
+
+fn subject_json(id: u32, name: &str) -> Fixture {
+    let subject = (1..=10).fold(fixture::subject(id), |subject, score| {
+        subject.with(&format!("rating.count.{score}"), score)
+    });
+    subject
+        .with("name", name)
+        .with(
+            "infobox",
+            serde_json::json!([
+                {"key": "中文名", "value": "测试"},
+                {"key": "别名", "value": [{"v": "alias"}]}
+            ]),
+        )
+        .with("rating.rank", 10)
+        .with("rating.total", 55)
+        .with(
+            "collection",
+            serde_json::json!({"wish": 1, "collect": 2, "doing": 3, "on_hold": 4, "dropped": 5}),
+        )
+        .with("meta_tags", ["TV", "原创"])
+        .with(
+            "tags",
+            serde_json::json!([
+                {"name": "科幻", "count": 30, "total_cont": 100},
+                {"name": "原创", "count": 20, "total_cont": 80}
+            ]),
+        )
+}
+
+fn person_json(id: u32) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": "花泽香菜",
+        "type": 1,
+        "career": ["seiyu", "artist"],
+        "images": fixture::images(),
+        "summary": "",
+        "locked": false,
+        "last_modified": "2024-01-01T00:00:00Z",
+        "infobox": [{"key": "生日", "value": "1989-02-25"}],
+        "gender": "female",
+        "blood_type": 1,
+        "birth_year": 1989,
+        "birth_mon": 2,
+        "birth_day": 25,
+        "stat": {"comments": 1, "collects": 2}
+    })
+}
+
+fn character_json(id: u32) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": "角色",
+        "type": 1,
+        "images": fixture::images(),
+        "summary": "",
+        "locked": false,
+        "infobox": [],
+        "gender": "female",
+        "blood_type": null,
+        "birth_year": null,
+        "birth_mon": null,
+        "birth_day": null,
+        "stat": {"comments": 0, "collects": 0},
+        "nsfw": false
+    })
+}
+
+#[test]
+fn test_subject_round_trip() {
+    let store = Store::open_in_memory().unwrap();
+    assert!(store.get_subject(1).unwrap().is_none());
+
+    let subject: Subject = subject_json(1, "first").parse();
+    store.upsert_subject(&subject).unwrap();
+    let cached = store.get_subject(1).unwrap().unwrap();
+    assert_eq!(cached.value.name, "first");
+    assert!(cached.last_fetched > 0);
+
+    // 重复写入时覆盖原有数据
+    let updated: Subject = subject_json(1, "second").parse();
+    store.upsert_subject(&updated).unwrap();
+    assert_eq!(store.get_subject(1).unwrap().unwrap().value.name, "second");
+
+    let (subjects, tags, meta_tags, infobox, rated): (u32, u32, u32, u32, u32) = store
+        .with_connection(|connection| {
+            connection.query_row(
+                "SELECT
+                    (SELECT COUNT(*) FROM subjects),
+                    (SELECT COUNT(*) FROM subject_tags WHERE subject_id = 1),
+                    (SELECT COUNT(*) FROM subject_meta_tags WHERE subject_id = 1),
+                    (SELECT COUNT(*) FROM infobox WHERE entity = 'subject' AND entity_id = 1),
+                    (SELECT SUM(count) FROM subject_rating_counts WHERE subject_id = 1)",
+                [],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                    ))
+                },
+            )
+        })
+        .unwrap();
+    assert_eq!(
+        (subjects, tags, meta_tags, infobox, rated),
+        (1, 2, 2, 2, 55)
+    );
+
+    let (score, doing): (f64, u32) = store
+        .with_connection(|connection| {
+            connection.query_row(
+                "SELECT rating_score, collection_doing FROM subjects WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+        })
+        .unwrap();
+    assert_eq!((score, doing), (7.5, 3));
+}
+
+#[test]
+fn test_entity_round_trip() {
+    let store = Store::open_in_memory().unwrap();
+
+    let person: PersonDetail = serde_json::from_value(person_json(7)).unwrap();
+    store.upsert_person(&person).unwrap();
+    assert_eq!(store.get_person(7).unwrap().unwrap().value.name, "花泽香菜");
+    let careers: Vec<String> = store
+        .with_connection(|connection| {
+            let mut statement = connection
+                .prepare("SELECT career FROM person_careers WHERE person_id = 7 ORDER BY career")?;
+            statement.query_map([], |row| row.get(0))?.collect()
+        })
+        .unwrap();
+    assert_eq!(careers, vec!["artist", "seiyu"]);
+
+    let character: Character = serde_json::from_value(character_json(3)).unwrap();
+    store.upsert_character(&character).unwrap();
+    assert_eq!(store.get_character(3).unwrap().unwrap().value.id, 3);
+
+    let episodes: Vec<Episode> = parse_all([fixture::episode(12, 2.0), fixture::episode(11, 1.0)]);
+    store.upsert_episodes(&episodes).unwrap();
+    assert_eq!(store.get_episode(11).unwrap().unwrap().value.sort, 1.0);
+    // 单独写入的剧集不构成完整列表
+    assert!(store.get_subject_episodes(1).unwrap().is_none());
+    store.upsert_subject_episodes(1, &episodes).unwrap();
+    let ids: Vec<u32> = store
+        .get_subject_episodes(1)
+        .unwrap()
+        .unwrap()
+        .value
+        .iter()
+        .map(|episode| episode.id)
+        .collect();
+    assert_eq!(ids, vec![11, 12]);
+
+    let related: Vec<SubjectSubject> =
+        parse_all([fixture::related(2, "续集"), fixture::related(3, "前传")]);
+    store.upsert_subject_subjects(1, &related).unwrap();
+    store.upsert_subject_subjects(1, &related[..1]).unwrap();
+    let cached = store.get_subject_subjects(1).unwrap().unwrap();
+    assert_eq!(cached.value.len(), 1);
+    assert_eq!(cached.value[0].relation, "续集");
+    assert!(store.get_subject_persons(1).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_cached_client() {
+    let server = MockServer::start(vec![
+        MockRoute::json(
+            "GET",
+            "/v0/subjects/1",
+            200,
+            subject_json(1, "first").json(),
+        ),
+        MockRoute::json(
+            "GET",
+            "/v0/episodes",
+            200,
+            serde_json::json!({
+                "total": 2,
+                "limit": 100,
+                "offset": 0,
+                "data": [fixture::episode(11, 1.0), fixture::episode(12, 2.0)]
+            }),
+        ),
+    ]);
+    let client = CachedClient::new(server.client(), Store::open_in_memory().unwrap(), None);
+
+    for _ in 0..2 {
+        assert_eq!(client.get_subject(1).await.unwrap().name, "first");
+        assert_eq!(client.get_subject_episodes(1).await.unwrap().len(), 2);
+    }
+    assert_eq!(server.requests().len(), 2);
+
+    // 完整剧集列表写入后，单集也能从镜像读取
+    assert_eq!(client.get_episode(12).await.unwrap().sort, 2.0);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_cached_client_max_age() {
+    let server = MockServer::start(vec![MockRoute::json(
+        "GET",
+        "/v0/subjects/1",
+        200,
+        subject_json(1, "first").json(),
+    )]);
+    let client = CachedClient::new(
+        server.client(),
+        Store::open_in_memory().unwrap(),
+        Some(3600),
+    );
+
+    client.get_subject(1).await.unwrap();
+    client.get_subject(1).await.unwrap();
+    assert_eq!(server.requests().len(), 1);
+
+    // 数据过期后重新请求接口
+    client
+        .store
+        .with_connection(|connection| {
+            connection.execute("UPDATE subjects SET last_fetched = last_fetched - 7200", [])
+        })
+        .unwrap();
+    client.get_subject(1).await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_cached_client_coalesces_misses() {
+    let server = MockServer::start(vec![MockRoute::json(
+        "GET",
+        "/v0/subjects/1",
+        200,
+        subject_json(1, "first").json(),
+    )]);
+    let client = CachedClient::new(server.client(), Store::open_in_memory().unwrap(), None);
+
+    // 镜像未命中的相同并发请求只访问一次接口
+    let (first, second, third) = tokio::join!(
+        client.get_subject(1),
+        client.get_subject(1),
+        client.get_subject(1),
+    );
+    assert_eq!(first.unwrap().name, "first");
+    assert_eq!(second.unwrap().name, third.unwrap().name);
+    assert_eq!(server.requests().len(), 1);
+}
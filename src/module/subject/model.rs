@@ -271,7 +271,7 @@ pub enum SubjectBookCategory {
 /// 动画类条目的子分类枚举
 ///
 /// 基于u16存储，细分动画类型
-#[derive(Debug, Clone, Serialize_repr, Deserialize_repr)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
 #[repr(u16)]
 pub enum SubjectAnimeCategory {
     Other = 0, // 其他动画类型
@@ -378,3 +378,256 @@ pub struct SubjectSubject {
     /// 与当前条目的关系描述
     pub relation: String,
 }
+
+/// 生成带有多语言标签的关系枚举
+///
+/// 每个变体对应一个中文原始标签（可附带别称）以及英文、日文标签，
+/// 无法识别的标签保存在 `Other` 变体中，序列化时还原为原始字符串
+macro_rules! labeled_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident => ($cn:literal $(| $alias:literal)*, $en:literal, $ja:literal),
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+            /// 未收录的标签（保存原始字符串）
+            Other(String),
+        }
+
+        impl $name {
+            /// 由中文标签解析，兼容前后空白及常见别称
+            pub fn from_label(label: &str) -> Self {
+                match label.trim() {
+                    $($cn $(| $alias)* => $name::$variant,)*
+                    other => $name::Other(other.to_string()),
+                }
+            }
+
+            /// 中文标签（与 Bangumi API 返回的字符串一致）
+            pub fn label_cn(&self) -> &str {
+                match self {
+                    $($name::$variant => $cn,)*
+                    $name::Other(label) => label,
+                }
+            }
+
+            /// 英文标签
+            pub fn label_en(&self) -> &str {
+                match self {
+                    $($name::$variant => $en,)*
+                    $name::Other(label) => label,
+                }
+            }
+
+            /// 日文标签
+            pub fn label_ja(&self) -> &str {
+                match self {
+                    $($name::$variant => $ja,)*
+                    $name::Other(label) => label,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(label: &str) -> Self {
+                $name::from_label(label)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(label: String) -> Self {
+                $name::from_label(&label)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.label_cn().to_string()
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.label_cn())
+            }
+        }
+    };
+}
+
+labeled_enum! {
+    /// 条目之间的关系枚举
+    ///
+    /// 对应 `SubjectSubject.relation` 中的关系描述
+    pub enum SubjectRelation {
+        /// 改编
+        Adaptation => ("改编", "Adaptation", "改編"),
+        /// 前传
+        Prequel => ("前传" | "前作", "Prequel", "前日譚"),
+        /// 续集
+        Sequel => ("续集" | "续作", "Sequel", "続編"),
+        /// 总集篇
+        Summary => ("总集篇", "Summary", "総集編"),
+        /// 全集
+        FullStory => ("全集", "Full Story", "全集"),
+        /// 番外篇
+        SideStory => ("番外篇" | "番外" | "外传", "Side Story", "番外編"),
+        /// 角色出演
+        Character => ("角色出演", "Character", "キャラクター出演"),
+        /// 相同世界观
+        SameSetting => ("相同世界观", "Same Setting", "同じ世界観"),
+        /// 不同世界观
+        AlternativeSetting => ("不同世界观", "Alternative Setting", "異なる世界観"),
+        /// 不同演绎
+        AlternativeVersion => ("不同演绎", "Alternative Version", "別バージョン"),
+        /// 衍生
+        SpinOff => ("衍生", "Spin-off", "スピンオフ"),
+        /// 主线故事
+        ParentStory => ("主线故事" | "主线", "Parent Story", "本編"),
+        /// 联动
+        Collaboration => ("联动", "Collaboration", "コラボレーション"),
+        /// 不同版本
+        AlternativeEdition => ("不同版本", "Alternative Edition", "別バージョン版"),
+        /// 系列
+        Series => ("系列", "Series", "シリーズ"),
+        /// 单行本
+        Offprint => ("单行本", "Offprint", "単行本"),
+        /// 画集
+        Artbook => ("画集", "Artbook", "画集"),
+        /// 原声集
+        Soundtrack => ("原声集", "Soundtrack", "サウンドトラック"),
+        /// 角色歌
+        CharacterSong => ("角色歌", "Character Song", "キャラクターソング"),
+        /// 片头曲
+        OpeningSong => ("片头曲", "Opening Song", "オープニング曲"),
+        /// 片尾曲
+        EndingSong => ("片尾曲", "Ending Song", "エンディング曲"),
+        /// 插入歌
+        InsertSong => ("插入歌", "Insert Song", "挿入歌"),
+        /// 印象曲
+        ImageSong => ("印象曲", "Image Song", "イメージソング"),
+        /// 广播剧
+        DramaCd => ("广播剧", "Drama CD", "ドラマCD"),
+        /// 扩展包
+        Expansion => ("扩展包", "Expansion", "拡張パック"),
+    }
+}
+
+labeled_enum! {
+    /// 制作人员职位枚举
+    ///
+    /// 对应 `SubjectPerson.relation` 中的职位描述
+    pub enum StaffPosition {
+        /// 原作
+        OriginalCreator => ("原作", "Original Creator", "原作"),
+        /// 导演
+        Director => ("导演" | "监督", "Director", "監督"),
+        /// 脚本
+        Script => ("脚本", "Script", "脚本"),
+        /// 分镜
+        Storyboard => ("分镜", "Storyboard", "絵コンテ"),
+        /// 演出
+        EpisodeDirector => ("演出", "Episode Director", "演出"),
+        /// 音乐
+        Music => ("音乐", "Music", "音楽"),
+        /// 人物原案
+        OriginalCharacterDesign => ("人物原案", "Original Character Design", "キャラクター原案"),
+        /// 人物设定
+        CharacterDesign => ("人物设定", "Character Design", "キャラクターデザイン"),
+        /// 系列构成
+        SeriesComposition => ("系列构成", "Series Composition", "シリーズ構成"),
+        /// 美术监督
+        ArtDirector => ("美术监督", "Art Director", "美術監督"),
+        /// 色彩设计
+        ColorDesign => ("色彩设计", "Color Design", "色彩設計"),
+        /// 总作画监督
+        ChiefAnimationDirector => ("总作画监督", "Chief Animation Director", "総作画監督"),
+        /// 作画监督
+        AnimationDirector => ("作画监督", "Animation Director", "作画監督"),
+        /// 摄影监督
+        DirectorOfPhotography => ("摄影监督", "Director of Photography", "撮影監督"),
+        /// 音响监督
+        SoundDirector => ("音响监督", "Sound Director", "音響監督"),
+        /// 剪辑
+        Editing => ("剪辑", "Editing", "編集"),
+        /// 原画
+        KeyAnimation => ("原画", "Key Animation", "原画"),
+        /// 机械设定
+        MechanicalDesign => ("机械设定", "Mechanical Design", "メカニックデザイン"),
+        /// 动画制作
+        AnimationProduction => ("动画制作", "Animation Production", "アニメーション制作"),
+        /// 制片人
+        Producer => ("制片人", "Producer", "プロデューサー"),
+        /// 主题歌演出
+        ThemeSongPerformance => ("主题歌演出", "Theme Song Performance", "主題歌歌唱"),
+        /// 主题歌作词
+        ThemeSongLyrics => ("主题歌作词", "Theme Song Lyrics", "主題歌作詞"),
+        /// 主题歌作曲
+        ThemeSongComposition => ("主题歌作曲", "Theme Song Composition", "主題歌作曲"),
+        /// 作者
+        Author => ("作者", "Author", "著者"),
+        /// 插图
+        Illustration => ("插图", "Illustration", "イラスト"),
+        /// 出版社
+        Publisher => ("出版社", "Publisher", "出版社"),
+        /// 连载杂志
+        Magazine => ("连载杂志", "Serialized In", "連載誌"),
+        /// 艺术家
+        Artist => ("艺术家", "Artist", "アーティスト"),
+        /// 作曲
+        Composer => ("作曲", "Composer", "作曲"),
+        /// 作词
+        Lyricist => ("作词", "Lyricist", "作詞"),
+        /// 编曲
+        Arranger => ("编曲", "Arranger", "編曲"),
+        /// 开发
+        Developer => ("开发", "Developer", "開発"),
+        /// 发行
+        Distributor => ("发行", "Distributor", "発売元"),
+    }
+}
+
+labeled_enum! {
+    /// 角色在条目中的定位枚举
+    ///
+    /// 对应 `SubjectCharacter.relation` 中的角色描述
+    pub enum CharacterRole {
+        /// 主角
+        Main => ("主角", "Main", "メイン"),
+        /// 配角
+        Supporting => ("配角", "Supporting", "サブ"),
+        /// 客串
+        Guest => ("客串", "Guest", "ゲスト"),
+    }
+}
+
+impl SubjectSubject {
+    /// 获取与当前条目的类型化关系
+    pub fn relation_type(&self) -> SubjectRelation {
+        SubjectRelation::from_label(&self.relation)
+    }
+}
+
+impl SubjectPerson {
+    /// 获取人物在条目中的类型化职位
+    pub fn position(&self) -> StaffPosition {
+        StaffPosition::from_label(&self.relation)
+    }
+}
+
+impl SubjectCharacter {
+    /// 获取角色在条目中的类型化定位
+    pub fn role(&self) -> CharacterRole {
+        CharacterRole::from_label(&self.relation)
+    }
+}
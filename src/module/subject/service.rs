@@ -1,9 +1,9 @@
 use anyhow::Result;
 use bytes::Bytes;
-use reqwest::Method;
+use http::Method;
 
 use crate::{
-    common::model::BangumiClient,
+    common::model::{ApiResponse, BangumiClient},
     module::model::{ImageType, Paged},
 };
 
@@ -24,7 +24,7 @@ impl BangumiClient {
 
         let request_builder = self.request_builder(Method::GET, &url);
 
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -59,7 +59,7 @@ impl BangumiClient {
 
         let request_builder = request_builder.json(&payload);
 
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -94,7 +94,7 @@ impl BangumiClient {
         offset: Option<u32>,
     ) -> Result<Paged<Subject>> {
         let url = format!("{}/v0/subjects", self.base_path);
-        let mut req_builder = self.request_builder(reqwest::Method::GET, &url);
+        let mut req_builder = self.request_builder(Method::GET, &url);
 
         req_builder = req_builder.query(&[("type", &r#type)]);
         if let Some(ref param_value) = cat {
@@ -122,7 +122,7 @@ impl BangumiClient {
             req_builder = req_builder.query(&[("offset", &param_value)]);
         }
 
-        let res = self.request_send(req_builder).await?.json().await?;
+        let res = self.request_send(req_builder).await?.json()?;
         Ok(res)
     }
 
@@ -136,11 +136,20 @@ impl BangumiClient {
     /// # 返回
     /// 成功返回番剧条目详情，失败返回错误
     pub async fn get_subject(&self, subject_id: u32) -> Result<Subject> {
+        let res = self.get_subject_with_meta(subject_id).await?.data;
+
+        Ok(res)
+    }
+
+    /// 获取单个番剧条目详情，并返回响应元信息
+    ///
+    /// 与 [`BangumiClient::get_subject`] 相同，额外保留状态码、响应头、耗时与请求ID
+    pub async fn get_subject_with_meta(&self, subject_id: u32) -> Result<ApiResponse<Subject>> {
         let url = format!("{}/v0/subjects/{subject_id}", self.base_path);
 
         let request_builder = self.request_builder(Method::GET, &url);
 
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.send_with_meta(request_builder).await?;
 
         Ok(res)
     }
@@ -162,7 +171,7 @@ impl BangumiClient {
 
         request_builder = request_builder.query(&[("type", &r#type)]);
 
-        let res = self.request_send(request_builder).await?.bytes().await?;
+        let res = self.request_send(request_builder).await?.bytes();
 
         Ok(res)
     }
@@ -181,7 +190,7 @@ impl BangumiClient {
 
         let request_builder = self.request_builder(Method::GET, &url);
 
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -200,7 +209,7 @@ impl BangumiClient {
 
         let request_builder = self.request_builder(Method::GET, &url);
 
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -219,7 +228,7 @@ impl BangumiClient {
 
         let request_builder = self.request_builder(Method::GET, &url);
 
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
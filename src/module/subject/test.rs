@@ -3,7 +3,8 @@ use crate::{
     module::{
         model::ImageType,
         subject::model::{
-            SubjectAnimeCategory, SubjectBrowseSort, SubjectCategory, SubjectSearch, SubjectType,
+            CharacterRole, StaffPosition, SubjectAnimeCategory, SubjectBrowseSort, SubjectCategory,
+            SubjectRelation, SubjectSearch, SubjectSubject, SubjectType,
         },
     },
 };
@@ -105,3 +106,42 @@ async fn test_get_subject_subjects() {
     let result = client.get_subject_subjects(1024).await;
     assert!(result.is_ok(), "获取关联条目失败: {:?}", result.err());
 }
+
+#[test]
+fn test_subject_relation_labels() {
+    assert_eq!(SubjectRelation::from_label("续集"), SubjectRelation::Sequel);
+    assert_eq!(
+        SubjectRelation::from_label(" 外传 "),
+        SubjectRelation::SideStory
+    );
+    assert_eq!(SubjectRelation::Sequel.label_en(), "Sequel");
+    assert_eq!(SubjectRelation::Sequel.label_ja(), "続編");
+    assert_eq!(
+        SubjectRelation::from_label("未知关系"),
+        SubjectRelation::Other("未知关系".to_string())
+    );
+    assert_eq!(StaffPosition::from("导演"), StaffPosition::Director);
+    assert_eq!(StaffPosition::Director.label_en(), "Director");
+    assert_eq!(CharacterRole::from("主角"), CharacterRole::Main);
+    assert_eq!(CharacterRole::Guest.to_string(), "客串");
+}
+
+#[test]
+fn test_subject_relation_serde() {
+    let related: SubjectSubject = serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "type": 2,
+        "name": "name",
+        "name_cn": "name_cn",
+        "images": {"large": "", "common": "", "medium": "", "small": "", "grid": ""},
+        "relation": "前传"
+    }))
+    .unwrap();
+    assert_eq!(related.relation_type(), SubjectRelation::Prequel);
+
+    let relation: SubjectRelation = serde_json::from_str("\"番外篇\"").unwrap();
+    assert_eq!(relation, SubjectRelation::SideStory);
+    assert_eq!(serde_json::to_string(&relation).unwrap(), "\"番外篇\"");
+    let other: SubjectRelation = serde_json::from_str("\"联动\"").unwrap();
+    assert_eq!(serde_json::to_string(&other).unwrap(), "\"联动\"");
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::module::collection::model::{
+    CollectionEpisodeType, CollectionEpisodesUpdate, CollectionSubjectUpdate, CollectionType,
+};
+
+/// 本地与远端收藏冲突时的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// 以远端（Bangumi）为准
+    RemoteWins,
+    /// 以本地为准
+    LocalWins,
+    /// 以更新时间较晚的一方为准，时间相同时以远端为准
+    LatestUpdated,
+}
+
+/// 单个条目的收藏快照
+///
+/// 同时记录条目收藏信息与各剧集的观看状态，用于比较本地与远端的差异
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncRecord {
+    /// 条目ID
+    pub subject_id: u32,
+    /// 收藏类型
+    pub r#type: CollectionType,
+    /// 评分（0表示未评分）
+    pub rate: u32,
+    /// 剧集进度
+    pub ep_status: u32,
+    /// 卷进度
+    pub vol_status: u32,
+    /// 评论（可选）
+    pub comment: Option<String>,
+    /// 标签列表
+    pub tags: Vec<String>,
+    /// 是否私密
+    pub private: bool,
+    /// 最后更新时间（Unix时间戳，秒）
+    pub updated_at: i64,
+    /// 剧集观看状态（剧集ID到收藏状态的映射）
+    pub episodes: BTreeMap<u32, CollectionEpisodeType>,
+}
+
+/// 一组条目收藏快照
+///
+/// 本地保存的收藏副本与从远端拉取的收藏均以此表示
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncState {
+    /// 条目ID到收藏快照的映射
+    pub records: BTreeMap<u32, SyncRecord>,
+}
+
+/// 需要写入远端的同步操作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SyncOperation {
+    /// 远端尚无该收藏，需要创建（POST）
+    CreateSubject {
+        /// 条目ID
+        subject_id: u32,
+        /// 收藏内容
+        payload: CollectionSubjectUpdate,
+    },
+    /// 更新远端收藏中发生变化的字段（PATCH）
+    PatchSubject {
+        /// 条目ID
+        subject_id: u32,
+        /// 仅包含变化字段的更新内容
+        payload: CollectionSubjectUpdate,
+    },
+    /// 批量更新远端剧集观看状态（PATCH）
+    PatchEpisodes {
+        /// 条目ID
+        subject_id: u32,
+        /// 同一目标状态的剧集列表
+        payload: CollectionEpisodesUpdate,
+    },
+}
+
+/// 同步计划
+///
+/// 包含需要写入远端的操作，以及需要写回本地的收藏快照
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncPlan {
+    /// 写入远端的操作
+    pub remote: Vec<SyncOperation>,
+    /// 写回本地的收藏快照
+    pub local: Vec<SyncRecord>,
+}
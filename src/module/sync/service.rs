@@ -0,0 +1,367 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+
+use crate::{
+    common::{model::BangumiClient, time::days_from_civil},
+    module::{
+        collection::model::{
+            CollectionEpisode, CollectionEpisodeType, CollectionEpisodesUpdate, CollectionSubject,
+            CollectionSubjectUpdate,
+        },
+        subject::model::SubjectType,
+    },
+};
+
+use super::model::{ConflictPolicy, SyncOperation, SyncPlan, SyncRecord, SyncState};
+
+/// 解析RFC 3339格式的时间字符串
+///
+/// 支持`Z`及`+08:00`形式的时区偏移，忽略小数秒
+///
+/// # 返回
+/// 成功返回Unix时间戳（秒），格式不正确返回None
+pub fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (date, time) = value.split_once(['T', ' '])?;
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: i64 = date_parts.next()?.parse().ok()?;
+    let day: i64 = date_parts.next()?.parse().ok()?;
+
+    let (clock, offset) = if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+        (clock, 0)
+    } else if let Some(index) = time.rfind(['+', '-']) {
+        let (clock, zone) = time.split_at(index);
+        let sign = if zone.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = zone[1..].split_once(':').unwrap_or((&zone[1..], "0"));
+        let offset = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+        (clock, sign * offset)
+    } else {
+        (time, 0)
+    };
+
+    let mut clock_parts = clock.splitn(3, ':');
+    let hour: i64 = clock_parts.next()?.parse().ok()?;
+    let minute: i64 = clock_parts.next()?.parse().ok()?;
+    let second: i64 = clock_parts
+        .next()
+        .map(|second| second.split('.').next().unwrap_or_default())
+        .unwrap_or("0")
+        .parse()
+        .ok()?;
+
+    let days = days_from_civil(year, month, day);
+    Some(days * 86400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+impl SyncRecord {
+    /// 由远端的条目收藏与剧集收藏构建快照
+    ///
+    /// 更新时间取条目收藏与各剧集收藏中最晚的时间
+    pub fn from_remote(subject: &CollectionSubject, episodes: &[CollectionEpisode]) -> Self {
+        let updated_at = episodes
+            .iter()
+            .map(|episode| episode.updated_at as i64)
+            .chain(parse_timestamp(&subject.updated_at))
+            .max()
+            .unwrap_or_default();
+
+        SyncRecord {
+            subject_id: subject.subject_id,
+            r#type: subject.r#type.clone(),
+            rate: subject.rate,
+            ep_status: subject.ep_status,
+            vol_status: subject.vol_status,
+            comment: subject.comment.clone(),
+            tags: subject.tags.clone(),
+            private: subject.private,
+            updated_at,
+            episodes: episodes
+                .iter()
+                .map(|episode| (episode.episode.id, episode.r#type.clone()))
+                .collect(),
+        }
+    }
+
+    /// 比较两个快照的内容是否一致（忽略更新时间）
+    fn same_content(&self, other: &SyncRecord) -> bool {
+        self.r#type == other.r#type
+            && self.rate == other.rate
+            && self.ep_status == other.ep_status
+            && self.vol_status == other.vol_status
+            && self.comment.as_deref().unwrap_or_default()
+                == other.comment.as_deref().unwrap_or_default()
+            && self.tags == other.tags
+            && self.private == other.private
+            && episode_changes(&self.episodes, &other.episodes).is_empty()
+            && episode_changes(&other.episodes, &self.episodes).is_empty()
+    }
+}
+
+/// 计算需要写入远端的剧集状态变化
+///
+/// 远端缺失的剧集视为"未处理"，返回按目标状态分组的剧集ID
+fn episode_changes(
+    local: &BTreeMap<u32, CollectionEpisodeType>,
+    remote: &BTreeMap<u32, CollectionEpisodeType>,
+) -> Vec<(CollectionEpisodeType, Vec<u32>)> {
+    let mut groups: Vec<(CollectionEpisodeType, Vec<u32>)> = Vec::new();
+    for (episode_id, r#type) in local {
+        let current = remote
+            .get(episode_id)
+            .unwrap_or(&CollectionEpisodeType::Not);
+        if current == r#type {
+            continue;
+        }
+        match groups.iter_mut().find(|(target, _)| target == r#type) {
+            Some((_, ids)) => ids.push(*episode_id),
+            None => groups.push((r#type.clone(), vec![*episode_id])),
+        }
+    }
+    groups
+}
+
+/// 计算将本地快照写入远端所需的最少操作
+fn push_operations(local: &SyncRecord, remote: Option<&SyncRecord>) -> Vec<SyncOperation> {
+    let mut operations = Vec::new();
+    let subject_id = local.subject_id;
+
+    let Some(remote) = remote else {
+        operations.push(SyncOperation::CreateSubject {
+            subject_id,
+            payload: CollectionSubjectUpdate {
+                r#type: Some(local.r#type.clone()),
+                rate: Some(local.rate).filter(|rate| *rate > 0),
+                ep_status: Some(local.ep_status).filter(|ep| *ep > 0),
+                vol_status: Some(local.vol_status).filter(|vol| *vol > 0),
+                comment: local.comment.clone().filter(|comment| !comment.is_empty()),
+                private: Some(local.private).filter(|private| *private),
+                tags: Some(local.tags.clone()).filter(|tags| !tags.is_empty()),
+            },
+        });
+        operations.extend(
+            episode_changes(&local.episodes, &BTreeMap::new())
+                .into_iter()
+                .map(|(r#type, episode_id)| SyncOperation::PatchEpisodes {
+                    subject_id,
+                    payload: CollectionEpisodesUpdate { episode_id, r#type },
+                }),
+        );
+        return operations;
+    };
+
+    let local_comment = local.comment.as_deref().unwrap_or_default();
+    let remote_comment = remote.comment.as_deref().unwrap_or_default();
+    let payload = CollectionSubjectUpdate {
+        r#type: Some(local.r#type.clone()).filter(|r#type| *r#type != remote.r#type),
+        rate: Some(local.rate).filter(|rate| *rate != remote.rate),
+        ep_status: Some(local.ep_status).filter(|ep| *ep != remote.ep_status),
+        vol_status: Some(local.vol_status).filter(|vol| *vol != remote.vol_status),
+        comment: (local_comment != remote_comment).then(|| local_comment.to_string()),
+        private: Some(local.private).filter(|private| *private != remote.private),
+        tags: Some(local.tags.clone()).filter(|tags| *tags != remote.tags),
+    };
+    if payload != CollectionSubjectUpdate::default() {
+        operations.push(SyncOperation::PatchSubject {
+            subject_id,
+            payload,
+        });
+    }
+    operations.extend(
+        episode_changes(&local.episodes, &remote.episodes)
+            .into_iter()
+            .map(|(r#type, episode_id)| SyncOperation::PatchEpisodes {
+                subject_id,
+                payload: CollectionEpisodesUpdate { episode_id, r#type },
+            }),
+    );
+    operations
+}
+
+/// 比较本地与远端收藏，生成同步计划
+///
+/// - 仅存在于本地的收藏会在远端创建
+/// - 仅存在于远端的收藏会写回本地
+/// - 双方内容不一致时按冲突策略决定以哪一方为准，远端只写入变化的字段
+///
+/// # 参数
+/// - `local`: 本地收藏快照
+/// - `remote`: 远端收藏快照
+/// - `policy`: 冲突处理策略
+///
+/// # 返回
+/// 返回包含远端操作与本地更新的同步计划
+pub fn diff_collections(local: &SyncState, remote: &SyncState, policy: ConflictPolicy) -> SyncPlan {
+    let mut plan = SyncPlan::default();
+    let subject_ids: BTreeSet<u32> = local
+        .records
+        .keys()
+        .chain(remote.records.keys())
+        .copied()
+        .collect();
+
+    for subject_id in subject_ids {
+        match (
+            local.records.get(&subject_id),
+            remote.records.get(&subject_id),
+        ) {
+            (Some(local), None) => plan.remote.extend(push_operations(local, None)),
+            (None, Some(remote)) => plan.local.push(remote.clone()),
+            (Some(local), Some(remote)) => {
+                if local.same_content(remote) {
+                    continue;
+                }
+                let local_wins = match policy {
+                    ConflictPolicy::RemoteWins => false,
+                    ConflictPolicy::LocalWins => true,
+                    ConflictPolicy::LatestUpdated => local.updated_at > remote.updated_at,
+                };
+                if local_wins {
+                    plan.remote.extend(push_operations(local, Some(remote)));
+                } else {
+                    plan.local.push(remote.clone());
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    plan
+}
+
+impl BangumiClient {
+    /// 拉取用户收藏的同步快照
+    ///
+    /// # 参数
+    /// - `username`: 用户名
+    /// - `subject_type`: 可选，按条目类型筛选
+    /// - `include_episodes`: 是否拉取剧集观看状态（需认证，且`username`应为当前用户）
+    ///
+    /// # 返回
+    /// 返回远端收藏快照
+    pub async fn fetch_sync_state(
+        &self,
+        username: &str,
+        subject_type: Option<SubjectType>,
+        include_episodes: bool,
+    ) -> Result<SyncState> {
+        let subjects = BangumiClient::fetch_all(|limit, offset| {
+            self.get_collection_subjects(
+                username,
+                subject_type.clone(),
+                None,
+                Some(limit),
+                Some(offset),
+            )
+        })
+        .await?;
+
+        let mut state = SyncState::default();
+        for subject in subjects {
+            let mut episodes = Vec::new();
+            if include_episodes
+                && matches!(subject.subject_type, SubjectType::Anime | SubjectType::Real)
+            {
+                episodes = BangumiClient::fetch_all(|limit, offset| {
+                    self.get_collection_episodes(
+                        subject.subject_id,
+                        Some(offset),
+                        Some(limit),
+                        None,
+                    )
+                })
+                .await?;
+            }
+            state.records.insert(
+                subject.subject_id,
+                SyncRecord::from_remote(&subject, &episodes),
+            );
+        }
+
+        Ok(state)
+    }
+
+    /// 依次执行同步操作（需认证）
+    ///
+    /// # 参数
+    /// - `operations`: 同步计划中的远端操作
+    ///
+    /// # 返回
+    /// 全部操作成功返回空结果，任一操作失败立即返回错误
+    pub async fn apply_sync_operations(&self, operations: &[SyncOperation]) -> Result<()> {
+        for operation in operations {
+            match operation {
+                SyncOperation::CreateSubject {
+                    subject_id,
+                    payload,
+                } => {
+                    self.post_collection_subject(*subject_id, Some(payload.clone()))
+                        .await?
+                }
+                SyncOperation::PatchSubject {
+                    subject_id,
+                    payload,
+                } => {
+                    self.patch_collection_subject(*subject_id, Some(payload.clone()))
+                        .await?
+                }
+                SyncOperation::PatchEpisodes {
+                    subject_id,
+                    payload,
+                } => {
+                    self.patch_collection_episodes(*subject_id, Some(payload.clone()))
+                        .await?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 双向同步本地收藏与远端收藏（需认证）
+    ///
+    /// 拉取远端快照并与本地比较，将本地胜出的变化写入远端，
+    /// 再将远端胜出的收藏写回`local`。不同步剧集时保留本地的剧集状态
+    ///
+    /// # 参数
+    /// - `username`: 当前用户的用户名
+    /// - `local`: 本地收藏快照（同步后会被更新）
+    /// - `policy`: 冲突处理策略
+    /// - `include_episodes`: 是否同步剧集观看状态
+    ///
+    /// # 返回
+    /// 返回已执行的同步计划
+    pub async fn sync_collections(
+        &self,
+        username: &str,
+        local: &mut SyncState,
+        policy: ConflictPolicy,
+        include_episodes: bool,
+    ) -> Result<SyncPlan> {
+        let remote = self
+            .fetch_sync_state(username, None, include_episodes)
+            .await?;
+        // 不同步剧集时，比较前忽略本地的剧集状态
+        let plan = if include_episodes {
+            diff_collections(local, &remote, policy)
+        } else {
+            let mut view = local.clone();
+            for record in view.records.values_mut() {
+                record.episodes.clear();
+            }
+            diff_collections(&view, &remote, policy)
+        };
+
+        self.apply_sync_operations(&plan.remote).await?;
+        for record in &plan.local {
+            let mut record = record.clone();
+            if !include_episodes && let Some(existing) = local.records.get(&record.subject_id) {
+                record.episodes = existing.episodes.clone();
+            }
+            local.records.insert(record.subject_id, record);
+        }
+
+        Ok(plan)
+    }
+}
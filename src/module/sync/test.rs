@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    common::mock::{MockRoute, MockServer},
+    module::{
+        collection::model::{
+            CollectionEpisodeType, CollectionEpisodesUpdate, CollectionSubjectUpdate,
+            CollectionType,
+        },
+        sync::{
+            model::{ConflictPolicy, SyncOperation, SyncRecord, SyncState},
+            service::{diff_collections, parse_timestamp},
+        },
+    },
+};
+
+fn record(subject_id: u32, rate: u32, updated_at: i64) -> SyncRecord {
+    SyncRecord {
+        subject_id,
+        r#type: CollectionType::Doing,
+        rate,
+        ep_status: 3,
+        vol_status: 0,
+        comment: None,
+        tags: vec!["TV".to_string()],
+        private: false,
+        updated_at,
+        episodes: BTreeMap::from([
+            (11, CollectionEpisodeType::Done),
+            (12, CollectionEpisodeType::Done),
+            (13, CollectionEpisodeType::Done),
+        ]),
+    }
+}
+
+fn state(records: Vec<SyncRecord>) -> SyncState {
+    SyncState {
+        records: records.into_iter().map(|r| (r.subject_id, r)).collect(),
+    }
+}
+
+#[test]
+fn test_parse_timestamp() {
+    assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(0));
+    assert_eq!(
+        parse_timestamp("2024-03-01T08:00:00+08:00"),
+        Some(1709251200)
+    );
+    assert_eq!(
+        parse_timestamp("2024-02-29T23:59:59.123Z"),
+        Some(1709251199)
+    );
+    assert_eq!(parse_timestamp("not a date"), None);
+}
+
+#[test]
+fn test_diff_identical_is_empty() {
+    let local = state(vec![record(1, 8, 100)]);
+    let remote = state(vec![record(1, 8, 200)]);
+    let plan = diff_collections(&local, &remote, ConflictPolicy::LocalWins);
+    assert!(plan.remote.is_empty());
+    assert!(plan.local.is_empty());
+}
+
+#[test]
+fn test_diff_policies() {
+    let mut changed = record(1, 9, 300);
+    changed.episodes.insert(14, CollectionEpisodeType::Done);
+    changed.episodes.insert(11, CollectionEpisodeType::Dropped);
+    let local = state(vec![changed]);
+    let remote = state(vec![record(1, 8, 200)]);
+
+    let plan = diff_collections(&local, &remote, ConflictPolicy::LocalWins);
+    assert!(plan.local.is_empty());
+    assert_eq!(
+        plan.remote,
+        vec![
+            SyncOperation::PatchSubject {
+                subject_id: 1,
+                payload: CollectionSubjectUpdate {
+                    rate: Some(9),
+                    ..Default::default()
+                },
+            },
+            SyncOperation::PatchEpisodes {
+                subject_id: 1,
+                payload: CollectionEpisodesUpdate {
+                    episode_id: vec![11],
+                    r#type: CollectionEpisodeType::Dropped,
+                },
+            },
+            SyncOperation::PatchEpisodes {
+                subject_id: 1,
+                payload: CollectionEpisodesUpdate {
+                    episode_id: vec![14],
+                    r#type: CollectionEpisodeType::Done,
+                },
+            },
+        ]
+    );
+
+    let plan = diff_collections(&local, &remote, ConflictPolicy::RemoteWins);
+    assert!(plan.remote.is_empty());
+    assert_eq!(plan.local, vec![record(1, 8, 200)]);
+
+    let plan = diff_collections(&local, &remote, ConflictPolicy::LatestUpdated);
+    assert_eq!(plan.remote.len(), 3);
+
+    let older = state(vec![record(1, 9, 100)]);
+    let plan = diff_collections(&older, &remote, ConflictPolicy::LatestUpdated);
+    assert!(plan.remote.is_empty());
+    assert_eq!(plan.local.len(), 1);
+}
+
+#[test]
+fn test_diff_one_sided_records() {
+    let local = state(vec![record(1, 0, 100)]);
+    let remote = state(vec![record(2, 7, 100)]);
+    let plan = diff_collections(&local, &remote, ConflictPolicy::RemoteWins);
+
+    assert_eq!(plan.local, vec![record(2, 7, 100)]);
+    assert!(matches!(
+        &plan.remote[0],
+        SyncOperation::CreateSubject { subject_id: 1, payload }
+            if payload.rate.is_none() && payload.r#type == Some(CollectionType::Doing)
+    ));
+    assert_eq!(plan.remote.len(), 2);
+}
+
+#[tokio::test]
+async fn test_sync_collections_with_mock_server() {
+    let server = MockServer::start(vec![
+        MockRoute::json(
+            "GET",
+            "/v0/users/sai/collections",
+            200,
+            serde_json::json!({
+                "total": 1,
+                "limit": 100,
+                "offset": 0,
+                "data": [{
+                    "subject_id": 1,
+                    "subject_type": 2,
+                    "rate": 8,
+                    "type": 3,
+                    "comment": null,
+                    "tags": ["TV"],
+                    "ep_status": 3,
+                    "vol_status": 0,
+                    "updated_at": "1970-01-01T00:03:20Z",
+                    "private": false,
+                    "subject": null
+                }]
+            }),
+        ),
+        MockRoute::json(
+            "PATCH",
+            "/v0/users/-/collections/1",
+            204,
+            serde_json::Value::Null,
+        ),
+    ]);
+    let client = server.client();
+
+    let mut local = state(vec![record(1, 9, 300)]);
+    let plan = client
+        .sync_collections("sai", &mut local, ConflictPolicy::LatestUpdated, false)
+        .await
+        .unwrap();
+
+    assert_eq!(plan.remote.len(), 1);
+    let requests = server.requests();
+    let patch = requests
+        .iter()
+        .find(|request| request.method == "PATCH")
+        .expect("未发送PATCH请求");
+    assert_eq!(patch.path, "/v0/users/-/collections/1");
+    assert_eq!(
+        serde_json::from_str::<CollectionSubjectUpdate>(&patch.body).unwrap(),
+        CollectionSubjectUpdate {
+            rate: Some(9),
+            ..Default::default()
+        }
+    );
+    assert_eq!(local.records[&1].episodes.len(), 3);
+}
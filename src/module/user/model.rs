@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserPublic {
     pub id: u32,
     pub url: String,
@@ -12,7 +12,7 @@ pub struct UserPublic {
     pub sign: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: u32,
     pub url: String,
@@ -26,14 +26,14 @@ pub struct User {
     pub time_offset: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Avatar {
     pub large: String,
     pub medium: String,
     pub small: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AvatarType {
     Small,
@@ -43,7 +43,7 @@ pub enum AvatarType {
 
 /// 用户组
 #[repr(u8)]
-#[derive(Debug, Serialize_repr, Deserialize_repr)]
+#[derive(Debug, Clone, Serialize_repr, Deserialize_repr)]
 pub enum UserGroup {
     Admin = 1,
     BangumiAdmin = 2,
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use bytes::Bytes;
-use reqwest::Method;
+use http::Method;
 
 use crate::common::model::BangumiClient;
 
@@ -12,7 +12,7 @@ impl BangumiClient {
 
         let request_builder = self.request_builder(Method::GET, &url);
 
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }
@@ -23,7 +23,7 @@ impl BangumiClient {
         let mut request_builder = self.request_builder(Method::GET, &url);
         request_builder = request_builder.query(&[("type", r#type)]);
 
-        let res = self.request_send(request_builder).await?.bytes().await?;
+        let res = self.request_send(request_builder).await?.bytes();
 
         Ok(res)
     }
@@ -33,7 +33,7 @@ impl BangumiClient {
 
         let request_builder = self.request_builder(Method::GET, &url);
 
-        let res = self.request_send(request_builder).await?.json().await?;
+        let res = self.request_send(request_builder).await?.json()?;
 
         Ok(res)
     }